@@ -0,0 +1,57 @@
+/* C declarations for the array descriptors in the mdarray `ffi` module. */
+
+#ifndef MDARRAY_H
+#define MDARRAY_H
+
+#include <stdbool.h>
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define MDARRAY_MAX_RANK 6
+
+/* Status code returned by the functions. */
+typedef enum mdarray_status {
+    MDARRAY_SUCCESS = 0,
+    MDARRAY_INVALID_RANK = 1,
+    MDARRAY_OVERFLOW = 2,
+} mdarray_status;
+
+/* Array descriptor for dense layout, similar to mdspan with layout_right. */
+typedef struct mdarray_dense_descriptor {
+    void *data;
+    size_t rank;
+    size_t extents[MDARRAY_MAX_RANK];
+} mdarray_dense_descriptor;
+
+/* Array descriptor for strided layout, similar to mdspan with layout_stride. */
+typedef struct mdarray_strided_descriptor {
+    void *data;
+    size_t rank;
+    size_t extents[MDARRAY_MAX_RANK];
+    ptrdiff_t strides[MDARRAY_MAX_RANK];
+} mdarray_strided_descriptor;
+
+mdarray_status mdarray_dense_len(const mdarray_dense_descriptor *desc, size_t *len);
+
+mdarray_status mdarray_dense_offset(const mdarray_dense_descriptor *desc, const size_t *index,
+                                    ptrdiff_t *offset);
+
+mdarray_status mdarray_dense_to_strided(const mdarray_dense_descriptor *desc,
+                                        mdarray_strided_descriptor *result);
+
+mdarray_status mdarray_strided_is_contiguous(const mdarray_strided_descriptor *desc,
+                                             bool *contiguous);
+
+mdarray_status mdarray_strided_len(const mdarray_strided_descriptor *desc, size_t *len);
+
+mdarray_status mdarray_strided_offset(const mdarray_strided_descriptor *desc, const size_t *index,
+                                      ptrdiff_t *offset);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* MDARRAY_H */
@@ -0,0 +1,307 @@
+//! Accessor policies, for custom element access in array views.
+//!
+//! An accessor is given as the last type parameter of `View` and `ViewMut`, and
+//! controls how elements are accessed when iterating over the view or using it in
+//! an expression. The default accessor `Plain` gives references to the elements,
+//! and array views with other accessors can be created with the methods below.
+//!
+//! - `Slice::scaled` gives elements multiplied with a scaling factor.
+//! - `Slice::conj` gives the complex conjugate of the elements.
+//! - `Slice::atomic` gives atomic types, for concurrent accumulation of elements.
+//!
+//! # Examples
+//!
+//! ```
+//! use mdarray::expr::Expression;
+//! use mdarray::tensor;
+//!
+//! let a = tensor![[1.0, 2.0], [3.0, 4.0]];
+//!
+//! assert_eq!(a.scaled(2.0).eval(), tensor![[2.0, 4.0], [6.0, 8.0]]);
+//! ```
+
+use core::ops::Mul;
+use core::sync::atomic::{AtomicI32, AtomicU32, Ordering};
+#[cfg(all(target_has_atomic = "64", target_pointer_width = "64"))]
+use core::sync::atomic::{AtomicI64, AtomicU64};
+
+use crate::layout::Layout;
+use crate::shape::Shape;
+use crate::slice::Slice;
+use crate::view::View;
+
+/// Trait for accessing elements in array views.
+pub trait Accessor<T> {
+    /// Element type when accessing elements in a shared array view.
+    type Reference<'a>
+    where
+        T: 'a;
+
+    /// Element type when accessing elements in a mutable array view.
+    type ReferenceMut<'a>
+    where
+        T: 'a;
+
+    /// Accesses the element at the given pointer.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must be valid for reads during the lifetime `'a`.
+    unsafe fn access<'a>(&self, ptr: *const T) -> Self::Reference<'a>
+    where
+        T: 'a;
+
+    /// Accesses the element at the given pointer, with mutable access.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must be valid for reads and writes during the lifetime `'a`, and
+    /// not aliased by other references.
+    unsafe fn access_mut<'a>(&self, ptr: *mut T) -> Self::ReferenceMut<'a>
+    where
+        T: 'a;
+}
+
+/// Accessor for atomic access to elements, for concurrent accumulation.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Atomic;
+
+/// Floating-point type `f32` with atomic operations.
+#[derive(Debug, Default)]
+#[repr(transparent)]
+pub struct AtomicF32(AtomicU32);
+
+/// Floating-point type `f64` with atomic operations.
+#[cfg(all(target_has_atomic = "64", target_pointer_width = "64"))]
+#[derive(Debug, Default)]
+#[repr(transparent)]
+pub struct AtomicF64(AtomicU64);
+
+/// Accessor for the complex conjugate of elements.
+///
+/// The accessor is implemented for complex numbers with the `num-complex` feature,
+/// and for real floating-point types where the conjugate is the value itself.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Conj;
+
+/// Default accessor, with references to the elements.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Plain;
+
+/// Accessor for elements multiplied with a scaling factor.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Scaled<T> {
+    factor: T,
+}
+
+impl<T, S: Shape, L: Layout> Slice<T, S, L> {
+    /// Returns an array view with atomic access to the elements.
+    ///
+    /// The array view can be shared between threads, and elements are accumulated
+    /// concurrently with atomic operations.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::atomic::Ordering;
+    /// use std::thread;
+    ///
+    /// use mdarray::tensor;
+    ///
+    /// let mut a = tensor![0.0f32, 0.0];
+    /// let v = a.atomic();
+    ///
+    /// thread::scope(|s| {
+    ///     for i in 0..4 {
+    ///         _ = s.spawn(move || _ = v.into_iter().nth(i % 2).unwrap().fetch_add(1.0, Ordering::Relaxed));
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(a, tensor![2.0, 2.0]);
+    /// ```
+    pub fn atomic(&mut self) -> View<'_, T, S, L, Atomic>
+    where
+        Atomic: Accessor<T>,
+    {
+        // The atomic accessor is valid since the array slice is mutably borrowed.
+        unsafe {
+            View::new_unchecked(self.as_mut_ptr(), self.mapping().clone()).with_accessor(Atomic)
+        }
+    }
+
+    /// Returns an array view with the complex conjugate of the elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mdarray::expr::Expression;
+    /// use mdarray::tensor;
+    ///
+    /// let a = tensor![1.0, -2.0];
+    ///
+    /// assert_eq!(a.conj().eval(), a);
+    /// ```
+    pub fn conj(&self) -> View<'_, T, S, L, Conj>
+    where
+        Conj: Accessor<T>,
+    {
+        unsafe { self.expr().with_accessor(Conj) }
+    }
+
+    /// Returns an array view with the elements multiplied with a scaling factor.
+    pub fn scaled(&self, factor: T) -> View<'_, T, S, L, Scaled<T>>
+    where
+        T: Copy + Mul<Output = T>,
+    {
+        unsafe { self.expr().with_accessor(Scaled::new(factor)) }
+    }
+}
+
+impl<T> Accessor<T> for Plain {
+    type Reference<'a>
+        = &'a T
+    where
+        T: 'a;
+
+    type ReferenceMut<'a>
+        = &'a mut T
+    where
+        T: 'a;
+
+    unsafe fn access<'a>(&self, ptr: *const T) -> &'a T {
+        unsafe { &*ptr }
+    }
+
+    unsafe fn access_mut<'a>(&self, ptr: *mut T) -> &'a mut T {
+        unsafe { &mut *ptr }
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Accessor<T> for Scaled<T> {
+    type Reference<'a>
+        = T
+    where
+        T: 'a;
+
+    type ReferenceMut<'a>
+        = T
+    where
+        T: 'a;
+
+    unsafe fn access<'a>(&self, ptr: *const T) -> Self::Reference<'a>
+    where
+        T: 'a,
+    {
+        self.factor * unsafe { *ptr }
+    }
+
+    unsafe fn access_mut<'a>(&self, ptr: *mut T) -> Self::ReferenceMut<'a>
+    where
+        T: 'a,
+    {
+        self.factor * unsafe { *ptr }
+    }
+}
+
+impl<T> Scaled<T> {
+    /// Returns the scaling factor.
+    pub fn factor(&self) -> &T {
+        &self.factor
+    }
+
+    /// Creates a new accessor with the given scaling factor.
+    pub fn new(factor: T) -> Self {
+        Self { factor }
+    }
+}
+
+macro_rules! impl_atomic_float {
+    ($name:tt, $float:tt, $atomic:tt) => {
+        impl $name {
+            /// Adds to the current value and returns the previous value.
+            pub fn fetch_add(&self, value: $float, order: Ordering) -> $float {
+                let f = |x| Some($float::to_bits($float::from_bits(x) + value));
+
+                $float::from_bits(self.0.fetch_update(order, Ordering::Relaxed, f).unwrap())
+            }
+
+            /// Creates a new atomic type from a pointer.
+            ///
+            /// # Safety
+            ///
+            /// The pointer must be valid for the lifetime `'a`, and all accesses must be
+            /// atomic during the lifetime.
+            pub unsafe fn from_ptr<'a>(ptr: *mut $float) -> &'a Self {
+                unsafe { &*ptr.cast::<Self>() }
+            }
+
+            /// Loads the value.
+            pub fn load(&self, order: Ordering) -> $float {
+                $float::from_bits(self.0.load(order))
+            }
+
+            /// Creates a new atomic type with the given value.
+            pub fn new(value: $float) -> Self {
+                Self($atomic::new(value.to_bits()))
+            }
+
+            /// Stores the value.
+            pub fn store(&self, value: $float, order: Ordering) {
+                self.0.store(value.to_bits(), order)
+            }
+        }
+    };
+}
+
+impl_atomic_float!(AtomicF32, f32, AtomicU32);
+#[cfg(all(target_has_atomic = "64", target_pointer_width = "64"))]
+impl_atomic_float!(AtomicF64, f64, AtomicU64);
+
+macro_rules! impl_atomic {
+    ($type:tt, $atomic:tt) => {
+        impl Accessor<$type> for Atomic {
+            type Reference<'a> = &'a $atomic;
+            type ReferenceMut<'a> = &'a $atomic;
+
+            unsafe fn access<'a>(&self, ptr: *const $type) -> &'a $atomic {
+                unsafe { $atomic::from_ptr(ptr.cast_mut()) }
+            }
+
+            unsafe fn access_mut<'a>(&self, ptr: *mut $type) -> &'a $atomic {
+                unsafe { $atomic::from_ptr(ptr) }
+            }
+        }
+    };
+}
+
+impl_atomic!(f32, AtomicF32);
+impl_atomic!(i32, AtomicI32);
+impl_atomic!(u32, AtomicU32);
+
+#[cfg(all(target_has_atomic = "64", target_pointer_width = "64"))]
+impl_atomic!(f64, AtomicF64);
+#[cfg(all(target_has_atomic = "64", target_pointer_width = "64"))]
+impl_atomic!(i64, AtomicI64);
+#[cfg(all(target_has_atomic = "64", target_pointer_width = "64"))]
+impl_atomic!(u64, AtomicU64);
+
+macro_rules! impl_conj_real {
+    ($($type:tt),+) => {
+        $(
+            impl Accessor<$type> for Conj {
+                type Reference<'a> = $type;
+                type ReferenceMut<'a> = $type;
+
+                unsafe fn access<'a>(&self, ptr: *const $type) -> Self::Reference<'a> {
+                    unsafe { *ptr }
+                }
+
+                unsafe fn access_mut<'a>(&self, ptr: *mut $type) -> Self::ReferenceMut<'a> {
+                    unsafe { *ptr }
+                }
+            }
+        )+
+    };
+}
+
+impl_conj_real!(f32, f64);
@@ -1,9 +1,9 @@
-use std::borrow::{Borrow, BorrowMut};
-use std::fmt::{Debug, Formatter, Result};
-use std::hash::{Hash, Hasher};
-use std::mem::{self, ManuallyDrop, MaybeUninit};
-use std::ops::{Deref, DerefMut, Index, IndexMut};
-use std::ptr;
+use core::borrow::{Borrow, BorrowMut};
+use core::fmt::{Debug, Formatter, Result};
+use core::hash::{Hash, Hasher};
+use core::mem::{self, ManuallyDrop, MaybeUninit};
+use core::ops::{Deref, DerefMut, Index, IndexMut};
+use core::ptr;
 
 use crate::dim::Const;
 use crate::expr::{self, IntoExpr, Iter, Map, Zip};
@@ -12,6 +12,7 @@ use crate::index::SliceIndex;
 use crate::layout::{Dense, Layout};
 use crate::shape::{ConstShape, Shape};
 use crate::slice::Slice;
+#[cfg(feature = "alloc")]
 use crate::tensor::Tensor;
 use crate::traits::Owned;
 use crate::view::{View, ViewMut};
@@ -226,6 +227,7 @@ impl<T, S: ConstShape> DerefMut for Array<T, S> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T, S: ConstShape> From<Tensor<T, S>> for Array<T, S> {
     fn from(value: Tensor<T, S>) -> Self {
         Self::from_expr(value.into_expr())
@@ -355,6 +357,7 @@ impl<T, S: ConstShape> IntoIterator for Array<T, S> {
 }
 
 impl<T, S: ConstShape> Owned<T, S> for Array<T, S> {
+    #[cfg(feature = "alloc")]
     type WithConst<const N: usize> = S::WithConst<T, N, Self>;
 
     fn clone_from_slice(&mut self, slice: &Slice<T, S>)
@@ -0,0 +1,165 @@
+use core::ops::{Add, Mul, Neg};
+
+use num_complex::Complex;
+
+use crate::accessor::{Accessor, Conj};
+use crate::dim::{Dim, Dims};
+use crate::expr::{Expression, Map};
+use crate::layout::{Layout, Strided};
+#[cfg(feature = "std")]
+use crate::linalg::Element;
+use crate::mapping::StridedMapping;
+use crate::shape::Shape;
+use crate::slice::Slice;
+use crate::view::{View, ViewMut};
+
+impl<T, S: Shape, L: Layout> Slice<Complex<T>, S, L> {
+    /// Replaces the elements with the complex conjugate.
+    pub fn conj_mut(&mut self)
+    where
+        T: Clone + Neg<Output = T>,
+    {
+        self.expr_mut().for_each(|x| *x = conj(x));
+    }
+
+    /// Returns an array view of the imaginary parts of the elements.
+    pub fn im(&self) -> View<'_, T, S, Strided> {
+        // If the view is empty, we must not offset the pointer.
+        let count = if self.is_empty() { 0 } else { 1 };
+
+        unsafe { View::new_unchecked(self.as_ptr().cast::<T>().add(count), self.part_mapping()) }
+    }
+
+    /// Returns a mutable array view of the imaginary parts of the elements.
+    pub fn im_mut(&mut self) -> ViewMut<'_, T, S, Strided> {
+        let mapping = self.part_mapping();
+
+        // If the view is empty, we must not offset the pointer.
+        let count = if self.is_empty() { 0 } else { 1 };
+
+        unsafe { ViewMut::new_unchecked(self.as_mut_ptr().cast::<T>().add(count), mapping) }
+    }
+
+    /// Returns an expression with the squared norm of the elements.
+    pub fn norm_sqr(&self) -> Map<View<'_, Complex<T>, S, L>, fn(&Complex<T>) -> T>
+    where
+        T: Clone + Add<Output = T> + Mul<Output = T>,
+    {
+        self.expr().map(norm_sqr as fn(&Complex<T>) -> T)
+    }
+
+    /// Returns an array view of the real parts of the elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mdarray::tensor;
+    /// use num_complex::Complex;
+    ///
+    /// let mut a = tensor![Complex::new(1.0, 2.0), Complex::new(3.0, 4.0)];
+    ///
+    /// a.im_mut().fill(0.0);
+    ///
+    /// assert_eq!(a.re(), tensor![1.0, 3.0]);
+    /// assert_eq!(a, tensor![Complex::new(1.0, 0.0), Complex::new(3.0, 0.0)]);
+    /// ```
+    pub fn re(&self) -> View<'_, T, S, Strided> {
+        unsafe { View::new_unchecked(self.as_ptr().cast::<T>(), self.part_mapping()) }
+    }
+
+    /// Returns a mutable array view of the real parts of the elements.
+    pub fn re_mut(&mut self) -> ViewMut<'_, T, S, Strided> {
+        let mapping = self.part_mapping();
+
+        unsafe { ViewMut::new_unchecked(self.as_mut_ptr().cast::<T>(), mapping) }
+    }
+
+    fn part_mapping(&self) -> StridedMapping<S> {
+        let mut strides = S::Dims::<isize>::new(self.rank());
+
+        // The complex type has the same layout as an array with the real and imaginary
+        // parts, so the strides in units of the parts are doubled.
+        for (i, stride) in strides.as_mut().iter_mut().enumerate() {
+            *stride = 2 * self.stride(i);
+        }
+
+        StridedMapping::new(self.shape().clone(), strides.as_ref())
+    }
+}
+
+impl<T, X: Dim, Y: Dim, L: Layout> Slice<Complex<T>, (X, Y), L> {
+    /// Returns an array view for the conjugate transpose of the matrix, without
+    /// copying elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mdarray::expr::Expression;
+    /// use mdarray::tensor;
+    /// use num_complex::Complex;
+    ///
+    /// let a = tensor![[Complex::new(1.0, 2.0), Complex::new(3.0, 4.0)]];
+    /// let b = a.conj_transpose().eval();
+    ///
+    /// assert_eq!(b, tensor![[Complex::new(1.0, -2.0)], [Complex::new(3.0, -4.0)]]);
+    /// ```
+    pub fn conj_transpose(&self) -> View<'_, Complex<T>, (Y, X), Strided, Conj>
+    where
+        T: Clone + Neg<Output = T>,
+    {
+        unsafe { self.reorder().into_mapping().with_accessor(Conj) }
+    }
+}
+
+impl<T: Clone + Neg<Output = T>> Accessor<Complex<T>> for Conj {
+    type Reference<'a>
+        = Complex<T>
+    where
+        T: 'a;
+
+    type ReferenceMut<'a>
+        = Complex<T>
+    where
+        T: 'a;
+
+    unsafe fn access<'a>(&self, ptr: *const Complex<T>) -> Complex<T>
+    where
+        T: 'a,
+    {
+        conj(unsafe { &*ptr })
+    }
+
+    unsafe fn access_mut<'a>(&self, ptr: *mut Complex<T>) -> Complex<T>
+    where
+        T: 'a,
+    {
+        conj(unsafe { &*ptr })
+    }
+}
+
+#[cfg(feature = "std")]
+macro_rules! impl_element {
+    ($($type:tt),+) => {
+        $(
+            impl Element for Complex<$type> {
+                const ZERO: Self = Complex::new(0.0, 0.0);
+                const ONE: Self = Complex::new(1.0, 0.0);
+
+                fn conj(self) -> Self {
+                    Complex::conj(&self)
+                }
+            }
+        )+
+    };
+}
+
+#[cfg(feature = "std")]
+impl_element!(f32, f64);
+
+fn conj<T: Clone + Neg<Output = T>>(x: &Complex<T>) -> Complex<T> {
+    Complex::new(x.re.clone(), -x.im.clone())
+}
+
+fn norm_sqr<T: Clone + Add<Output = T> + Mul<Output = T>>(x: &Complex<T>) -> T {
+    x.re.clone() * x.re.clone() + x.im.clone() * x.im.clone()
+}
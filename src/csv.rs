@@ -0,0 +1,298 @@
+//! CSV module, for reading and writing 2-dimensional arrays as delimited text.
+//!
+//! Each line is a row in the array, and the fields in a line are separated by the
+//! delimiter, which is a comma by default. Fields can be enclosed in double quotes,
+//! where a double quote inside the field is escaped by another double quote. A field
+//! in double quotes can also contain line breaks, and then continues on the next
+//! line. Empty lines are skipped, and whitespace around fields is trimmed.
+//!
+//! # Examples
+//!
+//! ```
+//! use mdarray::csv::{self, Reader};
+//! use mdarray::tensor;
+//!
+//! let t = Reader::new().skip_lines(1).read::<f64, _>("x,y\n1,2.5\n3,4\n".as_bytes()).unwrap();
+//!
+//! assert_eq!(t, tensor![[1.0, 2.5], [3.0, 4.0]]);
+//!
+//! let mut buffer = Vec::new();
+//!
+//! csv::write(&t, &mut buffer).unwrap();
+//!
+//! assert_eq!(buffer, b"1,2.5\n3,4\n");
+//! ```
+
+use std::boxed::Box;
+use std::error::Error as StdError;
+use std::fmt::{Display, Write as _};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::str::FromStr;
+use std::string::String;
+
+use thiserror::Error;
+
+use crate::allocator::Vec;
+use crate::dim::Dyn;
+use crate::layout::Layout;
+use crate::slice::DSlice;
+use crate::tensor::Tensor;
+
+/// Error type for reading delimited text.
+#[derive(Debug, Error)]
+pub enum CsvError {
+    /// Reading from the input failed.
+    #[error("I/O error")]
+    Io(#[from] io::Error),
+    /// A field could not be parsed, at the given line and column counted from 1.
+    #[error("parse error at line {line}, column {column}")]
+    Parse {
+        /// Line number, where the row starts.
+        line: usize,
+        /// Column number, i.e. the field index in the row.
+        column: usize,
+        /// Error from parsing the value, or `None` if the field is not valid.
+        source: Option<Box<dyn StdError + Send + Sync>>,
+    },
+    /// A row has a different length than the first row.
+    #[error("invalid row length {len} at line {line}, expected {expected}")]
+    RowLength {
+        /// Line number, where the row starts.
+        line: usize,
+        /// Number of fields in the row.
+        len: usize,
+        /// Number of fields in the first row.
+        expected: usize,
+    },
+}
+
+/// Reader for delimited text, with options for the delimiter and header lines.
+#[derive(Clone, Copy, Debug)]
+pub struct Reader {
+    delimiter: char,
+    skip_lines: usize,
+}
+
+/// Writer for delimited text, with an option for the delimiter.
+#[derive(Clone, Copy, Debug)]
+pub struct Writer {
+    delimiter: char,
+}
+
+impl Reader {
+    /// Sets the delimiter between fields.
+    pub fn delimiter(self, delimiter: char) -> Self {
+        Self { delimiter, ..self }
+    }
+
+    /// Creates a new reader with comma as delimiter and no header lines.
+    pub fn new() -> Self {
+        Self { delimiter: ',', skip_lines: 0 }
+    }
+
+    /// Reads delimited text into a 2-dimensional array.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading from the input fails, if a field cannot be parsed
+    /// or if the rows have different lengths.
+    pub fn read<T: FromStr<Err: StdError + Send + Sync + 'static>, R: Read>(
+        &self,
+        reader: R,
+    ) -> Result<Tensor<T, (Dyn, Dyn)>, CsvError> {
+        let mut reader = BufReader::new(reader);
+        let mut buf = String::new();
+        let mut fields = Vec::new();
+
+        let mut vec = Vec::new();
+        let mut shape = (0, 0);
+        let mut line = 0;
+
+        loop {
+            buf.clear();
+
+            if reader.read_line(&mut buf)? == 0 {
+                break;
+            }
+
+            line += 1;
+
+            if line <= self.skip_lines || buf.trim().is_empty() {
+                continue;
+            }
+
+            let start = line;
+
+            fields.clear();
+
+            // Read more lines while a field in double quotes continues on the next line.
+            while !self.split(buf.trim_end_matches(['\n', '\r']), start, &mut fields)? {
+                if reader.read_line(&mut buf)? == 0 {
+                    return Err(CsvError::Parse {
+                        line: start,
+                        column: fields.len() + 1,
+                        source: None,
+                    });
+                }
+
+                line += 1;
+                fields.clear();
+            }
+
+            if shape.0 == 0 {
+                shape.1 = fields.len();
+            } else if fields.len() != shape.1 {
+                let (len, expected) = (fields.len(), shape.1);
+
+                return Err(CsvError::RowLength { line: start, len, expected });
+            }
+
+            for (i, field) in fields.iter().enumerate() {
+                let value = field.parse().map_err(|e: T::Err| CsvError::Parse {
+                    line: start,
+                    column: i + 1,
+                    source: Some(Box::new(e)),
+                })?;
+
+                vec.push(value);
+            }
+
+            shape.0 += 1;
+        }
+
+        Ok(Tensor::from(vec).into_shape(shape))
+    }
+
+    /// Sets the number of header lines to skip before reading the array.
+    pub fn skip_lines(self, skip_lines: usize) -> Self {
+        Self { skip_lines, ..self }
+    }
+
+    // Splits a row into fields, and returns false if the last field is in double quotes
+    // and continues on the next line.
+    fn split(&self, text: &str, line: usize, fields: &mut Vec<String>) -> Result<bool, CsvError> {
+        let mut chars = text.chars().peekable();
+
+        loop {
+            let mut field = String::new();
+
+            while chars.next_if(|&c| c != self.delimiter && c.is_whitespace()).is_some() {}
+
+            if chars.next_if_eq(&'"').is_some() {
+                loop {
+                    match chars.next() {
+                        Some('"') if chars.next_if_eq(&'"').is_some() => field.push('"'),
+                        Some('"') => break,
+                        Some(c) => field.push(c),
+                        None => return Ok(false),
+                    }
+                }
+
+                while chars.next_if(|&c| c != self.delimiter && c.is_whitespace()).is_some() {}
+
+                if chars.peek().is_some_and(|&c| c != self.delimiter) {
+                    return Err(CsvError::Parse { line, column: fields.len() + 1, source: None });
+                }
+            } else {
+                while let Some(c) = chars.next_if(|&c| c != self.delimiter) {
+                    field.push(c);
+                }
+
+                field.truncate(field.trim_end().len());
+            }
+
+            fields.push(field);
+
+            if chars.next().is_none() {
+                return Ok(true);
+            }
+        }
+    }
+}
+
+impl Default for Reader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Writer {
+    /// Sets the delimiter between fields.
+    pub fn delimiter(self, delimiter: char) -> Self {
+        Self { delimiter }
+    }
+
+    /// Creates a new writer with comma as delimiter.
+    pub fn new() -> Self {
+        Self { delimiter: ',' }
+    }
+
+    /// Writes a 2-dimensional array as delimited text, with one line per row.
+    ///
+    /// Fields that contain the delimiter, a double quote or a line break are
+    /// enclosed in double quotes, so that they are read back as the same text.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to the output fails.
+    pub fn write<T: Display, L: Layout, W: Write>(
+        &self,
+        slice: &DSlice<T, 2, L>,
+        writer: W,
+    ) -> io::Result<()> {
+        let mut writer = io::BufWriter::new(writer);
+        let mut field = String::new();
+
+        for row in slice.rows() {
+            for (i, x) in row.iter().enumerate() {
+                if i > 0 {
+                    write!(writer, "{}", self.delimiter)?;
+                }
+
+                field.clear();
+
+                _ = write!(field, "{}", x);
+
+                if field.contains([self.delimiter, '"', '\n', '\r']) {
+                    write!(writer, "\"{}\"", field.replace('"', "\"\""))?;
+                } else {
+                    writer.write_all(field.as_bytes())?;
+                }
+            }
+
+            writeln!(writer)?;
+        }
+
+        writer.flush()
+    }
+}
+
+impl Default for Writer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Reads comma-separated text into a 2-dimensional array.
+///
+/// # Errors
+///
+/// Returns an error if reading from the input fails, if a field cannot be parsed
+/// or if the rows have different lengths.
+pub fn read<T: FromStr<Err: StdError + Send + Sync + 'static>, R: Read>(
+    reader: R,
+) -> Result<Tensor<T, (Dyn, Dyn)>, CsvError> {
+    Reader::new().read(reader)
+}
+
+/// Writes a 2-dimensional array as comma-separated text, with one line per row.
+///
+/// # Errors
+///
+/// Returns an error if writing to the output fails.
+pub fn write<T: Display, L: Layout, W: Write>(
+    slice: &DSlice<T, 2, L>,
+    writer: W,
+) -> io::Result<()> {
+    Writer::new().write(slice, writer)
+}
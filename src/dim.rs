@@ -1,8 +1,15 @@
-use std::fmt::{self, Debug, Formatter};
-use std::hash::Hash;
-
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::vec;
+use core::fmt::{self, Debug, Formatter};
+use core::hash::Hash;
+
+#[cfg(feature = "alloc")]
 use crate::shape::Shape;
+#[cfg(feature = "alloc")]
 use crate::tensor::Tensor;
+#[cfg(feature = "alloc")]
 use crate::traits::Owned;
 
 /// Array dimension trait.
@@ -10,6 +17,7 @@ pub trait Dim: Copy + Debug + Default + Hash + Ord + Send + Sync {
     /// Merge dimensions, where constant size is preferred over dynamic.
     type Merge<D: Dim>: Dim;
 
+    #[cfg(feature = "alloc")]
     #[doc(hidden)]
     type Owned<T, S: Shape>: Owned<T, S::Prepend<Self>>;
 
@@ -58,6 +66,7 @@ impl<const N: usize> Debug for Const<N> {
 
 impl<const N: usize> Dim for Const<N> {
     type Merge<D: Dim> = Self;
+    #[cfg(feature = "alloc")]
     type Owned<T, S: Shape> = <S::Owned<T> as Owned<T, S>>::WithConst<N>;
 
     const SIZE: Option<usize> = Some(N);
@@ -75,6 +84,7 @@ impl<const N: usize> Dim for Const<N> {
 
 impl Dim for Dyn {
     type Merge<D: Dim> = D;
+    #[cfg(feature = "alloc")]
     type Owned<T, S: Shape> = Tensor<T, S::Prepend<Self>>;
 
     const SIZE: Option<usize> = None;
@@ -104,6 +114,7 @@ macro_rules! impl_dims {
 
 impl_dims!(0, 1, 2, 3, 4, 5, 6);
 
+#[cfg(feature = "alloc")]
 impl<T: Copy + Debug + Default + Eq + Hash + Send + Sync> Dims<T> for Box<[T]> {
     fn new(len: usize) -> Self {
         vec![T::default(); len].into()
@@ -0,0 +1,563 @@
+//! DLPack module, for zero-copy exchange of arrays with other frameworks.
+//!
+//! The types in this module follow the C definitions in `dlpack.h`. An array slice
+//! can be borrowed as a `DLTensor` with `DLTensorRef`, and an array can be moved into
+//! a `ManagedTensor` that is released with its deleter when the consumer is done.
+//!
+//! # Examples
+//!
+//! ```
+//! use mdarray::dlpack::{DLTensorRef, ManagedTensor};
+//! use mdarray::{Strided, View, tensor};
+//!
+//! let t = tensor![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]];
+//! let s = t.view(.., 1..);
+//! let r = DLTensorRef::from(&*s);
+//!
+//! let v: View<f64, (usize, usize), Strided> = unsafe { r.as_dl_tensor().view().unwrap() };
+//!
+//! assert_eq!(v, s);
+//!
+//! let m = ManagedTensor::from(t.clone());
+//!
+//! assert_eq!(m.view::<f64, (usize, usize)>().unwrap(), t);
+//! ```
+
+use alloc::boxed::Box;
+use core::ffi::c_void;
+use core::marker::PhantomData;
+use core::mem;
+use core::ptr::{self, NonNull};
+
+use thiserror::Error;
+
+use crate::allocator::Allocator;
+use crate::dim::Dims;
+use crate::layout::{Layout, Strided};
+use crate::mapping::{Mapping, StridedMapping};
+use crate::shape::Shape;
+use crate::slice::Slice;
+use crate::tensor::Tensor;
+use crate::view::{View, ViewMut};
+
+/// Type code for the device where the array data is located.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[repr(transparent)]
+pub struct DLDeviceType(pub i32);
+
+/// Device where the array data is located.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[repr(C)]
+pub struct DLDevice {
+    /// Device type.
+    pub device_type: DLDeviceType,
+    /// Device index, which is zero for the CPU.
+    pub device_id: i32,
+}
+
+/// Type code for the array element type.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[repr(transparent)]
+pub struct DLDataTypeCode(pub u8);
+
+/// Array element type.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[repr(C)]
+pub struct DLDataType {
+    /// Type code.
+    pub code: DLDataTypeCode,
+    /// Number of bits per lane.
+    pub bits: u8,
+    /// Number of lanes, which is 1 for scalar types.
+    pub lanes: u16,
+}
+
+/// Array descriptor without ownership of the array data.
+///
+/// The shape and strides are in number of elements, and the strides pointer can
+/// be null for a compact array in row-major order.
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct DLTensor {
+    /// Pointer to the array data.
+    pub data: *mut c_void,
+    /// Device where the array data is located.
+    pub device: DLDevice,
+    /// Array rank.
+    pub ndim: i32,
+    /// Array element type.
+    pub dtype: DLDataType,
+    /// Pointer to the number of elements in each dimension.
+    pub shape: *mut i64,
+    /// Pointer to the distance between elements in each dimension, or null.
+    pub strides: *mut i64,
+    /// Offset in bytes from the data pointer to the first element.
+    pub byte_offset: u64,
+}
+
+/// Array descriptor with a deleter for releasing the array data.
+#[derive(Debug)]
+#[repr(C)]
+pub struct DLManagedTensor {
+    /// Array descriptor.
+    pub dl_tensor: DLTensor,
+    /// Context of the array owner.
+    pub manager_ctx: *mut c_void,
+    /// Function for releasing the array data, called by the consumer.
+    pub deleter: Option<unsafe extern "C" fn(*mut DLManagedTensor)>,
+}
+
+/// Borrowed DLPack array descriptor for an array slice.
+///
+/// The descriptor contains the shape and strides of the array slice, and is valid
+/// for the lifetime of the borrow.
+#[derive(Debug)]
+pub struct DLTensorRef<'a> {
+    tensor: DLTensor,
+    _shape: Box<[i64]>,
+    _strides: Box<[i64]>,
+    phantom: PhantomData<&'a ()>,
+}
+
+/// Owned DLPack array descriptor, which calls the deleter when dropped.
+#[derive(Debug)]
+pub struct ManagedTensor {
+    ptr: NonNull<DLManagedTensor>,
+}
+
+/// Error type for conversion from a DLPack array descriptor.
+#[derive(Debug, Error)]
+pub enum DLPackError {
+    /// The array data is not located on the CPU.
+    #[error("unsupported device")]
+    UnsupportedDevice(DLDevice),
+    /// The element type is not matching the array type.
+    #[error("data type mismatch")]
+    DataTypeMismatch(DLDataType),
+    /// The array rank is not matching the shape type.
+    #[error("invalid rank")]
+    InvalidRank(usize),
+    /// The dimensions are negative, not matching the shape type or the length overflows.
+    #[error("invalid shape")]
+    InvalidShape,
+    /// The data pointer is null or not aligned.
+    #[error("invalid pointer")]
+    InvalidPointer,
+    /// The strides are such that elements overlap in memory.
+    #[error("overlapping elements")]
+    OverlappingElements,
+}
+
+/// Trait for array element types that have a DLPack data type.
+///
+/// # Safety
+///
+/// The data type must match the size and representation of the element type.
+pub unsafe trait DataType {
+    /// DLPack data type for the element type.
+    const DATA_TYPE: DLDataType;
+}
+
+#[repr(C)]
+struct Context<T, S: Shape, A: Allocator> {
+    managed: DLManagedTensor,
+    shape: Box<[i64]>,
+    strides: Box<[i64]>,
+    tensor: Tensor<T, S, A>,
+}
+
+impl DLDeviceType {
+    /// CPU device.
+    pub const CPU: Self = Self(1);
+    /// CUDA GPU device.
+    pub const CUDA: Self = Self(2);
+    /// Pinned CUDA CPU memory.
+    pub const CUDA_HOST: Self = Self(3);
+    /// OpenCL device.
+    pub const OPENCL: Self = Self(4);
+    /// Vulkan buffer.
+    pub const VULKAN: Self = Self(7);
+    /// Metal device.
+    pub const METAL: Self = Self(8);
+    /// Verilog simulator buffer.
+    pub const VPI: Self = Self(9);
+    /// ROCm GPU device.
+    pub const ROCM: Self = Self(10);
+    /// Pinned ROCm CPU memory.
+    pub const ROCM_HOST: Self = Self(11);
+    /// Reserved extension device type.
+    pub const EXT_DEV: Self = Self(12);
+    /// CUDA managed memory.
+    pub const CUDA_MANAGED: Self = Self(13);
+    /// Unified shared memory for oneAPI.
+    pub const ONE_API: Self = Self(14);
+    /// WebGPU device.
+    pub const WEB_GPU: Self = Self(15);
+    /// Qualcomm Hexagon DSP.
+    pub const HEXAGON: Self = Self(16);
+}
+
+impl DLDevice {
+    /// CPU device, which is used for all arrays.
+    pub const CPU: Self = Self { device_type: DLDeviceType::CPU, device_id: 0 };
+}
+
+impl DLDataTypeCode {
+    /// Signed integer.
+    pub const INT: Self = Self(0);
+    /// Unsigned integer.
+    pub const UINT: Self = Self(1);
+    /// IEEE floating point.
+    pub const FLOAT: Self = Self(2);
+    /// Opaque handle.
+    pub const OPAQUE_HANDLE: Self = Self(3);
+    /// Brain floating point.
+    pub const BFLOAT: Self = Self(4);
+    /// Complex number, with real and imaginary parts of half the size.
+    pub const COMPLEX: Self = Self(5);
+    /// Boolean.
+    pub const BOOL: Self = Self(6);
+}
+
+impl DLDataType {
+    /// Creates a scalar data type with the given type code and number of bits.
+    pub const fn new(code: DLDataTypeCode, bits: u8) -> Self {
+        Self { code, bits, lanes: 1 }
+    }
+}
+
+impl DLTensor {
+    /// Creates an array view from the array descriptor.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the array data is not on the CPU, if the element type or
+    /// shape is not matching, or if the data pointer is null or not aligned.
+    ///
+    /// # Safety
+    ///
+    /// The array descriptor must be valid, and the array data must be valid for reading
+    /// and not modified during the lifetime of the array view.
+    pub unsafe fn view<'a, T: DataType, S: Shape>(
+        &self,
+    ) -> Result<View<'a, T, S, Strided>, DLPackError> {
+        let (ptr, mapping) = unsafe { self.parts::<T, S>()? };
+
+        Ok(unsafe { View::new_unchecked(ptr, mapping) })
+    }
+
+    /// Creates a mutable array view from the array descriptor.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the array data is not on the CPU, if the element type or
+    /// shape is not matching, or if the data pointer is null or not aligned.
+    ///
+    /// # Safety
+    ///
+    /// The array descriptor must be valid, and the array data must be valid for reading
+    /// and writing and not accessed through other pointers during the lifetime of the
+    /// array view. Elements must not overlap in memory.
+    pub unsafe fn view_mut<'a, T: DataType, S: Shape>(
+        &self,
+    ) -> Result<ViewMut<'a, T, S, Strided>, DLPackError> {
+        let (ptr, mapping) = unsafe { self.parts::<T, S>()? };
+
+        Ok(unsafe { ViewMut::new_unchecked(ptr, mapping) })
+    }
+
+    unsafe fn parts<T: DataType, S: Shape>(
+        &self,
+    ) -> Result<(*mut T, StridedMapping<S>), DLPackError> {
+        if self.device.device_type != DLDeviceType::CPU {
+            return Err(DLPackError::UnsupportedDevice(self.device));
+        }
+
+        if self.dtype != T::DATA_TYPE {
+            return Err(DLPackError::DataTypeMismatch(self.dtype));
+        }
+
+        let rank = usize::try_from(self.ndim).map_err(|_| DLPackError::InvalidShape)?;
+
+        if S::RANK.is_some_and(|n| n != rank) {
+            return Err(DLPackError::InvalidRank(rank));
+        }
+
+        let dims =
+            if rank > 0 { unsafe { core::slice::from_raw_parts(self.shape, rank) } } else { &[] };
+
+        let mut shape = S::new(rank);
+        let mut len = 1usize;
+
+        for (i, &dim) in dims.iter().enumerate() {
+            let size = usize::try_from(dim).map_err(|_| DLPackError::InvalidShape)?;
+
+            if S::static_dim(i).is_some_and(|n| n != size) {
+                return Err(DLPackError::InvalidShape);
+            }
+
+            len = len.checked_mul(size).ok_or(DLPackError::InvalidShape)?;
+        }
+
+        shape.with_mut_dims(|dst| {
+            for (x, &dim) in dst.iter_mut().zip(dims) {
+                *x = dim as usize;
+            }
+        });
+
+        let mut strides = S::Dims::<isize>::new(rank);
+
+        if self.strides.is_null() {
+            let mut stride = 1isize;
+
+            for i in (0..rank).rev() {
+                strides.as_mut()[i] = stride;
+                stride = stride.wrapping_mul(dims[i] as isize);
+            }
+        } else {
+            let src = if rank > 0 {
+                unsafe { core::slice::from_raw_parts(self.strides, rank) }
+            } else {
+                &[]
+            };
+
+            for (x, &stride) in strides.as_mut().iter_mut().zip(src) {
+                *x = isize::try_from(stride).map_err(|_| DLPackError::InvalidShape)?;
+            }
+        }
+
+        let ptr = if self.data.is_null() {
+            if len > 0 || self.byte_offset > 0 {
+                return Err(DLPackError::InvalidPointer);
+            }
+
+            NonNull::dangling().as_ptr()
+        } else {
+            let offset =
+                usize::try_from(self.byte_offset).map_err(|_| DLPackError::InvalidPointer)?;
+
+            unsafe { self.data.cast::<u8>().add(offset).cast::<T>() }
+        };
+
+        if !ptr.is_aligned() {
+            return Err(DLPackError::InvalidPointer);
+        }
+
+        Ok((ptr, StridedMapping::new(shape, strides.as_ref())))
+    }
+}
+
+impl ManagedTensor {
+    /// Returns a pointer to the array descriptor.
+    pub fn as_ptr(&self) -> *const DLManagedTensor {
+        self.ptr.as_ptr()
+    }
+
+    /// Returns a mutable pointer to the array descriptor.
+    pub fn as_mut_ptr(&mut self) -> *mut DLManagedTensor {
+        self.ptr.as_ptr()
+    }
+
+    /// Creates an owned array descriptor from a raw pointer.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must be non-null and point to a valid array descriptor, and the
+    /// ownership must be transferred to the returned value.
+    pub unsafe fn from_raw(ptr: *mut DLManagedTensor) -> Self {
+        Self { ptr: unsafe { NonNull::new_unchecked(ptr) } }
+    }
+
+    /// Returns a raw pointer to the array descriptor, and transfers the ownership
+    /// to the caller.
+    ///
+    /// The consumer must call the deleter to release the array data.
+    pub fn into_raw(self) -> *mut DLManagedTensor {
+        let ptr = self.ptr.as_ptr();
+
+        mem::forget(self);
+
+        ptr
+    }
+
+    /// Returns an array view of the array data.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the array data is not on the CPU, if the element type or
+    /// shape is not matching, or if the data pointer is null or not aligned.
+    pub fn view<T: DataType, S: Shape>(&self) -> Result<View<'_, T, S, Strided>, DLPackError> {
+        unsafe { self.ptr.as_ref().dl_tensor.view() }
+    }
+
+    /// Returns a mutable array view of the array data.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the array data is not on the CPU, if the element type or
+    /// shape is not matching, if the data pointer is null or not aligned, or if
+    /// elements overlap in memory.
+    pub fn view_mut<T: DataType, S: Shape>(
+        &mut self,
+    ) -> Result<ViewMut<'_, T, S, Strided>, DLPackError> {
+        let (ptr, mapping) = unsafe { self.ptr.as_ref().dl_tensor.parts::<T, S>()? };
+
+        if is_overlapping(&mapping) {
+            return Err(DLPackError::OverlappingElements);
+        }
+
+        Ok(unsafe { ViewMut::new_unchecked(ptr, mapping) })
+    }
+}
+
+impl DLTensorRef<'_> {
+    /// Returns the array descriptor.
+    pub fn as_dl_tensor(&self) -> &DLTensor {
+        &self.tensor
+    }
+
+    /// Returns a pointer to the array descriptor.
+    pub fn as_ptr(&self) -> *const DLTensor {
+        &self.tensor
+    }
+}
+
+impl<'a, T: DataType, S: Shape, L: Layout> From<&'a Slice<T, S, L>> for DLTensorRef<'a> {
+    fn from(value: &'a Slice<T, S, L>) -> Self {
+        let mut shape = dims(value);
+        let mut strides = strides(value);
+
+        let tensor = dl_tensor(value.as_ptr() as *mut T, value, &mut shape, &mut strides);
+
+        Self { tensor, _shape: shape, _strides: strides, phantom: PhantomData }
+    }
+}
+
+impl<'a, T: DataType, S: Shape, L: Layout> From<&'a mut Slice<T, S, L>> for DLTensorRef<'a> {
+    fn from(value: &'a mut Slice<T, S, L>) -> Self {
+        let mut shape = dims(value);
+        let mut strides = strides(value);
+
+        let tensor = dl_tensor(value.as_mut_ptr(), value, &mut shape, &mut strides);
+
+        Self { tensor, _shape: shape, _strides: strides, phantom: PhantomData }
+    }
+}
+
+impl<T: DataType, S: Shape, A: Allocator + 'static> From<Tensor<T, S, A>> for ManagedTensor {
+    fn from(value: Tensor<T, S, A>) -> Self {
+        let managed = DLManagedTensor {
+            dl_tensor: dl_tensor(ptr::null_mut::<T>(), &value, &mut [], &mut []),
+            manager_ctx: ptr::null_mut(),
+            deleter: Some(deleter::<T, S, A>),
+        };
+
+        let shape = dims(&value);
+        let strides = strides(&value);
+
+        let context = Box::into_raw(Box::new(Context { managed, shape, strides, tensor: value }));
+
+        unsafe {
+            let managed = &raw mut (*context).managed;
+
+            (*managed).dl_tensor.data = (*context).tensor.as_mut_ptr().cast();
+            (*managed).dl_tensor.shape = (*context).shape.as_mut_ptr();
+            (*managed).dl_tensor.strides = (*context).strides.as_mut_ptr();
+            (*managed).manager_ctx = context.cast();
+
+            Self { ptr: NonNull::new_unchecked(managed) }
+        }
+    }
+}
+
+impl Drop for ManagedTensor {
+    fn drop(&mut self) {
+        unsafe {
+            if let Some(deleter) = self.ptr.as_ref().deleter {
+                deleter(self.ptr.as_ptr());
+            }
+        }
+    }
+}
+
+macro_rules! impl_data_type {
+    ($type:ty, $code:ident) => {
+        unsafe impl DataType for $type {
+            const DATA_TYPE: DLDataType =
+                DLDataType::new(DLDataTypeCode::$code, 8 * mem::size_of::<$type>() as u8);
+        }
+    };
+}
+
+impl_data_type!(bool, BOOL);
+impl_data_type!(f32, FLOAT);
+impl_data_type!(f64, FLOAT);
+impl_data_type!(i8, INT);
+impl_data_type!(i16, INT);
+impl_data_type!(i32, INT);
+impl_data_type!(i64, INT);
+impl_data_type!(isize, INT);
+impl_data_type!(u8, UINT);
+impl_data_type!(u16, UINT);
+impl_data_type!(u32, UINT);
+impl_data_type!(u64, UINT);
+impl_data_type!(usize, UINT);
+
+unsafe extern "C" fn deleter<T, S: Shape, A: Allocator>(ptr: *mut DLManagedTensor) {
+    unsafe {
+        _ = Box::from_raw((*ptr).manager_ctx.cast::<Context<T, S, A>>());
+    }
+}
+
+fn dims<T, S: Shape, L: Layout>(slice: &Slice<T, S, L>) -> Box<[i64]> {
+    slice.shape().with_dims(|dims| dims.iter().map(|&x| x as i64).collect())
+}
+
+fn dl_tensor<T: DataType, S: Shape, L: Layout>(
+    ptr: *mut T,
+    slice: &Slice<T, S, L>,
+    shape: &mut [i64],
+    strides: &mut [i64],
+) -> DLTensor {
+    DLTensor {
+        data: ptr.cast(),
+        device: DLDevice::CPU,
+        ndim: slice.rank() as i32,
+        dtype: T::DATA_TYPE,
+        shape: shape.as_mut_ptr(),
+        strides: strides.as_mut_ptr(),
+        byte_offset: 0,
+    }
+}
+
+// Checks if elements overlap in memory, which is the case if the stride for some dimension
+// is less than the extent of the dimensions with smaller strides.
+fn is_overlapping<S: Shape>(mapping: &StridedMapping<S>) -> bool {
+    if mapping.is_empty() {
+        return false;
+    }
+
+    let mut axes = S::Dims::<(usize, usize)>::new(mapping.rank());
+
+    for (i, x) in axes.as_mut().iter_mut().enumerate() {
+        *x = (mapping.stride(i).unsigned_abs(), mapping.dim(i));
+    }
+
+    axes.as_mut().sort_unstable();
+
+    let mut extent = 1usize;
+
+    for &(stride, size) in axes.as_ref().iter().filter(|&&(_, size)| size > 1) {
+        if stride < extent {
+            return true;
+        }
+
+        extent = extent.saturating_add(stride.saturating_mul(size - 1));
+    }
+
+    false
+}
+
+fn strides<T, S: Shape, L: Layout>(slice: &Slice<T, S, L>) -> Box<[i64]> {
+    (0..slice.rank()).map(|i| slice.stride(i) as i64).collect()
+}
@@ -0,0 +1,21 @@
+use thiserror::Error;
+
+/// Error type for array operations that change the array shape.
+#[derive(Debug, Error)]
+pub enum TensorError {
+    /// The default array length for the layout mapping is not zero.
+    #[error("default length not zero")]
+    DefaultLength(usize),
+    /// The array rank is not valid for the operation.
+    #[error("invalid rank")]
+    InvalidRank(usize),
+    /// The first dimension is not dynamically-sized.
+    #[error("first dimension not dynamically-sized")]
+    FirstDimNotDyn(usize),
+    /// The dimension for the axis is not dynamically-sized.
+    #[error("dimension not dynamically-sized")]
+    DimNotDyn(usize),
+    /// The array length exceeds the capacity.
+    #[error("capacity exceeded")]
+    CapacityExceeded(usize),
+}
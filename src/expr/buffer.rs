@@ -1,17 +1,21 @@
-#[cfg(feature = "nightly")]
-use std::alloc::{Allocator, Global};
-use std::mem::ManuallyDrop;
-use std::ptr;
+use core::mem::ManuallyDrop;
+#[cfg(feature = "alloc")]
+use core::ptr;
 
-#[cfg(not(feature = "nightly"))]
-use crate::alloc::{Allocator, Global};
+#[cfg(feature = "alloc")]
+use crate::allocator::{Allocator, Global};
 use crate::array::Array;
+#[cfg(feature = "alloc")]
 use crate::dim::Const;
+#[cfg(feature = "alloc")]
 use crate::index::Axis;
+#[cfg(feature = "alloc")]
 use crate::mapping::Mapping;
 use crate::shape::{ConstShape, Shape};
 use crate::slice::Slice;
+#[cfg(feature = "alloc")]
 use crate::tensor::Tensor;
+#[cfg(feature = "alloc")]
 use crate::view::ViewMut;
 
 /// Array buffer trait, for moving elements out of an array.
@@ -30,6 +34,7 @@ pub trait Buffer {
 }
 
 /// Buffer for moving elements out of an array range.
+#[cfg(feature = "alloc")]
 pub struct Drain<'a, T, S: Shape, A: Allocator = Global> {
     tensor: &'a mut Tensor<T, S, A>,
     view: ViewMut<'a, ManuallyDrop<T>, S>,
@@ -37,6 +42,7 @@ pub struct Drain<'a, T, S: Shape, A: Allocator = Global> {
     tail: usize,
 }
 
+#[cfg(feature = "alloc")]
 impl<'a, T, S: Shape, A: Allocator> Drain<'a, T, S, A> {
     pub(crate) fn new(tensor: &'a mut Tensor<T, S, A>, start: usize, end: usize) -> Self {
         assert!(start <= end && end <= tensor.dim(0), "invalid range");
@@ -58,6 +64,7 @@ impl<'a, T, S: Shape, A: Allocator> Drain<'a, T, S, A> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T, S: Shape, A: Allocator> Buffer for Drain<'_, T, S, A> {
     type Item = T;
     type Shape = S;
@@ -71,6 +78,7 @@ impl<T, S: Shape, A: Allocator> Buffer for Drain<'_, T, S, A> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T, S: Shape, A: Allocator> Drop for Drain<'_, T, S, A> {
     fn drop(&mut self) {
         let mapping = Mapping::resize_dim(self.tensor.mapping(), 0, self.new_size);
@@ -95,6 +103,7 @@ impl<T, S: ConstShape> Buffer for Array<ManuallyDrop<T>, S> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T, S: Shape, A: Allocator> Buffer for Tensor<ManuallyDrop<T>, S, A> {
     type Item = T;
     type Shape = S;
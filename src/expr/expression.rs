@@ -1,12 +1,17 @@
-#[cfg(feature = "nightly")]
-use std::alloc::Allocator;
+use core::mem::MaybeUninit;
 
-#[cfg(not(feature = "nightly"))]
-use crate::alloc::Allocator;
+#[cfg(feature = "alloc")]
+use crate::allocator::{Allocator, Vec};
+#[cfg(feature = "alloc")]
+use crate::error::TensorError;
 use crate::expr::adapters::{Cloned, Copied, Enumerate, Map, Zip};
 use crate::expr::iter::Iter;
+use crate::layout::Layout;
 use crate::shape::Shape;
+use crate::slice::Slice;
+#[cfg(feature = "alloc")]
 use crate::tensor::Tensor;
+#[cfg(feature = "alloc")]
 use crate::traits::IntoCloned;
 
 /// Trait for applying a closure and returning an existing array or an expression.
@@ -77,6 +82,7 @@ pub trait Expression: IntoIterator {
     /// The resulting type is `Array` if the shape has constant-sized dimensions, or
     /// otherwise `Tensor`. If the shape type is generic, `FromExpression::from_expr`
     /// can be used to evaluate the expression into a specific array type.
+    #[cfg(feature = "alloc")]
     fn eval(self) -> <Self::Shape as Shape>::Owned<Self::Item>
     where
         Self: Sized,
@@ -89,19 +95,40 @@ pub trait Expression: IntoIterator {
     ///
     /// If the array is empty, it is reshaped to match the shape of the expression.
     ///
+    /// # Errors
+    ///
+    /// Returns an error if the rank is not at least 1, or if the first dimension
+    /// is not dynamically-sized.
+    ///
     /// # Panics
     ///
-    /// Panics if the inner dimensions do not match, if the rank is not the same and
-    /// at least 1, or if the first dimension is not dynamically-sized.
+    /// Panics if the inner dimensions do not match, or if the rank is not the same.
+    #[cfg(feature = "alloc")]
     fn eval_into<S: Shape, A: Allocator>(
         self,
         tensor: &mut Tensor<Self::Item, S, A>,
-    ) -> &mut Tensor<Self::Item, S, A>
+    ) -> Result<&mut Tensor<Self::Item, S, A>, TensorError>
+    where
+        Self: Sized,
+    {
+        tensor.expand(self)?;
+
+        Ok(tensor)
+    }
+
+    /// Evaluates the expression with broadcasting and writes the elements into the given
+    /// array slice with uninitialized elements.
+    ///
+    /// Note that existing elements in the array slice are overwritten without being dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the expression cannot be broadcast to the shape of the array slice.
+    fn eval_into_uninit<S: Shape, L: Layout>(self, slice: &mut Slice<MaybeUninit<Self::Item>, S, L>)
     where
         Self: Sized,
     {
-        tensor.expand(self);
-        tensor
+        slice.expr_mut().zip(self).for_each(|(x, y)| _ = x.write(y));
     }
 
     /// Folds all elements into an accumulator by applying an operation, and returns the result.
@@ -167,7 +194,7 @@ pub trait Expression: IntoIterator {
     #[doc(hidden)]
     unsafe fn step_dim(&mut self, index: usize);
 
-    #[cfg(not(feature = "nightly"))]
+    #[cfg(all(feature = "alloc", not(any(feature = "nightly", feature = "allocator-api2"))))]
     #[doc(hidden)]
     fn clone_into_vec<T>(self, vec: &mut Vec<T>)
     where
@@ -181,7 +208,7 @@ pub trait Expression: IntoIterator {
         });
     }
 
-    #[cfg(feature = "nightly")]
+    #[cfg(all(feature = "alloc", any(feature = "nightly", feature = "allocator-api2")))]
     #[doc(hidden)]
     fn clone_into_vec<T, A: Allocator>(self, vec: &mut Vec<T, A>)
     where
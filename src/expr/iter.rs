@@ -1,5 +1,5 @@
-use std::fmt::{Debug, Formatter, Result};
-use std::iter::FusedIterator;
+use core::fmt::{Debug, Formatter, Result};
+use core::iter::FusedIterator;
 
 use crate::dim::Dims;
 use crate::expr::expression::Expression;
@@ -20,7 +20,12 @@ impl<E: Expression> Iter<E> {
         let outer_rank = expr.rank().saturating_sub(expr.inner_rank());
 
         let inner_index = 0;
-        let inner_limit = expr.shape().with_dims(|dims| dims[outer_rank..].iter().product());
+        // If an outer dimension is zero, there are no elements also in the inner dimensions.
+        let inner_limit = if expr.is_empty() {
+            0
+        } else {
+            expr.shape().with_dims(|dims| dims[outer_rank..].iter().product())
+        };
 
         let mut outer_index = Default::default();
         let mut outer_limit = Default::default();
@@ -8,13 +8,16 @@ mod iter;
 mod sources;
 
 pub use adapters::{Cloned, Copied, Enumerate, Map, Zip, cloned, copied, enumerate, map, zip};
-pub use buffer::{Buffer, Drain};
+pub use buffer::Buffer;
 pub use expression::{Apply, Expression, FromExpression, IntoExpression};
 pub use into_expr::IntoExpr;
 pub use iter::Iter;
-pub use sources::{AxisExpr, AxisExprMut, Lanes, LanesMut};
+pub use sources::{AxisExpr, AxisExprMut, Lanes, LanesMut, Pad, PadMode, pad};
 pub use sources::{Fill, FillWith, FromElem, FromFn, fill, fill_with, from_elem, from_fn};
 
+#[cfg(feature = "alloc")]
+pub use buffer::Drain;
+
 /// Folds all elements of the argument into an accumulator by applying an operation,
 /// and returns the result.
 ///
@@ -1,5 +1,6 @@
-use std::fmt::{Debug, Formatter, Result};
+use core::fmt::{Debug, Formatter, Result};
 
+use crate::dim::Dims;
 use crate::expr::expression::Expression;
 use crate::expr::iter::Iter;
 use crate::index::{Axis, Keep, Split};
@@ -68,6 +69,28 @@ pub struct LanesMut<'a, T, S: Shape, L: Layout, A: Axis> {
     offset: isize,
 }
 
+/// Expression that pads an array slice along each dimension.
+pub struct Pad<'a, T, S: Shape, L: Layout> {
+    slice: &'a Slice<T, S, L>,
+    shape: S::Dyn,
+    before: S::Dims<usize>,
+    mode: PadMode<T>,
+    index: S::Dims<usize>,
+}
+
+/// Padding mode, for elements outside of the array slice.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum PadMode<T> {
+    /// Pad with a constant value.
+    Constant(T),
+    /// Pad by repeating the edge elements.
+    Edge,
+    /// Pad by reflecting the elements around the edges, excluding the edge elements.
+    Reflect,
+    /// Pad by wrapping around periodically.
+    Wrap,
+}
+
 /// Creates an expression with elements by cloning `value`.
 ///
 /// # Examples
@@ -350,7 +373,9 @@ impl<S: Shape, F> FromFn<S, F> {
     pub(crate) fn new(shape: S, f: F) -> Self {
         _ = shape.checked_len().expect("invalid length");
 
-        Self { shape, f, index: S::Dims::default() }
+        let index = S::Dims::new(shape.rank());
+
+        Self { shape, f, index }
     }
 }
 
@@ -405,6 +430,36 @@ impl<T, S: Shape, F: FnMut(&[usize]) -> T> IntoIterator for FromFn<S, F> {
     }
 }
 
+/// Creates an expression that pads an array slice with the given mode.
+///
+/// The padding is given as the number of elements before and after for each dimension.
+///
+/// # Examples
+///
+/// ```
+/// use mdarray::expr::{self, Expression, PadMode};
+/// use mdarray::view;
+///
+/// let v = view![1, 2, 3];
+///
+/// assert_eq!(expr::pad(&v, &[(2, 1)], PadMode::Constant(0)).eval(), view![0, 0, 1, 2, 3, 0]);
+/// assert_eq!(expr::pad(&v, &[(2, 1)], PadMode::Edge).eval(), view![1, 1, 1, 2, 3, 3]);
+/// assert_eq!(expr::pad(&v, &[(2, 1)], PadMode::Reflect).eval(), view![3, 2, 1, 2, 3, 2]);
+/// assert_eq!(expr::pad(&v, &[(2, 1)], PadMode::Wrap).eval(), view![2, 3, 1, 2, 3, 1]);
+/// ```
+///
+/// # Panics
+///
+/// Panics if the padding length is not equal to the rank, if the resulting length
+/// overflows, or if a dimension to be padded is empty and the mode is not constant.
+pub fn pad<'a, T: Clone, S: Shape, L: Layout>(
+    slice: &'a Slice<T, S, L>,
+    padding: &[(usize, usize)],
+    mode: PadMode<T>,
+) -> Pad<'a, T, S, L> {
+    Pad::new(slice, padding, mode)
+}
+
 macro_rules! impl_lanes {
     ($name:tt, $expr:tt, $as_ptr:tt, {$($mut:tt)?}, $repeatable:tt) => {
         impl<'a, T, S: Shape, L: Layout, A: Axis> $name<'a, T, S, L, A> {
@@ -439,7 +494,7 @@ macro_rules! impl_lanes {
             }
 
             unsafe fn get_unchecked(&mut self, index: usize) -> Self::Item {
-                let offset = self.mapping.inner_stride() * index as isize;
+                let offset = self.offset + self.mapping.inner_stride() * index as isize;
                 let mapping = self.axis.get(self.slice.mapping());
 
                 // If the view is empty, we must not offset the pointer.
@@ -498,3 +553,144 @@ impl<T, S: Shape, L: Layout, A: Axis> Clone for Lanes<'_, T, S, L, A> {
         self.offset = source.offset;
     }
 }
+
+impl<'a, T, S: Shape, L: Layout> Pad<'a, T, S, L> {
+    pub(crate) fn new(
+        slice: &'a Slice<T, S, L>,
+        padding: &[(usize, usize)],
+        mode: PadMode<T>,
+    ) -> Self {
+        assert!(padding.len() == slice.rank(), "invalid rank");
+
+        let mut before = S::Dims::new(slice.rank());
+        let mut dims = S::Dims::new(slice.rank());
+
+        for (i, &(first, last)) in padding.iter().enumerate() {
+            let size = slice.dim(i);
+            let dim =
+                size.checked_add(first).and_then(|x| x.checked_add(last)).expect("invalid length");
+
+            if size == 0 && dim > 0 {
+                assert!(matches!(mode, PadMode::Constant(_)), "empty dimension");
+            }
+
+            before.as_mut()[i] = first;
+            dims.as_mut()[i] = dim;
+        }
+
+        let shape = S::Dyn::from_dims(dims.as_ref());
+
+        _ = shape.checked_len().expect("invalid length");
+
+        Self { slice, shape, before, mode, index: S::Dims::new(slice.rank()) }
+    }
+}
+
+impl<T: Clone, S: Shape, L: Layout> Clone for Pad<'_, T, S, L> {
+    fn clone(&self) -> Self {
+        Self {
+            slice: self.slice,
+            shape: self.shape.clone(),
+            before: self.before.clone(),
+            mode: self.mode.clone(),
+            index: self.index.clone(),
+        }
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        self.slice = source.slice;
+        self.shape.clone_from(&source.shape);
+        self.before.clone_from(&source.before);
+        self.mode.clone_from(&source.mode);
+        self.index.clone_from(&source.index);
+    }
+}
+
+impl<T: Debug, S: Shape, L: Layout> Debug for Pad<'_, T, S, L> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_tuple("Pad").field(&self.slice).field(&self.shape).field(&self.mode).finish()
+    }
+}
+
+impl<T: Clone, S: Shape, L: Layout> Expression for Pad<'_, T, S, L> {
+    type Shape = S::Dyn;
+
+    const IS_REPEATABLE: bool = true;
+
+    fn shape(&self) -> &S::Dyn {
+        &self.shape
+    }
+
+    unsafe fn get_unchecked(&mut self, _: usize) -> T {
+        let mut offset = 0;
+        let mut inside = true;
+
+        self.slice.mapping().for_each_stride(|i, stride| {
+            let size = self.slice.dim(i);
+            let index = self.index.as_ref()[i] as isize - self.before.as_ref()[i] as isize;
+
+            let index = if index >= 0 && index < size as isize {
+                index
+            } else {
+                match self.mode {
+                    PadMode::Constant(_) => {
+                        inside = false;
+
+                        0
+                    }
+                    PadMode::Edge => index.clamp(0, size as isize - 1),
+                    PadMode::Reflect => {
+                        let period = 2 * (size as isize - 1);
+
+                        if period > 0 {
+                            let index = index.rem_euclid(period);
+
+                            if index < size as isize { index } else { period - index }
+                        } else {
+                            0
+                        }
+                    }
+                    PadMode::Wrap => index.rem_euclid(size as isize),
+                }
+            };
+
+            offset += stride * index;
+        });
+
+        let value = match self.mode {
+            PadMode::Constant(ref value) if !inside => value.clone(),
+            _ => unsafe { (*self.slice.as_ptr().offset(offset)).clone() },
+        };
+
+        // Increment the last dimension, which will be reset by reset_dim().
+        if self.rank() > 0 {
+            self.index.as_mut()[self.shape.rank() - 1] += 1;
+        }
+
+        value
+    }
+
+    fn inner_rank(&self) -> usize {
+        if self.shape.rank() > 0 { 1 } else { usize::MAX }
+    }
+
+    unsafe fn reset_dim(&mut self, index: usize, _: usize) {
+        self.index.as_mut()[index] = 0;
+    }
+
+    unsafe fn step_dim(&mut self, index: usize) {
+        // Don't increment the last dimension, since it is done in get_unchecked().
+        if index + 1 < self.rank() {
+            self.index.as_mut()[index] += 1;
+        }
+    }
+}
+
+impl<T: Clone, S: Shape, L: Layout> IntoIterator for Pad<'_, T, S, L> {
+    type Item = T;
+    type IntoIter = Iter<Self>;
+
+    fn into_iter(self) -> Iter<Self> {
+        Iter::new(self)
+    }
+}
@@ -0,0 +1,427 @@
+//! FFI module, with C-compatible array descriptors similar to C++ mdspan.
+//!
+//! A descriptor contains the data pointer, the rank and the extents of an array,
+//! and for strided layout also the strides. The dimensions are in row-major order
+//! as for the array types, and the strides are in number of elements. The rank is
+//! limited to `MAX_RANK`. The C declarations are found in `include/mdarray.h`.
+//!
+//! The C functions return a status code and write the result through a pointer,
+//! so that invalid descriptors are reported without panicking.
+//!
+//! # Examples
+//!
+//! ```
+//! use mdarray::ffi::{Status, StridedDescriptor, mdarray_strided_offset};
+//! use mdarray::{Strided, View, tensor};
+//!
+//! let t = tensor![[1, 2, 3], [4, 5, 6]];
+//! let s = t.view(.., 1..);
+//!
+//! let desc = StridedDescriptor::try_from(&*s).unwrap();
+//! let mut offset = 0;
+//!
+//! let status = unsafe { mdarray_strided_offset(&desc, [1, 1].as_ptr(), &mut offset) };
+//!
+//! assert!(status == Status::Success && offset == 4);
+//!
+//! let v: View<i32, (usize, usize), Strided> = unsafe { desc.view() };
+//!
+//! assert_eq!(v, s);
+//! ```
+
+use core::ffi::c_void;
+
+use thiserror::Error;
+
+use crate::layout::{Dense, Layout, Strided};
+use crate::mapping::{DenseMapping, StridedMapping};
+use crate::shape::Shape;
+use crate::slice::Slice;
+use crate::view::{View, ViewMut};
+
+/// Maximum rank for array descriptors.
+pub const MAX_RANK: usize = 6;
+
+/// Array descriptor for dense layout, similar to `mdspan` with `layout_right`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[repr(C)]
+pub struct DenseDescriptor {
+    /// Pointer to the first element.
+    pub data: *mut c_void,
+    /// Array rank.
+    pub rank: usize,
+    /// Number of elements in each dimension, where unused entries are zero.
+    pub extents: [usize; MAX_RANK],
+}
+
+/// Array descriptor for strided layout, similar to `mdspan` with `layout_stride`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[repr(C)]
+pub struct StridedDescriptor {
+    /// Pointer to the first element.
+    pub data: *mut c_void,
+    /// Array rank.
+    pub rank: usize,
+    /// Number of elements in each dimension, where unused entries are zero.
+    pub extents: [usize; MAX_RANK],
+    /// Distance between elements in each dimension, where unused entries are zero.
+    pub strides: [isize; MAX_RANK],
+}
+
+/// Error type for array descriptors.
+#[derive(Clone, Copy, Debug, Eq, Error, Hash, PartialEq)]
+pub enum FfiError {
+    /// The array rank is larger than `MAX_RANK`.
+    #[error("invalid rank")]
+    InvalidRank(usize),
+    /// The array length, a stride or an element offset overflows.
+    #[error("arithmetic overflow")]
+    Overflow,
+}
+
+/// Status code returned by the C functions.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[repr(C)]
+pub enum Status {
+    /// The function completed successfully.
+    Success = 0,
+    /// The array rank is larger than `MAX_RANK`.
+    InvalidRank = 1,
+    /// The array length, a stride or an element offset overflows.
+    Overflow = 2,
+}
+
+macro_rules! impl_descriptor {
+    ($name:tt, $layout:tt) => {
+        impl $name {
+            /// Returns the number of elements in the array.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the rank is larger than `MAX_RANK`, or if the length overflows.
+            pub fn len(&self) -> usize {
+                self.try_len().expect("invalid descriptor")
+            }
+
+            /// Returns `true` if the array contains no elements.
+            pub fn is_empty(&self) -> bool {
+                self.len() == 0
+            }
+
+            /// Creates an array view from the array descriptor.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the rank is larger than `MAX_RANK`, or if the extents are not
+            /// matching static rank or constant-sized dimensions.
+            ///
+            /// # Safety
+            ///
+            /// The data pointer must be non-null and a valid array view for the given
+            /// layout, and the array data must not be modified during the lifetime
+            /// of the array view.
+            pub unsafe fn view<'a, T, S: Shape>(&self) -> View<'a, T, S, $layout> {
+                unsafe { View::new_unchecked(self.data as *const T, self.mapping()) }
+            }
+
+            /// Creates a mutable array view from the array descriptor.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the rank is larger than `MAX_RANK`, or if the extents are not
+            /// matching static rank or constant-sized dimensions.
+            ///
+            /// # Safety
+            ///
+            /// The data pointer must be non-null and a valid array view for the given
+            /// layout, and the array data must not be accessed through other pointers
+            /// during the lifetime of the array view.
+            pub unsafe fn view_mut<'a, T, S: Shape>(&self) -> ViewMut<'a, T, S, $layout> {
+                unsafe { ViewMut::new_unchecked(self.data as *mut T, self.mapping()) }
+            }
+
+            fn extents(&self) -> &[usize] {
+                self.try_extents().expect("invalid rank")
+            }
+
+            fn try_extents(&self) -> Result<&[usize], FfiError> {
+                if self.rank > MAX_RANK {
+                    return Err(FfiError::InvalidRank(self.rank));
+                }
+
+                Ok(&self.extents[..self.rank])
+            }
+
+            fn try_len(&self) -> Result<usize, FfiError> {
+                let extents = self.try_extents()?;
+
+                extents
+                    .iter()
+                    .try_fold(1usize, |acc, &x| acc.checked_mul(x))
+                    .ok_or(FfiError::Overflow)
+            }
+        }
+    };
+}
+
+impl_descriptor!(DenseDescriptor, Dense);
+impl_descriptor!(StridedDescriptor, Strided);
+
+impl DenseDescriptor {
+    fn mapping<S: Shape>(&self) -> DenseMapping<S> {
+        DenseMapping::new(Shape::from_dims(self.extents()))
+    }
+
+    fn new<T, S: Shape>(ptr: *mut T, slice: &Slice<T, S, Dense>) -> Result<Self, FfiError> {
+        if slice.rank() > MAX_RANK {
+            return Err(FfiError::InvalidRank(slice.rank()));
+        }
+
+        let mut extents = [0; MAX_RANK];
+
+        slice.shape().with_dims(|dims| extents[..dims.len()].copy_from_slice(dims));
+
+        Ok(Self { data: ptr.cast(), rank: slice.rank(), extents })
+    }
+}
+
+impl StridedDescriptor {
+    fn mapping<S: Shape>(&self) -> StridedMapping<S> {
+        StridedMapping::new(Shape::from_dims(self.extents()), &self.strides[..self.rank])
+    }
+
+    fn new<T, S: Shape, L: Layout>(ptr: *mut T, slice: &Slice<T, S, L>) -> Result<Self, FfiError> {
+        if slice.rank() > MAX_RANK {
+            return Err(FfiError::InvalidRank(slice.rank()));
+        }
+
+        let mut extents = [0; MAX_RANK];
+        let mut strides = [0; MAX_RANK];
+
+        slice.shape().with_dims(|dims| extents[..dims.len()].copy_from_slice(dims));
+
+        for i in 0..slice.rank() {
+            strides[i] = slice.stride(i);
+        }
+
+        Ok(Self { data: ptr.cast(), rank: slice.rank(), extents, strides })
+    }
+
+    fn is_contiguous(&self) -> Result<bool, FfiError> {
+        let extents = self.try_extents()?;
+        let mut stride = 1isize;
+
+        for i in (0..extents.len()).rev() {
+            if extents[i] > 1 && self.strides[i] != stride {
+                return Ok(false);
+            }
+
+            let extent = isize::try_from(extents[i]).map_err(|_| FfiError::Overflow)?;
+
+            stride = stride.checked_mul(extent).ok_or(FfiError::Overflow)?;
+        }
+
+        Ok(true)
+    }
+
+    fn offset(&self, index: &[usize]) -> Result<isize, FfiError> {
+        let mut offset = 0isize;
+
+        for (&i, &stride) in index.iter().zip(&self.strides) {
+            let i = isize::try_from(i).map_err(|_| FfiError::Overflow)?;
+
+            offset = i
+                .checked_mul(stride)
+                .and_then(|x| offset.checked_add(x))
+                .ok_or(FfiError::Overflow)?;
+        }
+
+        Ok(offset)
+    }
+}
+
+impl From<FfiError> for Status {
+    fn from(value: FfiError) -> Self {
+        match value {
+            FfiError::InvalidRank(_) => Self::InvalidRank,
+            FfiError::Overflow => Self::Overflow,
+        }
+    }
+}
+
+impl TryFrom<DenseDescriptor> for StridedDescriptor {
+    type Error = FfiError;
+
+    fn try_from(value: DenseDescriptor) -> Result<Self, Self::Error> {
+        let extents = value.try_extents()?;
+
+        let mut strides = [0; MAX_RANK];
+        let mut stride = 1isize;
+
+        for i in (0..extents.len()).rev() {
+            strides[i] = stride;
+
+            let extent = isize::try_from(extents[i]).map_err(|_| FfiError::Overflow)?;
+
+            stride = stride.checked_mul(extent).ok_or(FfiError::Overflow)?;
+        }
+
+        Ok(Self { data: value.data, rank: value.rank, extents: value.extents, strides })
+    }
+}
+
+impl<T, S: Shape> TryFrom<&Slice<T, S>> for DenseDescriptor {
+    type Error = FfiError;
+
+    fn try_from(value: &Slice<T, S>) -> Result<Self, Self::Error> {
+        Self::new(value.as_ptr() as *mut T, value)
+    }
+}
+
+impl<T, S: Shape> TryFrom<&mut Slice<T, S>> for DenseDescriptor {
+    type Error = FfiError;
+
+    fn try_from(value: &mut Slice<T, S>) -> Result<Self, Self::Error> {
+        Self::new(value.as_mut_ptr(), value)
+    }
+}
+
+impl<T, S: Shape, L: Layout> TryFrom<&Slice<T, S, L>> for StridedDescriptor {
+    type Error = FfiError;
+
+    fn try_from(value: &Slice<T, S, L>) -> Result<Self, Self::Error> {
+        Self::new(value.as_ptr() as *mut T, value)
+    }
+}
+
+impl<T, S: Shape, L: Layout> TryFrom<&mut Slice<T, S, L>> for StridedDescriptor {
+    type Error = FfiError;
+
+    fn try_from(value: &mut Slice<T, S, L>) -> Result<Self, Self::Error> {
+        Self::new(value.as_mut_ptr(), value)
+    }
+}
+
+/// Computes the number of elements in the array, and writes it to `len`.
+///
+/// Returns a status code, which is not `Success` if the rank is larger than `MAX_RANK`
+/// or if the length overflows.
+///
+/// # Safety
+///
+/// The descriptor pointer must be valid, and the length pointer must be valid for writing.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mdarray_dense_len(
+    desc: *const DenseDescriptor,
+    len: *mut usize,
+) -> Status {
+    unsafe { write_result((*desc).try_len(), len) }
+}
+
+/// Computes the element offset from the data pointer for the given index, and writes
+/// it to `offset`.
+///
+/// Returns a status code, which is not `Success` if the rank is larger than `MAX_RANK`
+/// or if the offset overflows.
+///
+/// # Safety
+///
+/// The descriptor pointer must be valid, the index pointer must be valid for reading
+/// `rank` elements, and the offset pointer must be valid for writing.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mdarray_dense_offset(
+    desc: *const DenseDescriptor,
+    index: *const usize,
+    offset: *mut isize,
+) -> Status {
+    match StridedDescriptor::try_from(unsafe { *desc }) {
+        Ok(strided) => unsafe { mdarray_strided_offset(&strided, index, offset) },
+        Err(err) => err.into(),
+    }
+}
+
+/// Converts a dense array descriptor to a strided array descriptor.
+///
+/// Returns a status code, which is not `Success` if the rank is larger than `MAX_RANK`
+/// or if a stride overflows.
+///
+/// # Safety
+///
+/// The descriptor pointers must be valid.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mdarray_dense_to_strided(
+    desc: *const DenseDescriptor,
+    result: *mut StridedDescriptor,
+) -> Status {
+    unsafe { write_result(StridedDescriptor::try_from(*desc), result) }
+}
+
+/// Checks if the strides are consistent with contiguous memory layout, and writes
+/// the result to `contiguous`.
+///
+/// Returns a status code, which is not `Success` if the rank is larger than `MAX_RANK`
+/// or if the array length overflows.
+///
+/// # Safety
+///
+/// The descriptor pointer must be valid, and the result pointer must be valid for writing.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mdarray_strided_is_contiguous(
+    desc: *const StridedDescriptor,
+    contiguous: *mut bool,
+) -> Status {
+    unsafe { write_result((*desc).is_contiguous(), contiguous) }
+}
+
+/// Computes the number of elements in the array, and writes it to `len`.
+///
+/// Returns a status code, which is not `Success` if the rank is larger than `MAX_RANK`
+/// or if the length overflows.
+///
+/// # Safety
+///
+/// The descriptor pointer must be valid, and the length pointer must be valid for writing.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mdarray_strided_len(
+    desc: *const StridedDescriptor,
+    len: *mut usize,
+) -> Status {
+    unsafe { write_result((*desc).try_len(), len) }
+}
+
+/// Computes the element offset from the data pointer for the given index, and writes
+/// it to `offset`.
+///
+/// Returns a status code, which is not `Success` if the rank is larger than `MAX_RANK`
+/// or if the offset overflows.
+///
+/// # Safety
+///
+/// The descriptor pointer must be valid, the index pointer must be valid for reading
+/// `rank` elements, and the offset pointer must be valid for writing.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mdarray_strided_offset(
+    desc: *const StridedDescriptor,
+    index: *const usize,
+    offset: *mut isize,
+) -> Status {
+    let desc = unsafe { &*desc };
+
+    let result = desc.try_extents().and_then(|extents| {
+        desc.offset(unsafe { core::slice::from_raw_parts(index, extents.len()) })
+    });
+
+    unsafe { write_result(result, offset) }
+}
+
+// Writes the value if successful, and returns the status code.
+unsafe fn write_result<T>(result: Result<T, FfiError>, ptr: *mut T) -> Status {
+    match result {
+        Ok(value) => {
+            unsafe { ptr.write(value) };
+
+            Status::Success
+        }
+        Err(err) => err.into(),
+    }
+}
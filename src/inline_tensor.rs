@@ -0,0 +1,319 @@
+// Implements the methods and traits that are common for array types with inline
+// storage. The array type must have the methods `from_expr`, `raw_slice`,
+// `raw_slice_mut` and `free`, where `free` deallocates any heap storage.
+macro_rules! impl_inline_tensor {
+    ($name:tt) => {
+        impl<T, const N: usize, S: Shape> $name<T, N, S> {
+            /// Converts the array into an array with heap allocation.
+            #[cfg(feature = "alloc")]
+            pub fn into_tensor(self) -> Tensor<T, S> {
+                FromExpression::from_expr(self)
+            }
+
+            /// Returns the array with the given closure applied to each element.
+            pub fn map<F: FnMut(T) -> T>(self, mut f: F) -> Self {
+                self.zip_with(expr::fill(()), |(x, ())| f(x))
+            }
+
+            fn zip_with<I: IntoExpression, F>(self, expr: I, mut f: F) -> Self
+            where
+                F: FnMut((T, I::Item)) -> T,
+            {
+                struct DropGuard<T, const N: usize, S: Shape> {
+                    tensor: ManuallyDrop<$name<T, N, S>>,
+                    index: usize,
+                }
+
+                impl<T, const N: usize, S: Shape> Drop for DropGuard<T, N, S> {
+                    fn drop(&mut self) {
+                        let ptr = self.tensor.as_mut_ptr();
+                        let tail = self.tensor.len() - self.index;
+
+                        // Drop all elements except the current one, which is read but not
+                        // written back.
+                        unsafe {
+                            if self.index > 1 {
+                                ptr::slice_from_raw_parts_mut(ptr, self.index - 1).drop_in_place();
+                            }
+
+                            ptr::slice_from_raw_parts_mut(ptr.add(self.index), tail)
+                                .drop_in_place();
+
+                            self.tensor.free();
+                        }
+                    }
+                }
+
+                let mut guard = DropGuard { tensor: ManuallyDrop::new(self), index: 0 };
+                let expr = guard.tensor.expr_mut().zip(expr);
+
+                expr.for_each(|(x, y)| unsafe {
+                    guard.index += 1;
+                    ptr::write(x, f((ptr::read(x), y)));
+                });
+
+                let tensor = unsafe { ManuallyDrop::take(&mut guard.tensor) };
+
+                mem::forget(guard);
+
+                tensor
+            }
+        }
+
+        impl<'a, T, U, const N: usize, S: Shape> Apply<U> for &'a $name<T, N, S> {
+            type Output<F: FnMut(&'a T) -> U> = Map<Self::IntoExpr, F>;
+            type ZippedWith<I: IntoExpression, F: FnMut((&'a T, I::Item)) -> U> =
+                Map<Zip<Self::IntoExpr, I::IntoExpr>, F>;
+
+            fn apply<F: FnMut(&'a T) -> U>(self, f: F) -> Self::Output<F> {
+                self.expr().map(f)
+            }
+
+            fn zip_with<I: IntoExpression, F>(self, expr: I, f: F) -> Self::ZippedWith<I, F>
+            where
+                F: FnMut((&'a T, I::Item)) -> U,
+            {
+                self.expr().zip(expr).map(f)
+            }
+        }
+
+        impl<'a, T, U, const N: usize, S: Shape> Apply<U> for &'a mut $name<T, N, S> {
+            type Output<F: FnMut(&'a mut T) -> U> = Map<Self::IntoExpr, F>;
+            type ZippedWith<I: IntoExpression, F: FnMut((&'a mut T, I::Item)) -> U> =
+                Map<Zip<Self::IntoExpr, I::IntoExpr>, F>;
+
+            fn apply<F: FnMut(&'a mut T) -> U>(self, f: F) -> Self::Output<F> {
+                self.expr_mut().map(f)
+            }
+
+            fn zip_with<I: IntoExpression, F>(self, expr: I, f: F) -> Self::ZippedWith<I, F>
+            where
+                F: FnMut((&'a mut T, I::Item)) -> U,
+            {
+                self.expr_mut().zip(expr).map(f)
+            }
+        }
+
+        impl<T, const N: usize, S: Shape> Apply<T> for $name<T, N, S> {
+            type Output<F: FnMut(T) -> T> = Self;
+            type ZippedWith<I: IntoExpression, F: FnMut((T, I::Item)) -> T> = Self;
+
+            fn apply<F: FnMut(T) -> T>(self, f: F) -> Self {
+                self.map(f)
+            }
+
+            fn zip_with<I: IntoExpression, F>(self, expr: I, f: F) -> Self
+            where
+                F: FnMut((T, I::Item)) -> T,
+            {
+                self.zip_with(expr, f)
+            }
+        }
+
+        impl<T, U: ?Sized, const N: usize, S: Shape> AsMut<U> for $name<T, N, S>
+        where
+            Slice<T, S>: AsMut<U>,
+        {
+            fn as_mut(&mut self) -> &mut U {
+                (**self).as_mut()
+            }
+        }
+
+        impl<T, U: ?Sized, const N: usize, S: Shape> AsRef<U> for $name<T, N, S>
+        where
+            Slice<T, S>: AsRef<U>,
+        {
+            fn as_ref(&self) -> &U {
+                (**self).as_ref()
+            }
+        }
+
+        impl<T, const N: usize, S: Shape> Borrow<Slice<T, S>> for $name<T, N, S> {
+            fn borrow(&self) -> &Slice<T, S> {
+                self
+            }
+        }
+
+        impl<T, const N: usize, S: Shape> BorrowMut<Slice<T, S>> for $name<T, N, S> {
+            fn borrow_mut(&mut self) -> &mut Slice<T, S> {
+                self
+            }
+        }
+
+        impl<T, const N: usize, S: Shape> Buffer for $name<ManuallyDrop<T>, N, S> {
+            type Item = T;
+            type Shape = S;
+
+            fn as_mut_slice(&mut self) -> &mut Slice<ManuallyDrop<T>, S> {
+                self
+            }
+
+            fn as_slice(&self) -> &Slice<ManuallyDrop<T>, S> {
+                self
+            }
+        }
+
+        impl<T: Clone, const N: usize, S: Shape> Clone for $name<T, N, S> {
+            fn clone(&self) -> Self {
+                Self::from_expr(self.expr().cloned())
+            }
+        }
+
+        impl<T: Debug, const N: usize, S: Shape> Debug for $name<T, N, S> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                (**self).fmt(f)
+            }
+        }
+
+        impl<T, const N: usize, S: Shape> Default for $name<T, N, S> {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl<T, const N: usize, S: Shape> Deref for $name<T, N, S> {
+            type Target = Slice<T, S>;
+
+            fn deref(&self) -> &Self::Target {
+                self.raw_slice().as_slice()
+            }
+        }
+
+        impl<T, const N: usize, S: Shape> DerefMut for $name<T, N, S> {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                self.raw_slice_mut().as_mut_slice()
+            }
+        }
+
+        impl<T, const N: usize, S: Shape> Drop for $name<T, N, S> {
+            fn drop(&mut self) {
+                unsafe {
+                    ptr::slice_from_raw_parts_mut(self.as_mut_ptr(), self.len()).drop_in_place();
+
+                    self.free();
+                }
+            }
+        }
+
+        impl<'a, T: 'a + Clone, const N: usize, S: Shape, L: Layout, I> From<I> for $name<T, N, S>
+        where
+            I: IntoExpression<IntoExpr = View<'a, T, S, L>>,
+        {
+            fn from(value: I) -> Self {
+                Self::from_expr(value.into_expr().cloned())
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        impl<T, const N: usize, S: Shape> From<$name<T, N, S>> for Tensor<T, S> {
+            fn from(value: $name<T, N, S>) -> Self {
+                value.into_tensor()
+            }
+        }
+
+        impl<T, const N: usize, S: Shape> FromExpression<T, S> for $name<T, N, S> {
+            fn from_expr<I: IntoExpression<Item = T, Shape = S>>(expr: I) -> Self {
+                Self::from_expr(expr.into_expr())
+            }
+        }
+
+        impl<T: Hash, const N: usize, S: Shape> Hash for $name<T, N, S> {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                (**self).hash(state)
+            }
+        }
+
+        impl<T, const N: usize, S: Shape, I: SliceIndex<T, S, Dense>> Index<I> for $name<T, N, S> {
+            type Output = I::Output;
+
+            fn index(&self, index: I) -> &I::Output {
+                index.index(self)
+            }
+        }
+
+        impl<T, const N: usize, S: Shape, I: SliceIndex<T, S, Dense>> IndexMut<I>
+            for $name<T, N, S>
+        {
+            fn index_mut(&mut self, index: I) -> &mut I::Output {
+                index.index_mut(self)
+            }
+        }
+
+        impl<'a, T, const N: usize, S: Shape> IntoExpression for &'a $name<T, N, S> {
+            type Shape = S;
+            type IntoExpr = View<'a, T, S>;
+
+            fn into_expr(self) -> Self::IntoExpr {
+                self.expr()
+            }
+        }
+
+        impl<'a, T, const N: usize, S: Shape> IntoExpression for &'a mut $name<T, N, S> {
+            type Shape = S;
+            type IntoExpr = ViewMut<'a, T, S>;
+
+            fn into_expr(self) -> Self::IntoExpr {
+                self.expr_mut()
+            }
+        }
+
+        impl<T, const N: usize, S: Shape> IntoExpression for $name<T, N, S> {
+            type Shape = S;
+            type IntoExpr = IntoExpr<$name<ManuallyDrop<T>, N, S>>;
+
+            fn into_expr(self) -> Self::IntoExpr {
+                let me = ManuallyDrop::new(self);
+
+                // The layout is the same since ManuallyDrop<T> is transparent.
+                IntoExpr::new(unsafe { mem::transmute_copy(&*me) })
+            }
+        }
+
+        impl<'a, T, const N: usize, S: Shape> IntoIterator for &'a $name<T, N, S> {
+            type Item = &'a T;
+            type IntoIter = Iter<View<'a, T, S>>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.iter()
+            }
+        }
+
+        impl<'a, T, const N: usize, S: Shape> IntoIterator for &'a mut $name<T, N, S> {
+            type Item = &'a mut T;
+            type IntoIter = Iter<ViewMut<'a, T, S>>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.iter_mut()
+            }
+        }
+
+        impl<T, const N: usize, S: Shape> IntoIterator for $name<T, N, S> {
+            type Item = T;
+            type IntoIter = Iter<IntoExpr<$name<ManuallyDrop<T>, N, S>>>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.into_expr().into_iter()
+            }
+        }
+
+        impl<T, const N: usize, S: Shape> Owned<T, S> for $name<T, N, S> {
+            #[cfg(feature = "alloc")]
+            type WithConst<const M: usize> = $name<T, N, S::Prepend<Const<M>>>;
+
+            fn clone_from_slice(&mut self, slice: &Slice<T, S>)
+            where
+                T: Clone,
+            {
+                *self = Self::from_expr(slice.expr().cloned());
+            }
+        }
+
+        unsafe impl<T: Send, const N: usize, S: Shape> Send for $name<T, N, S> {}
+
+        // The pointer to inline storage is updated atomically when dereferencing.
+        #[cfg(target_has_atomic = "ptr")]
+        unsafe impl<T: Sync, const N: usize, S: Shape> Sync for $name<T, N, S> {}
+    };
+}
+
+pub(crate) use impl_inline_tensor;
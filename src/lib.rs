@@ -23,10 +23,22 @@
 //! The basic array type is `Tensor` for a dense array that owns the storage,
 //! similar to the Rust `Vec` type. It is parameterized by the element type,
 //! the shape (i.e. the size of each dimension) and optionally an allocator.
+//! Custom allocators require either the `nightly` feature, or the `allocator-api2`
+//! feature which uses the allocator trait, vector type and reserve error type from
+//! that crate. The vector type with a custom allocator is returned by
+//! `Tensor::into_vec_in`, while `Tensor::into_vec` returns a standard vector.
 //!
 //! `Array` is a dense array which stores elements inline, similar to the Rust
 //! `array` type. The shape must consist of dimensions with constant size.
 //!
+//! `SmallTensor` is a dense array which stores up to a given number of elements
+//! inline and otherwise spills to the heap, similar to small vector types. The
+//! shape can have dynamic dimensions or a dynamic rank.
+//!
+//! `StackTensor` is a dense array which stores elements inline with a fixed
+//! capacity, and never allocates. The shape can have dynamic dimensions, and
+//! operations that change the shape return an error if the capacity is exceeded.
+//!
 //! `View` and `ViewMut` are array types that refer to a parent array. They are
 //! used for example when creating array views without duplicating elements.
 //!
@@ -34,6 +46,10 @@
 //! It consists of a pointer to an internal structure that holds the storage
 //! and the layout mapping. All arrays can be dereferenced to an array slice.
 //!
+//! The crate is `no_std` if the default `std` feature is disabled. `Tensor` and
+//! `SmallTensor` then require the `alloc` feature, while `Array`, `StackTensor`,
+//! views and expressions are available also without heap allocation.
+//!
 //! The following type aliases are provided:
 //!
 //! - `DTensor<T, const N: usize, ...>` for a dense array with a given rank.
@@ -145,9 +161,10 @@
 //! assert_eq!(c, view![[4.0, 5.0], [5.0, 7.0], [6.0, 9.0]]);
 //! ```
 
+#![no_std]
 #![allow(clippy::comparison_chain)]
 #![allow(clippy::needless_range_loop)]
-#![cfg_attr(feature = "nightly", feature(allocator_api))]
+#![cfg_attr(all(feature = "alloc", feature = "nightly"), feature(allocator_api))]
 #![cfg_attr(feature = "nightly", feature(extern_types))]
 #![cfg_attr(feature = "nightly", feature(hasher_prefixfree_extras))]
 #![cfg_attr(feature = "nightly", feature(impl_trait_in_assoc_type))]
@@ -157,28 +174,74 @@
 #![warn(unreachable_pub)]
 #![warn(unused_results)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+pub mod accessor;
 pub mod expr;
 pub mod index;
 
+#[cfg(feature = "csv")]
+pub mod csv;
+#[cfg(feature = "dlpack")]
+pub mod dlpack;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "std")]
+pub mod linalg;
+#[cfg(feature = "matrix-market")]
+pub mod matrix_market;
+#[cfg(feature = "safetensors")]
+pub mod safetensors;
+#[cfg(feature = "serde")]
+pub mod serde;
+
 mod array;
 mod dim;
+mod error;
+mod inline_tensor;
 mod layout;
 mod macros;
 mod mapping;
 mod ops;
 mod raw_slice;
-mod raw_tensor;
 mod shape;
 mod slice;
-mod tensor;
+mod stack_tensor;
 mod traits;
 mod view;
 
-#[cfg(feature = "serde")]
-mod serde;
+#[cfg(feature = "alloc")]
+mod raw_tensor;
+#[cfg(feature = "alloc")]
+mod small_tensor;
+#[cfg(feature = "alloc")]
+mod tensor;
+
+#[cfg(feature = "num-complex")]
+mod complex;
+
+#[cfg(all(feature = "alloc", feature = "nightly"))]
+mod allocator {
+    pub(crate) use alloc::alloc::{Allocator, Global};
+    pub(crate) use alloc::collections::TryReserveError;
+    pub(crate) use alloc::vec::Vec;
+}
+
+#[cfg(all(feature = "allocator-api2", not(feature = "nightly")))]
+mod allocator {
+    pub(crate) use allocator_api2::alloc::{Allocator, Global};
+    pub(crate) use allocator_api2::collections::TryReserveError;
+    pub(crate) use allocator_api2::vec::Vec;
+}
+
+#[cfg(all(feature = "alloc", not(any(feature = "nightly", feature = "allocator-api2"))))]
+mod allocator {
+    pub(crate) use alloc::collections::TryReserveError;
+    pub(crate) use alloc::vec::Vec;
 
-#[cfg(not(feature = "nightly"))]
-mod alloc {
     pub trait Allocator {}
 
     #[derive(Copy, Clone, Default, Debug)]
@@ -189,11 +252,17 @@ mod alloc {
 
 pub use array::Array;
 pub use dim::{Const, Dim, Dyn};
+pub use error::TensorError;
 pub use layout::{Dense, Layout, Strided};
 pub use mapping::{DenseMapping, Mapping, StridedMapping};
 pub use ops::{StepRange, step};
 pub use shape::{ConstShape, DynRank, IntoShape, Rank, Shape};
 pub use slice::{DSlice, Slice};
-pub use tensor::{DTensor, Tensor};
+pub use stack_tensor::StackTensor;
 pub use traits::{IntoCloned, Owned};
 pub use view::{DView, DViewMut, View, ViewMut};
+
+#[cfg(feature = "alloc")]
+pub use small_tensor::SmallTensor;
+#[cfg(feature = "alloc")]
+pub use tensor::{DTensor, Tensor};
@@ -0,0 +1,267 @@
+use crate::dim::Dyn;
+use crate::layout::{Layout, Strided};
+use crate::slice::DSlice;
+use crate::tensor::Tensor;
+
+use super::Element;
+
+// Block sizes for the reference matrix multiplication, chosen so that a block of
+// `A` and a panel of `B` fit in the L1 and L2 caches for double precision.
+const MC: usize = 64;
+const KC: usize = 256;
+const NC: usize = 1024;
+
+/// Level 1 operations on vectors.
+///
+/// The operations take strided array slices, where the strides and the data pointer
+/// can be used to call external BLAS libraries.
+pub trait Level1<T> {
+    /// Computes `y = alpha * x + y`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vector lengths are not matching.
+    fn axpy(&self, alpha: T, x: &DSlice<T, 1, Strided>, y: &mut DSlice<T, 1, Strided>);
+
+    /// Returns the dot product `x^T * y`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vector lengths are not matching.
+    fn dot(&self, x: &DSlice<T, 1, Strided>, y: &DSlice<T, 1, Strided>) -> T;
+}
+
+/// Level 2 operations on matrices and vectors.
+pub trait Level2<T>: Level1<T> {
+    /// Computes `y = alpha * A * x + beta * y`.
+    ///
+    /// If `beta` is zero, the elements in `y` are not read.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the shapes are not matching.
+    fn gemv(
+        &self,
+        alpha: T,
+        a: &DSlice<T, 2, Strided>,
+        x: &DSlice<T, 1, Strided>,
+        beta: T,
+        y: &mut DSlice<T, 1, Strided>,
+    );
+}
+
+/// Level 3 operations on matrices.
+pub trait Level3<T>: Level2<T> {
+    /// Computes `C = alpha * A * B + beta * C`.
+    ///
+    /// If `beta` is zero, the elements in `C` are not read.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the shapes are not matching.
+    fn gemm(
+        &self,
+        alpha: T,
+        a: &DSlice<T, 2, Strided>,
+        b: &DSlice<T, 2, Strided>,
+        beta: T,
+        c: &mut DSlice<T, 2, Strided>,
+    );
+}
+
+/// Portable reference backend, with cache-blocked matrix multiplication.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Reference;
+
+impl<T: Element> Level1<T> for Reference {
+    fn axpy(&self, alpha: T, x: &DSlice<T, 1, Strided>, y: &mut DSlice<T, 1, Strided>) {
+        assert!(x.len() == y.len(), "shape mismatch");
+
+        for (x, y) in x.iter().zip(y.iter_mut()) {
+            *y += alpha * *x;
+        }
+    }
+
+    fn dot(&self, x: &DSlice<T, 1, Strided>, y: &DSlice<T, 1, Strided>) -> T {
+        assert!(x.len() == y.len(), "shape mismatch");
+
+        x.iter().zip(y.iter()).fold(T::ZERO, |acc, (&x, &y)| acc + x * y)
+    }
+}
+
+impl<T: Element> Level2<T> for Reference {
+    fn gemv(
+        &self,
+        alpha: T,
+        a: &DSlice<T, 2, Strided>,
+        x: &DSlice<T, 1, Strided>,
+        beta: T,
+        y: &mut DSlice<T, 1, Strided>,
+    ) {
+        assert!(a.dim(1) == x.len() && a.dim(0) == y.len(), "shape mismatch");
+
+        for (i, y) in y.iter_mut().enumerate() {
+            let value = alpha * self.dot(&a.row(i).remap(), x);
+
+            *y = if beta == T::ZERO { value } else { beta * *y + value };
+        }
+    }
+}
+
+impl<T: Element> Level3<T> for Reference {
+    fn gemm(
+        &self,
+        alpha: T,
+        a: &DSlice<T, 2, Strided>,
+        b: &DSlice<T, 2, Strided>,
+        beta: T,
+        c: &mut DSlice<T, 2, Strided>,
+    ) {
+        let (m, k) = *a.shape();
+        let n = b.dim(1);
+
+        assert!(b.dim(0) == k && *c.shape() == (m, n), "shape mismatch");
+
+        if beta == T::ZERO {
+            c.fill(T::ZERO);
+        } else if beta != T::ONE {
+            c.iter_mut().for_each(|x| *x *= beta);
+        }
+
+        // Iterate over blocks, where the innermost loop is over the columns in `C`
+        // and `B` to get sequential access for row-major layout.
+        for jc in (0..n).step_by(NC) {
+            let nc = NC.min(n - jc);
+
+            for pc in (0..k).step_by(KC) {
+                let kc = KC.min(k - pc);
+
+                for ic in (0..m).step_by(MC) {
+                    let mc = MC.min(m - ic);
+
+                    for i in ic..ic + mc {
+                        for p in pc..pc + kc {
+                            let x = alpha * a[[i, p]];
+
+                            if x != T::ZERO {
+                                for j in jc..jc + nc {
+                                    c[[i, j]] += x * b[[p, j]];
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Returns the matrix product `A * B`, using the reference backend.
+///
+/// # Panics
+///
+/// Panics if the number of columns in `A` is not matching the number of rows in `B`.
+///
+/// # Examples
+///
+/// ```
+/// use mdarray::linalg;
+/// use mdarray::tensor;
+///
+/// let a = tensor![[1.0, 2.0], [3.0, 4.0]];
+/// let b = tensor![[5.0], [6.0]];
+///
+/// assert_eq!(linalg::matmul(&a, &b), tensor![[17.0], [39.0]]);
+/// ```
+pub fn matmul<T: Element, L: Layout, M: Layout>(
+    a: &DSlice<T, 2, L>,
+    b: &DSlice<T, 2, M>,
+) -> Tensor<T, (Dyn, Dyn)> {
+    matmul_with(&Reference, a, b)
+}
+
+/// Returns the matrix product `A^H * B`, where `A^H` is the conjugate transpose.
+///
+/// # Panics
+///
+/// Panics if the number of rows in `A` is not matching the number of rows in `B`.
+pub fn matmul_hn<T: Element, L: Layout, M: Layout>(
+    a: &DSlice<T, 2, L>,
+    b: &DSlice<T, 2, M>,
+) -> Tensor<T, (Dyn, Dyn)> {
+    matmul_hn_with(&Reference, a, b)
+}
+
+/// Returns the matrix product `A^H * B`, where `A^H` is the conjugate transpose,
+/// using the given backend.
+///
+/// # Panics
+///
+/// Panics if the number of rows in `A` is not matching the number of rows in `B`.
+pub fn matmul_hn_with<T: Element, B: Level3<T>, L: Layout, M: Layout>(
+    backend: &B,
+    a: &DSlice<T, 2, L>,
+    b: &DSlice<T, 2, M>,
+) -> Tensor<T, (Dyn, Dyn)> {
+    let a = Tensor::from_fn((a.dim(1), a.dim(0)), |i| a[[i[1], i[0]]].conj());
+
+    matmul_with(backend, &a, b)
+}
+
+/// Returns the matrix product `A * B^H`, where `B^H` is the conjugate transpose.
+///
+/// # Panics
+///
+/// Panics if the number of columns in `A` is not matching the number of columns in `B`.
+///
+/// # Examples
+///
+/// ```
+/// use mdarray::linalg;
+/// use mdarray::tensor;
+///
+/// let a = tensor![[1.0, 2.0], [3.0, 4.0]];
+///
+/// assert_eq!(linalg::matmul_nh(&a, &a), tensor![[5.0, 11.0], [11.0, 25.0]]);
+/// ```
+pub fn matmul_nh<T: Element, L: Layout, M: Layout>(
+    a: &DSlice<T, 2, L>,
+    b: &DSlice<T, 2, M>,
+) -> Tensor<T, (Dyn, Dyn)> {
+    matmul_nh_with(&Reference, a, b)
+}
+
+/// Returns the matrix product `A * B^H`, where `B^H` is the conjugate transpose,
+/// using the given backend.
+///
+/// # Panics
+///
+/// Panics if the number of columns in `A` is not matching the number of columns in `B`.
+pub fn matmul_nh_with<T: Element, B: Level3<T>, L: Layout, M: Layout>(
+    backend: &B,
+    a: &DSlice<T, 2, L>,
+    b: &DSlice<T, 2, M>,
+) -> Tensor<T, (Dyn, Dyn)> {
+    let b = Tensor::from_fn((b.dim(1), b.dim(0)), |i| b[[i[1], i[0]]].conj());
+
+    matmul_with(backend, a, &b)
+}
+
+/// Returns the matrix product `A * B`, using the given backend.
+///
+/// # Panics
+///
+/// Panics if the number of columns in `A` is not matching the number of rows in `B`.
+pub fn matmul_with<T: Element, B: Level3<T>, L: Layout, M: Layout>(
+    backend: &B,
+    a: &DSlice<T, 2, L>,
+    b: &DSlice<T, 2, M>,
+) -> Tensor<T, (Dyn, Dyn)> {
+    assert!(a.dim(1) == b.dim(0), "shape mismatch");
+
+    let mut c = Tensor::from_elem((a.dim(0), b.dim(1)), T::ZERO);
+
+    backend.gemm(T::ONE, &a.remap(), &b.remap(), T::ZERO, &mut c.remap_mut());
+
+    c
+}
@@ -0,0 +1,68 @@
+use core::cmp::Ordering;
+
+use crate::layout::Layout;
+use crate::slice::DSlice;
+
+use super::{LinalgError, Scalar};
+
+/// Computes the Cholesky factorization of a symmetric positive definite matrix in place.
+///
+/// The factorization is `A = L * L^T`, where `L` is lower triangular. Only the lower
+/// triangle of the input matrix is used, and it is overwritten by `L` while the upper
+/// triangle is set to zero.
+///
+/// # Errors
+///
+/// Returns an error if the matrix is not positive definite, with the index of the
+/// first non-positive pivot.
+///
+/// # Panics
+///
+/// Panics if the matrix is not square.
+///
+/// # Examples
+///
+/// ```
+/// use mdarray::{linalg, tensor};
+///
+/// let mut a = tensor![[4.0, 2.0], [2.0, 10.0]];
+///
+/// linalg::cholesky(&mut a).unwrap();
+///
+/// assert_eq!(a, tensor![[2.0, 0.0], [1.0, 3.0]]);
+/// ```
+pub fn cholesky<T: Scalar, L: Layout>(a: &mut DSlice<T, 2, L>) -> Result<(), LinalgError> {
+    assert!(a.dim(0) == a.dim(1), "matrix not square");
+
+    let n = a.dim(0);
+
+    for j in 0..n {
+        let mut diag = a[[j, j]];
+
+        for k in 0..j {
+            diag -= a[[j, k]] * a[[j, k]];
+        }
+
+        // Compare with partial ordering to also catch NaN values.
+        if diag.partial_cmp(&T::ZERO) != Some(Ordering::Greater) {
+            return Err(LinalgError::NotPositiveDefinite(j));
+        }
+
+        let diag = diag.sqrt();
+
+        a[[j, j]] = diag;
+
+        for i in j + 1..n {
+            let mut value = a[[i, j]];
+
+            for k in 0..j {
+                value -= a[[i, k]] * a[[j, k]];
+            }
+
+            a[[i, j]] = value / diag;
+            a[[j, i]] = T::ZERO;
+        }
+    }
+
+    Ok(())
+}
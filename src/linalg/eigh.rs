@@ -0,0 +1,138 @@
+use std::vec::Vec;
+
+use core::cmp::Ordering;
+
+use crate::dim::Dyn;
+use crate::layout::Layout;
+use crate::slice::DSlice;
+use crate::tensor::Tensor;
+
+use super::{LinalgError, Norm, Scalar, norm};
+
+// Maximum number of sweeps over all off-diagonal elements in the Jacobi methods.
+pub(super) const MAX_SWEEPS: usize = 60;
+
+/// Computes the eigenvalues and eigenvectors of a symmetric matrix.
+///
+/// The decomposition is `A = V * diag(w) * V^T`, where the eigenvalues `w` are in
+/// ascending order and the columns of the orthogonal matrix `V` are the eigenvectors.
+/// Only the lower triangle of `A` is accessed.
+///
+/// The eigenvalues are computed with the cyclic Jacobi method, which has high relative
+/// accuracy and is suitable for small and moderate-size matrices.
+///
+/// # Errors
+///
+/// Returns an error if the iteration does not converge.
+///
+/// # Panics
+///
+/// Panics if the matrix is not square.
+///
+/// # Examples
+///
+/// ```
+/// use mdarray::{linalg, tensor};
+///
+/// let a = tensor![[2.0, 1.0], [1.0, 2.0]];
+/// let (w, v) = linalg::eigh(&a).unwrap();
+///
+/// assert_eq!(w, tensor![1.0, 3.0]);
+/// assert!((v[[0, 1]] - 0.5f64.sqrt()).abs() < 1e-15);
+/// ```
+pub fn eigh<T: Scalar, L: Layout>(
+    a: &DSlice<T, 2, L>,
+) -> Result<(Tensor<T, (Dyn,)>, Tensor<T, (Dyn, Dyn)>), LinalgError> {
+    assert!(a.dim(0) == a.dim(1), "matrix not square");
+
+    let n = a.dim(0);
+
+    let mut a = Tensor::from_fn((n, n), |i| a[[i[0].max(i[1]), i[0].min(i[1])]]);
+    let mut v = identity(n);
+
+    // Off-diagonal elements below the threshold are negligible for the whole matrix.
+    let tiny = T::EPSILON * T::EPSILON * norm(&a, Norm::L2);
+
+    for _ in 0..MAX_SWEEPS {
+        let mut rotated = false;
+
+        for p in 0..n {
+            for q in p + 1..n {
+                let (app, aqq, apq) = (a[[p, p]], a[[q, q]], a[[p, q]]);
+
+                if apq.abs() <= T::EPSILON * app.abs().sqrt() * aqq.abs().sqrt()
+                    || apq.abs() <= tiny
+                {
+                    a[[p, q]] = T::ZERO;
+                    a[[q, p]] = T::ZERO;
+
+                    continue;
+                }
+
+                let (c, s) = rotation(app, aqq, apq);
+
+                rotate_cols(&mut a, p, q, c, s);
+                rotate_cols(&mut v, p, q, c, s);
+
+                for k in 0..n {
+                    let (x, y) = (a[[p, k]], a[[q, k]]);
+
+                    a[[p, k]] = c * x - s * y;
+                    a[[q, k]] = s * x + c * y;
+                }
+
+                // Set the updated 2x2 block directly, to reduce rounding errors.
+                a[[p, p]] = app - s / c * apq;
+                a[[q, q]] = aqq + s / c * apq;
+                a[[p, q]] = T::ZERO;
+                a[[q, p]] = T::ZERO;
+
+                rotated = true;
+            }
+        }
+
+        if !rotated {
+            let mut order = (0..n).collect::<Vec<_>>();
+
+            order.sort_by(|&i, &j| a[[i, i]].partial_cmp(&a[[j, j]]).unwrap_or(Ordering::Equal));
+
+            let w = Tensor::from_fn((n,), |i| a[[order[i[0]], order[i[0]]]]);
+            let v = Tensor::from_fn((n, n), |i| v[[i[0], order[i[1]]]]);
+
+            return Ok((w, v));
+        }
+    }
+
+    Err(LinalgError::NoConvergence)
+}
+
+pub(super) fn identity<T: Scalar>(n: usize) -> Tensor<T, (Dyn, Dyn)> {
+    Tensor::from_fn((n, n), |i| if i[0] == i[1] { T::ONE } else { T::ZERO })
+}
+
+pub(super) fn rotate_cols<T: Scalar>(a: &mut DSlice<T, 2>, p: usize, q: usize, c: T, s: T) {
+    for k in 0..a.dim(0) {
+        let (x, y) = (a[[k, p]], a[[k, q]]);
+
+        a[[k, p]] = c * x - s * y;
+        a[[k, q]] = s * x + c * y;
+    }
+}
+
+// Returns the Jacobi rotation that annihilates the off-diagonal element of the
+// symmetric 2x2 matrix [[app, apq], [apq, aqq]], given that apq is nonzero.
+pub(super) fn rotation<T: Scalar>(app: T, aqq: T, apq: T) -> (T, T) {
+    let theta = (aqq - app) / (apq + apq);
+
+    // Compute sqrt(theta^2 + 1) without overflow for large theta.
+    let r = if theta.abs() > T::ONE {
+        theta.abs() * (T::ONE + (T::ONE / theta) * (T::ONE / theta)).sqrt()
+    } else {
+        (theta * theta + T::ONE).sqrt()
+    };
+
+    let t = if theta < T::ZERO { -T::ONE } else { T::ONE } / (theta.abs() + r);
+    let c = T::ONE / (t * t + T::ONE).sqrt();
+
+    (c, t * c)
+}
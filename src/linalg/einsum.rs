@@ -0,0 +1,406 @@
+use std::vec;
+use std::vec::Vec;
+
+use thiserror::Error;
+
+use crate::layout::Layout;
+use crate::shape::{DynRank, Shape};
+use crate::slice::Slice;
+use crate::tensor::Tensor;
+
+use super::Element;
+
+// Labels are numbered in ASCII order for letters, followed by broadcast dimensions.
+const LETTERS: usize = 52;
+
+/// Error type for Einstein summation.
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum EinsumError {
+    /// The subscripts could not be parsed, an output label is repeated or missing in
+    /// the inputs, or the output has no ellipsis for broadcast dimensions.
+    #[error("invalid subscripts")]
+    InvalidSubscripts,
+    /// The number of operands is not matching the subscripts.
+    #[error("invalid number of operands {len}, expected {expected}")]
+    OperandCount {
+        /// Number of operands.
+        len: usize,
+        /// Number of input terms in the subscripts.
+        expected: usize,
+    },
+    /// The rank of an operand is not matching the subscripts.
+    #[error("invalid rank {rank} for operand {operand}, expected {expected}")]
+    RankMismatch {
+        /// Operand index.
+        operand: usize,
+        /// Rank of the operand.
+        rank: usize,
+        /// Number of labels in the subscripts.
+        expected: usize,
+    },
+    /// The dimension of an operand is not matching other dimensions with the same label.
+    #[error("invalid dimension {dim} for operand {operand} at axis {axis}, expected {expected}")]
+    DimensionMismatch {
+        /// Operand index.
+        operand: usize,
+        /// Axis in the operand.
+        axis: usize,
+        /// Dimension of the operand.
+        dim: usize,
+        /// Dimension given by earlier operands.
+        expected: usize,
+    },
+    /// The output shape is not matching the array type.
+    #[error("output shape not matching the array type")]
+    ShapeMismatch,
+}
+
+struct Operand<T> {
+    ptr: *const T,
+    labels: Vec<usize>,
+    strides: Vec<isize>,
+}
+
+/// Computes the Einstein summation over the operands.
+///
+/// The subscripts contain a comma-separated list of labels for each operand, and
+/// optionally `->` followed by the labels for the output. Labels are ASCII letters,
+/// and elements are summed over labels that are not in the output. A repeated label
+/// in an operand takes the diagonal along the dimensions.
+///
+/// If the output is not given, it contains the labels that appear once in the inputs,
+/// in alphabetical order. An ellipsis `...` matches the remaining dimensions, which
+/// are broadcast between the operands and placed first in the implicit output. If
+/// the output is given and there are broadcast dimensions, it must have an ellipsis.
+///
+/// For more than two operands, the contraction order is chosen pairwise to minimize
+/// the size of the intermediate results.
+///
+/// # Errors
+///
+/// Returns an error if the subscripts are invalid, or if the operands are not
+/// matching the subscripts.
+///
+/// # Examples
+///
+/// ```
+/// use mdarray::{linalg, tensor};
+///
+/// let a = tensor![[1.0, 2.0], [3.0, 4.0]].into_dyn();
+/// let b = tensor![[5.0, 6.0], [7.0, 8.0]].into_dyn();
+///
+/// let c = linalg::einsum("ij,jk->ik", &[&a, &b]).unwrap();
+///
+/// assert_eq!(c, tensor![[19.0, 22.0], [43.0, 50.0]].into_dyn());
+/// assert_eq!(linalg::einsum("ii", &[&a]).unwrap()[[]], 5.0);
+/// ```
+pub fn einsum<T: Element, S: Shape, L: Layout>(
+    subscripts: &str,
+    operands: &[&Slice<T, S, L>],
+) -> Result<Tensor<T, DynRank>, EinsumError> {
+    let (inputs, output) = match subscripts.split_once("->") {
+        Some((inputs, output)) => (inputs, Some(parse_term(output)?)),
+        None => (subscripts, None),
+    };
+
+    let inputs = inputs.split(',').map(parse_term).collect::<Result<Vec<_>, _>>()?;
+
+    if operands.len() != inputs.len() {
+        return Err(EinsumError::OperandCount { len: operands.len(), expected: inputs.len() });
+    }
+
+    // Expand the ellipsis in each operand, where broadcast dimensions are right-aligned.
+    let mut broadcast = 0;
+
+    for (i, (labels, ellipsis)) in inputs.iter().enumerate() {
+        let rank = operands[i].rank();
+
+        if rank < labels.len() || (ellipsis.is_none() && rank > labels.len()) {
+            return Err(EinsumError::RankMismatch { operand: i, rank, expected: labels.len() });
+        }
+
+        broadcast = broadcast.max(rank - labels.len());
+    }
+
+    let inputs = inputs
+        .iter()
+        .zip(operands)
+        .map(|((labels, ellipsis), x)| {
+            expand(labels, *ellipsis, x.rank() - labels.len(), broadcast)
+        })
+        .collect::<Vec<_>>();
+
+    let mut sizes = vec![None; LETTERS + broadcast];
+
+    for (i, (labels, x)) in inputs.iter().zip(operands).enumerate() {
+        for (axis, &label) in labels.iter().enumerate() {
+            let dim = x.dim(axis);
+
+            match sizes[label] {
+                Some(expected) if expected != dim => {
+                    // Broadcast dimensions of size one are extended, also to size zero.
+                    if label < LETTERS || (dim != 1 && expected != 1) {
+                        return Err(EinsumError::DimensionMismatch {
+                            operand: i,
+                            axis,
+                            dim,
+                            expected,
+                        });
+                    }
+
+                    sizes[label] = Some(if expected == 1 { dim } else { expected });
+                }
+                _ => sizes[label] = Some(dim),
+            }
+        }
+    }
+
+    let output = match output {
+        Some((labels, ellipsis)) => {
+            // Broadcast dimensions are not summed over, so they must be in the output.
+            if broadcast > 0 && ellipsis.is_none() {
+                return Err(EinsumError::InvalidSubscripts);
+            }
+
+            for (i, label) in labels.iter().enumerate() {
+                if labels[..i].contains(label) || sizes[*label].is_none() {
+                    return Err(EinsumError::InvalidSubscripts);
+                }
+            }
+
+            expand(&labels, ellipsis, broadcast, broadcast)
+        }
+        None => {
+            let mut output = (LETTERS..LETTERS + broadcast).collect::<Vec<_>>();
+
+            for label in 0..LETTERS {
+                if inputs.iter().flatten().filter(|&&x| x == label).count() == 1 {
+                    output.push(label);
+                }
+            }
+
+            output
+        }
+    };
+
+    let sizes = sizes.iter().map(|x| x.unwrap_or(0)).collect::<Vec<_>>();
+
+    let mut operands = inputs
+        .iter()
+        .zip(operands)
+        .map(|(labels, x)| Operand::new(x, labels, &sizes))
+        .collect::<Vec<_>>();
+
+    // Intermediate results are kept until the end, since the operands point to them.
+    let mut buffers = Vec::new();
+
+    while operands.len() > 2 {
+        let (i, j, labels) = contraction_pair(&operands, &output, &sizes);
+        let tensor = contract(&[&operands[i], &operands[j]], &labels, &sizes);
+
+        let operand = Operand::new(&tensor, &labels, &sizes);
+
+        _ = operands.remove(j);
+        _ = operands.remove(i);
+
+        operands.push(operand);
+        buffers.push(tensor);
+    }
+
+    Ok(contract(&operands.iter().collect::<Vec<_>>(), &output, &sizes))
+}
+
+/// Computes the Einstein summation over the operands, with the output array type
+/// given as a type parameter.
+///
+/// See the `einsum` function above for the format of the subscripts.
+///
+/// # Errors
+///
+/// Returns an error if the subscripts are invalid, if the operands are not matching
+/// the subscripts, or if the output shape is not matching the array type.
+///
+/// # Examples
+///
+/// ```
+/// use mdarray::{DTensor, linalg, tensor};
+///
+/// let a = tensor![[1.0, 2.0], [3.0, 4.0]];
+/// let b = tensor![[5.0, 6.0], [7.0, 8.0]];
+///
+/// let c: DTensor<f64, 2> = linalg::einsum_typed("ij,kj", &[&a, &b]).unwrap();
+///
+/// assert_eq!(c, tensor![[17.0, 23.0], [39.0, 53.0]]);
+/// ```
+pub fn einsum_typed<T: Element, O: Shape, S: Shape, L: Layout>(
+    subscripts: &str,
+    operands: &[&Slice<T, S, L>],
+) -> Result<Tensor<T, O>, EinsumError> {
+    let tensor = einsum(subscripts, operands)?;
+    let dims = tensor.dims();
+
+    if O::RANK.is_some_and(|rank| rank != dims.len()) {
+        return Err(EinsumError::ShapeMismatch);
+    }
+
+    for (i, &dim) in dims.iter().enumerate() {
+        if O::static_dim(i).is_some_and(|size| size != dim) {
+            return Err(EinsumError::ShapeMismatch);
+        }
+    }
+
+    let shape = O::from_dims(dims);
+
+    Ok(tensor.into_shape(shape))
+}
+
+impl<T> Operand<T> {
+    fn new<S: Shape, L: Layout>(x: &Slice<T, S, L>, labels: &[usize], sizes: &[usize]) -> Self {
+        let mut operand = Self { ptr: x.as_ptr(), labels: Vec::new(), strides: Vec::new() };
+
+        for (axis, &label) in labels.iter().enumerate() {
+            // Broadcast dimensions have zero stride, and repeated labels give the diagonal.
+            let stride = if x.dim(axis) < sizes[label] { 0 } else { x.stride(axis) };
+
+            match operand.labels.iter().position(|&l| l == label) {
+                Some(i) => operand.strides[i] += stride,
+                None => {
+                    operand.labels.push(label);
+                    operand.strides.push(stride);
+                }
+            }
+        }
+
+        operand
+    }
+}
+
+fn contract<T: Element>(
+    operands: &[&Operand<T>],
+    output: &[usize],
+    sizes: &[usize],
+) -> Tensor<T, DynRank> {
+    // Iterate over the output labels followed by the summed labels.
+    let mut labels = output.to_vec();
+
+    for operand in operands {
+        for &label in &operand.labels {
+            if !labels.contains(&label) {
+                labels.push(label);
+            }
+        }
+    }
+
+    let dims = labels.iter().map(|&x| sizes[x]).collect::<Vec<_>>();
+    let strides = operands
+        .iter()
+        .map(|operand| {
+            let position = |label| operand.labels.iter().position(|&x| x == label);
+
+            labels.iter().map(|&x| position(x).map_or(0, |i| operand.strides[i])).collect()
+        })
+        .collect::<Vec<Vec<isize>>>();
+
+    let (outer, inner) = dims.split_at(output.len());
+    let inner_len = inner.iter().product::<usize>();
+
+    let mut tensor = Tensor::from_elem(DynRank::from_dims(outer), T::ZERO);
+    let mut index = vec![0; labels.len()];
+
+    for x in tensor.iter_mut() {
+        for _ in 0..inner_len {
+            let mut value = T::ONE;
+
+            for (operand, strides) in operands.iter().zip(&strides) {
+                let offset = index.iter().zip(strides).map(|(&i, &s)| i as isize * s).sum();
+
+                value *= unsafe { *operand.ptr.offset(offset) };
+            }
+
+            *x += value;
+            increment(&mut index[output.len()..], inner);
+        }
+
+        increment(&mut index[..output.len()], outer);
+    }
+
+    tensor
+}
+
+fn contraction_pair<T>(
+    operands: &[Operand<T>],
+    output: &[usize],
+    sizes: &[usize],
+) -> (usize, usize, Vec<usize>) {
+    let mut best = (0, 1, Vec::new());
+    let mut best_len = usize::MAX;
+
+    for i in 0..operands.len() {
+        for j in i + 1..operands.len() {
+            // Keep labels that are in the output or in any other operand.
+            let is_needed = |label: &usize| {
+                output.contains(label)
+                    || operands
+                        .iter()
+                        .enumerate()
+                        .any(|(k, x)| k != i && k != j && x.labels.contains(label))
+            };
+
+            let mut labels = operands[i].labels.clone();
+
+            labels.extend(operands[j].labels.iter().filter(|x| !operands[i].labels.contains(x)));
+            labels.retain(is_needed);
+            labels.sort_unstable();
+
+            let len = labels.iter().fold(1usize, |acc, &x| acc.saturating_mul(sizes[x]));
+
+            if len < best_len {
+                best = (i, j, labels);
+                best_len = len;
+            }
+        }
+    }
+
+    best
+}
+
+fn expand(labels: &[usize], ellipsis: Option<usize>, len: usize, broadcast: usize) -> Vec<usize> {
+    let mut expanded = labels.to_vec();
+
+    if let Some(position) = ellipsis {
+        _ = expanded.splice(position..position, LETTERS + broadcast - len..LETTERS + broadcast);
+    }
+
+    expanded
+}
+
+fn increment(index: &mut [usize], dims: &[usize]) {
+    for (i, &dim) in index.iter_mut().zip(dims).rev() {
+        *i += 1;
+
+        if *i < dim {
+            return;
+        }
+
+        *i = 0;
+    }
+}
+
+fn parse_term(term: &str) -> Result<(Vec<usize>, Option<usize>), EinsumError> {
+    let mut labels = Vec::new();
+    let mut ellipsis = None;
+    let mut chars = term.chars().filter(|c| !c.is_whitespace());
+
+    while let Some(c) = chars.next() {
+        match c {
+            'A'..='Z' => labels.push(c as usize - 'A' as usize),
+            'a'..='z' => labels.push(c as usize - 'a' as usize + 26),
+            '.' if ellipsis.is_none() && chars.next() == Some('.') && chars.next() == Some('.') => {
+                ellipsis = Some(labels.len());
+            }
+            _ => return Err(EinsumError::InvalidSubscripts),
+        }
+    }
+
+    Ok((labels, ellipsis))
+}
@@ -0,0 +1,177 @@
+use crate::dim::Dyn;
+use crate::layout::Layout;
+use crate::slice::DSlice;
+use crate::tensor::Tensor;
+
+use super::{Diag, LinalgError, Scalar, Triangle, solve_triangular};
+
+/// Returns the determinant of a square matrix.
+///
+/// The determinant is zero if the matrix is singular, and it is infinite or NaN if
+/// the matrix has elements that are not finite.
+///
+/// # Panics
+///
+/// Panics if the matrix is not square.
+pub fn det<T: Scalar, L: Layout>(a: &DSlice<T, 2, L>) -> T {
+    assert!(a.dim(0) == a.dim(1), "matrix not square");
+
+    let mut lu = a.to_tensor();
+    let (pivots, _) = factorize(&mut lu);
+
+    let mut det = T::ONE;
+
+    for i in 0..lu.dim(0) {
+        det *= if pivots[i] == i { lu[[i, i]] } else { -lu[[i, i]] };
+    }
+
+    det
+}
+
+/// Returns the inverse of a square matrix.
+///
+/// # Errors
+///
+/// Returns an error if the matrix is singular.
+///
+/// # Panics
+///
+/// Panics if the matrix is not square.
+pub fn inverse<T: Scalar, L: Layout>(
+    a: &DSlice<T, 2, L>,
+) -> Result<Tensor<T, (Dyn, Dyn)>, LinalgError> {
+    let n = a.dim(0);
+    let identity = Tensor::from_fn((n, n), |i| if i[0] == i[1] { T::ONE } else { T::ZERO });
+
+    solve(a, &identity)
+}
+
+/// Computes the LU factorization with partial pivoting in place.
+///
+/// The factorization is `P * A = L * U`, where `P` is a permutation matrix, `L` is
+/// lower triangular with unit diagonal and `U` is upper triangular. The elements of
+/// `L` below the diagonal and `U` are stored in the input matrix, and the returned
+/// pivot indices give the row that was swapped with row `i` in step `i`.
+///
+/// # Errors
+///
+/// Returns an error if the matrix is singular, where the index is for the first zero
+/// or non-finite pivot. The contents of the matrix are then unspecified.
+///
+/// # Examples
+///
+/// ```
+/// use mdarray::{linalg, tensor};
+///
+/// let mut a = tensor![[2.0, 4.0], [4.0, 2.0]];
+/// let p = linalg::lu(&mut a).unwrap();
+///
+/// assert_eq!(p, tensor![1, 1]);
+/// assert_eq!(a, tensor![[4.0, 2.0], [0.5, 3.0]]);
+/// ```
+pub fn lu<T: Scalar, L: Layout>(
+    a: &mut DSlice<T, 2, L>,
+) -> Result<Tensor<usize, (Dyn,)>, LinalgError> {
+    let (pivots, singular) = factorize(a);
+
+    match singular {
+        Some(index) => Err(LinalgError::Singular(index)),
+        None => Ok(pivots),
+    }
+}
+
+/// Solves the linear system `A * X = B` for a square matrix `A`.
+///
+/// The matrix `B` has one column for each right-hand side.
+///
+/// # Errors
+///
+/// Returns an error if the matrix `A` is singular.
+///
+/// # Panics
+///
+/// Panics if the matrix `A` is not square, or if the number of rows in `B` is not
+/// matching the size of `A`.
+pub fn solve<T: Scalar, L: Layout, M: Layout>(
+    a: &DSlice<T, 2, L>,
+    b: &DSlice<T, 2, M>,
+) -> Result<Tensor<T, (Dyn, Dyn)>, LinalgError> {
+    assert!(a.dim(0) == a.dim(1), "matrix not square");
+    assert!(b.dim(0) == a.dim(0), "shape mismatch");
+
+    let mut lu = a.to_tensor();
+    let pivots = self::lu(&mut lu)?;
+
+    let n = b.dim(0);
+    let mut x = b.to_tensor();
+
+    for i in 0..n {
+        if pivots[i] != i {
+            swap_rows(&mut x, i, pivots[i]);
+        }
+    }
+
+    solve_triangular(&lu, &mut x, Triangle::Lower, Diag::Unit, false)?;
+    solve_triangular(&lu, &mut x, Triangle::Upper, Diag::NonUnit, false)?;
+
+    Ok(x)
+}
+
+fn factorize<T: Scalar, L: Layout>(
+    a: &mut DSlice<T, 2, L>,
+) -> (Tensor<usize, (Dyn,)>, Option<usize>) {
+    let (m, n) = *a.shape();
+    let mut pivots = Tensor::from_elem((m.min(n),), 0);
+    let mut singular = None;
+
+    for j in 0..m.min(n) {
+        let mut p = j;
+
+        for i in j + 1..m {
+            if a[[i, j]].abs() > a[[p, j]].abs() {
+                p = i;
+            }
+        }
+
+        pivots[j] = p;
+
+        if p != j {
+            swap_rows(a, j, p);
+        }
+
+        let pivot = a[[j, j]];
+
+        // A pivot that is NaN or infinite is also singular, since the elimination would
+        // otherwise give NaN values.
+        if pivot == T::ZERO || !pivot.is_finite() {
+            _ = singular.get_or_insert(j);
+
+            continue;
+        }
+
+        for i in j + 1..m {
+            a[[i, j]] /= pivot;
+
+            let l = a[[i, j]];
+
+            if l != T::ZERO {
+                for k in j + 1..n {
+                    let value = a[[j, k]];
+
+                    a[[i, k]] -= l * value;
+                }
+            }
+        }
+    }
+
+    (pivots, singular)
+}
+
+fn swap_rows<T: Copy, L: Layout>(a: &mut DSlice<T, 2, L>, i: usize, j: usize) {
+    for k in 0..a.dim(1) {
+        let value = a[[i, k]];
+
+        a[[i, k]] = a[[j, k]];
+        a[[j, k]] = value;
+    }
+}
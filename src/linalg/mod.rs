@@ -0,0 +1,155 @@
+//! Linear algebra module, with matrix factorizations and solvers in pure Rust.
+//!
+//! The factorizations operate in place on 2-dimensional array slices with any layout,
+//! and follow the storage conventions in LAPACK. The functions are intended for small
+//! and moderate-size matrices, where linking to LAPACK is not needed.
+//!
+//! Matrix products dispatch through the backend traits `Level1`, `Level2` and `Level3`,
+//! which can be implemented to call external BLAS libraries. The `Reference` backend
+//! is a portable implementation, and is used by default.
+//!
+//! # Examples
+//!
+//! ```
+//! use mdarray::linalg;
+//! use mdarray::tensor;
+//!
+//! let a = tensor![[4.0, 3.0], [6.0, 3.0]];
+//! let b = tensor![[10.0], [12.0]];
+//!
+//! assert_eq!(linalg::solve(&a, &b).unwrap(), tensor![[1.0], [2.0]]);
+//! assert_eq!(linalg::det(&a), -6.0);
+//! ```
+
+mod backend;
+mod cholesky;
+mod eigh;
+mod einsum;
+mod lu;
+mod norm;
+mod product;
+mod qr;
+mod svd;
+mod triangular;
+
+pub use backend::{Level1, Level2, Level3, Reference, matmul, matmul_with};
+pub use backend::{matmul_hn, matmul_hn_with, matmul_nh, matmul_nh_with};
+pub use cholesky::cholesky;
+pub use eigh::eigh;
+pub use einsum::{EinsumError, einsum, einsum_typed};
+pub use lu::{det, inverse, lu, solve};
+pub use norm::{MatrixNorm, Norm, cond, matrix_norm, norm, norm_axis, spectral_norm};
+pub use product::{kron, kron_expr, matrix_power, outer, outer_expr};
+pub use qr::{qr, qr_q};
+pub use svd::{lstsq, svd};
+pub use triangular::{Diag, Triangle, Triangular, TriangularMut, solve_triangular, symmetrize};
+
+use core::fmt::Debug;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use thiserror::Error;
+
+/// Error type for matrix factorizations and solvers.
+#[derive(Debug, Error)]
+pub enum LinalgError {
+    /// The matrix is singular, with a zero or non-finite pivot at the given index.
+    #[error("matrix is singular")]
+    Singular(usize),
+    /// The matrix is not positive definite, with a non-positive pivot at the given index.
+    #[error("matrix is not positive definite")]
+    NotPositiveDefinite(usize),
+    /// The iterative algorithm did not converge.
+    #[error("algorithm did not converge")]
+    NoConvergence,
+}
+
+/// Trait for element types in matrix products and contractions.
+pub trait Element:
+    Copy
+    + Debug
+    + Default
+    + PartialEq
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+{
+    /// The additive identity.
+    const ZERO: Self;
+
+    /// The multiplicative identity.
+    const ONE: Self;
+
+    /// Returns the complex conjugate, which is the value itself for real numbers.
+    fn conj(self) -> Self {
+        self
+    }
+}
+
+/// Trait for floating-point element types in linear algebra operations.
+pub trait Scalar: Element + PartialOrd + Div<Output = Self> + DivAssign {
+    /// The difference between one and the next larger representable number.
+    const EPSILON: Self;
+
+    /// Returns the absolute value.
+    fn abs(self) -> Self;
+
+    /// Returns true if the value is neither infinite nor NaN.
+    fn is_finite(self) -> bool;
+
+    /// Returns true if the value is NaN.
+    fn is_nan(self) -> bool;
+
+    /// Converts from an unsigned integer, rounding to the nearest value.
+    fn from_usize(value: usize) -> Self;
+
+    /// Returns the value raised to a floating-point power.
+    fn powf(self, n: Self) -> Self;
+
+    /// Returns the square root.
+    fn sqrt(self) -> Self;
+}
+
+macro_rules! impl_scalar {
+    ($($type:tt),+) => {
+        $(
+            impl Element for $type {
+                const ZERO: Self = 0.0;
+                const ONE: Self = 1.0;
+            }
+
+            impl Scalar for $type {
+                const EPSILON: Self = $type::EPSILON;
+
+                fn abs(self) -> Self {
+                    self.abs()
+                }
+
+                fn is_finite(self) -> bool {
+                    self.is_finite()
+                }
+
+                fn is_nan(self) -> bool {
+                    self.is_nan()
+                }
+
+                fn from_usize(value: usize) -> Self {
+                    value as Self
+                }
+
+                fn powf(self, n: Self) -> Self {
+                    self.powf(n)
+                }
+
+                fn sqrt(self) -> Self {
+                    self.sqrt()
+                }
+            }
+        )+
+    };
+}
+
+impl_scalar!(f32, f64);
@@ -0,0 +1,251 @@
+use crate::dim::Const;
+use crate::expr::{Expression, FromExpression};
+use crate::index::Axis;
+use crate::layout::Layout;
+use crate::shape::Shape;
+use crate::slice::{DSlice, Slice};
+use crate::tensor::Tensor;
+
+use super::{LinalgError, Scalar, inverse, matmul};
+
+// Maximum number of iterations for the spectral norm estimate.
+const MAX_ITER: usize = 100;
+
+/// Induced matrix norm or Frobenius norm.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum MatrixNorm {
+    /// Frobenius norm, which is the 2-norm of the elements.
+    Frobenius,
+    /// Induced 1-norm, which is the maximum absolute column sum.
+    One,
+    /// Induced infinity norm, which is the maximum absolute row sum.
+    Inf,
+}
+
+/// Vector norm of the elements in an array slice.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Norm<T> {
+    /// Sum of the absolute values.
+    L1,
+    /// Euclidean norm.
+    L2,
+    /// Maximum absolute value.
+    Inf,
+    /// General p-norm, for `p >= 1`.
+    P(T),
+}
+
+/// Returns the condition number of a square matrix in the induced 1-norm.
+///
+/// # Errors
+///
+/// Returns an error if the matrix is singular.
+///
+/// # Panics
+///
+/// Panics if the matrix is not square.
+///
+/// # Examples
+///
+/// ```
+/// use mdarray::linalg;
+/// use mdarray::tensor;
+///
+/// let a = tensor![[2.0, 0.0], [0.0, 0.5]];
+///
+/// assert_eq!(linalg::cond(&a).unwrap(), 4.0);
+/// ```
+pub fn cond<T: Scalar, L: Layout>(a: &DSlice<T, 2, L>) -> Result<T, LinalgError> {
+    assert!(a.dim(0) == a.dim(1), "matrix not square");
+
+    let inv = inverse(a)?;
+
+    Ok(matrix_norm(a, MatrixNorm::One) * matrix_norm(&inv, MatrixNorm::One))
+}
+
+/// Returns the induced matrix norm or the Frobenius norm of a matrix.
+///
+/// # Examples
+///
+/// ```
+/// use mdarray::linalg::{self, MatrixNorm};
+/// use mdarray::tensor;
+///
+/// let a = tensor![[1.0, -2.0], [3.0, 4.0]];
+///
+/// assert_eq!(linalg::matrix_norm(&a, MatrixNorm::One), 6.0);
+/// assert_eq!(linalg::matrix_norm(&a, MatrixNorm::Inf), 7.0);
+/// ```
+pub fn matrix_norm<T: Scalar, L: Layout>(a: &DSlice<T, 2, L>, ord: MatrixNorm) -> T {
+    match ord {
+        MatrixNorm::Frobenius => norm(a, Norm::L2),
+        MatrixNorm::One => norm(&norm_axis(a, Const::<0>, Norm::L1), Norm::Inf),
+        MatrixNorm::Inf => norm(&norm_axis(a, Const::<1>, Norm::L1), Norm::Inf),
+    }
+}
+
+/// Returns the vector norm of the elements in an array slice.
+///
+/// The 2-norm and p-norms are computed with scaling, so that there is no overflow
+/// or underflow in intermediate results unless the norm itself is out of range.
+///
+/// The norm is NaN if an element is NaN, except for the 2-norm which is infinite if
+/// an element is infinite, like the `hypot` function.
+///
+/// # Panics
+///
+/// Panics if the order of a p-norm is less than one.
+///
+/// # Examples
+///
+/// ```
+/// use mdarray::linalg::{self, Norm};
+/// use mdarray::tensor;
+///
+/// let a = tensor![3.0, -4.0];
+///
+/// assert_eq!(linalg::norm(&a, Norm::L1), 7.0);
+/// assert_eq!(linalg::norm(&a, Norm::L2), 5.0);
+/// assert_eq!(linalg::norm(&a, Norm::Inf), 4.0);
+/// assert_eq!(linalg::norm(&tensor![1e300, 1e300], Norm::L2), 2f64.sqrt() * 1e300);
+/// ```
+pub fn norm<T: Scalar, S: Shape, L: Layout>(a: &Slice<T, S, L>, ord: Norm<T>) -> T {
+    match ord {
+        Norm::L1 => a.iter().fold(T::ZERO, |acc, x| acc + x.abs()),
+        Norm::L2 => {
+            let (mut scale, mut ssq) = (T::ZERO, T::ONE);
+
+            for x in a.iter().map(|x| x.abs()) {
+                if x > scale {
+                    if !x.is_finite() {
+                        return x;
+                    }
+
+                    ssq = T::ONE + ssq * (scale / x) * (scale / x);
+                    scale = x;
+                } else if x != T::ZERO {
+                    ssq += (x / scale) * (x / scale);
+                }
+            }
+
+            scale * ssq.sqrt()
+        }
+        Norm::Inf => a.iter().fold(T::ZERO, |acc, x| {
+            // Propagate NaN, since it is not greater than other values.
+            if x.abs() > acc || x.is_nan() { x.abs() } else { acc }
+        }),
+        Norm::P(p) if p == T::ONE => norm(a, Norm::L1),
+        Norm::P(p) if p == T::ONE + T::ONE => norm(a, Norm::L2),
+        Norm::P(p) => {
+            assert!(p >= T::ONE, "invalid norm order");
+
+            let scale = norm(a, Norm::Inf);
+
+            if scale == T::ZERO || !scale.is_finite() {
+                return scale;
+            }
+
+            let sum = a.iter().fold(T::ZERO, |acc, x| acc + (x.abs() / scale).powf(p));
+
+            scale * sum.powf(T::ONE / p)
+        }
+    }
+}
+
+/// Returns the vector norms along the given axis, with the axis removed from the shape.
+///
+/// # Panics
+///
+/// Panics if the order of a p-norm is less than one, or if the axis is out of bounds.
+///
+/// # Examples
+///
+/// ```
+/// use mdarray::index::Cols;
+/// use mdarray::linalg::{self, Norm};
+/// use mdarray::tensor;
+///
+/// let a = tensor![[3.0, 1.0], [4.0, -1.0]];
+///
+/// assert_eq!(linalg::norm_axis(&a, Cols, Norm::L2), tensor![5.0, 2f64.sqrt()]);
+/// ```
+pub fn norm_axis<T: Scalar, S: Shape, L: Layout, A: Axis>(
+    a: &Slice<T, S, L>,
+    axis: A,
+    ord: Norm<T>,
+) -> Tensor<T, A::Remove<S>> {
+    FromExpression::from_expr(a.lanes(axis).map(|x| norm(&x, ord)))
+}
+
+/// Returns an estimate of the spectral norm of a matrix, which is the largest
+/// singular value.
+///
+/// The estimate is computed with power iteration on `A^T * A`, and is a lower bound.
+/// The iteration stops when the relative change in the estimate is less than the
+/// square root of the epsilon. The error in the estimate can be larger than this if
+/// the two largest singular values are close, since the rate of convergence is given
+/// by the squared ratio between them.
+///
+/// # Errors
+///
+/// Returns an error if the iteration does not converge within the maximum number
+/// of iterations.
+///
+/// # Examples
+///
+/// ```
+/// use mdarray::linalg;
+/// use mdarray::tensor;
+///
+/// let a = tensor![[3.0, 0.0], [4.0, 5.0]];
+///
+/// assert!((linalg::spectral_norm(&a).unwrap() - 45f64.sqrt()).abs() < 1e-6);
+/// ```
+pub fn spectral_norm<T: Scalar, L: Layout>(a: &DSlice<T, 2, L>) -> Result<T, LinalgError> {
+    let tol = T::EPSILON.sqrt();
+
+    // Start with the absolute column sums, which is nonzero unless the matrix is zero.
+    let sums = norm_axis(a, Const::<0>, Norm::L1);
+    let mut x = Tensor::from_fn((a.dim(1), 1), |i| sums[i[0]]);
+    let mut estimate = T::ZERO;
+    let mut restarted = false;
+
+    for _ in 0..MAX_ITER {
+        let scale = norm(&x, Norm::L2);
+
+        // The norm is zero for a zero matrix, and not finite if there are elements
+        // that are infinite or NaN.
+        if scale == T::ZERO || !scale.is_finite() {
+            return Ok(scale);
+        }
+
+        x.iter_mut().for_each(|x| *x /= scale);
+
+        let y = matmul(a, &x);
+        let value = norm(&y, Norm::L2);
+
+        // The start vector can be in the null space also for a nonzero matrix. In that
+        // case, restart with the unit vector for the column with the largest norm.
+        if value == T::ZERO && !restarted {
+            let norms = norm_axis(a, Const::<0>, Norm::L2);
+            let j = (0..norms.len()).fold(0, |j, k| if norms[k] > norms[j] { k } else { j });
+
+            x = Tensor::from_fn((a.dim(1), 1), |i| if i[0] == j { T::ONE } else { T::ZERO });
+            restarted = true;
+
+            continue;
+        }
+
+        x = Tensor::from_fn((a.dim(1), 1), |i| {
+            (0..a.dim(0)).fold(T::ZERO, |acc, k| acc + a[[k, i[0]]] * y[[k, 0]])
+        });
+
+        if (value - estimate).abs() <= tol * value {
+            return Ok(value);
+        }
+
+        estimate = value;
+    }
+
+    Err(LinalgError::NoConvergence)
+}
@@ -0,0 +1,168 @@
+use std::vec;
+use std::vec::Vec;
+
+use core::ops::Mul;
+
+use crate::dim::Dyn;
+use crate::expr::{self, FromExpression, FromFn};
+use crate::layout::Layout;
+use crate::shape::{DynRank, Shape};
+use crate::slice::{DSlice, Slice};
+use crate::tensor::Tensor;
+
+use super::{Element, matmul};
+
+/// Returns the Kronecker product of two array slices with the same rank.
+///
+/// The resulting dimensions are the products of the dimensions in `A` and `B`, and
+/// each element in `A` is replaced by a block with the element multiplied by `B`.
+///
+/// # Panics
+///
+/// Panics if the ranks are not matching, or if the resulting length overflows.
+///
+/// # Examples
+///
+/// ```
+/// use mdarray::linalg;
+/// use mdarray::tensor;
+///
+/// let a = tensor![[1, 2], [3, 4]];
+/// let b = tensor![[0, 1], [1, 0]];
+///
+/// let c = tensor![[0, 1, 0, 2], [1, 0, 2, 0], [0, 3, 0, 4], [3, 0, 4, 0]];
+///
+/// assert_eq!(linalg::kron(&a, &b), c);
+/// ```
+pub fn kron<T: Clone + Mul<Output = T>, S: Shape, L: Layout, M: Layout>(
+    a: &Slice<T, S, L>,
+    b: &Slice<T, S, M>,
+) -> Tensor<T, S::Dyn> {
+    FromExpression::from_expr(kron_expr(a, b))
+}
+
+/// Returns an expression for the Kronecker product of two array slices with the
+/// same rank, without allocating the result.
+///
+/// # Panics
+///
+/// Panics if the ranks are not matching, or if the resulting length overflows.
+pub fn kron_expr<'a, T: Clone + Mul<Output = T>, S: Shape, L: Layout, M: Layout>(
+    a: &'a Slice<T, S, L>,
+    b: &'a Slice<T, S, M>,
+) -> FromFn<S::Dyn, impl FnMut(&[usize]) -> T + use<'a, T, S, L, M>> {
+    assert!(a.rank() == b.rank(), "rank mismatch");
+
+    let dims = (0..a.rank())
+        .map(|i| a.dim(i).checked_mul(b.dim(i)).expect("invalid length"))
+        .collect::<Vec<_>>();
+
+    let shape = S::Dyn::from_dims(&dims);
+    let mut index_a = vec![0; a.rank()];
+    let mut index_b = vec![0; b.rank()];
+
+    expr::from_fn(shape, move |index| {
+        for (i, &x) in index.iter().enumerate() {
+            (index_a[i], index_b[i]) = (x / b.dim(i), x % b.dim(i));
+        }
+
+        a[&index_a[..]].clone() * b[&index_b[..]].clone()
+    })
+}
+
+/// Returns the matrix power `A^n` of a square matrix, using repeated squaring.
+///
+/// The identity matrix is returned for `n = 0`.
+///
+/// # Panics
+///
+/// Panics if the matrix is not square.
+///
+/// # Examples
+///
+/// ```
+/// use mdarray::linalg;
+/// use mdarray::tensor;
+///
+/// let a = tensor![[1.0, 1.0], [1.0, 0.0]];
+///
+/// assert_eq!(linalg::matrix_power(&a, 10), tensor![[89.0, 55.0], [55.0, 34.0]]);
+/// ```
+pub fn matrix_power<T: Element, L: Layout>(a: &DSlice<T, 2, L>, n: u32) -> Tensor<T, (Dyn, Dyn)> {
+    assert!(a.dim(0) == a.dim(1), "matrix not square");
+
+    let mut base = Tensor::from_fn(*a.shape(), |i| a[[i[0], i[1]]]);
+    let mut power = None::<Tensor<T, (Dyn, Dyn)>>;
+    let mut n = n;
+
+    while n > 0 {
+        if n & 1 == 1 {
+            power = Some(match power {
+                Some(power) => matmul(&power, &base),
+                None => base.clone(),
+            });
+        }
+
+        n >>= 1;
+
+        if n > 0 {
+            base = matmul(&base, &base);
+        }
+    }
+
+    power.unwrap_or_else(|| {
+        Tensor::from_fn(*a.shape(), |i| if i[0] == i[1] { T::ONE } else { T::ZERO })
+    })
+}
+
+/// Returns the outer product of two array slices.
+///
+/// The resulting shape is the concatenation of the shapes of `A` and `B`, and the
+/// elements are the products of all pairs of elements in `A` and `B`.
+///
+/// # Panics
+///
+/// Panics if the resulting length overflows.
+///
+/// # Examples
+///
+/// ```
+/// use mdarray::linalg;
+/// use mdarray::tensor;
+///
+/// let a = tensor![1, 2];
+/// let b = tensor![[1, 10, 100]];
+///
+/// let c = tensor![[[1, 10, 100]], [[2, 20, 200]]];
+///
+/// assert_eq!(linalg::outer(&a, &b), c.into_dyn());
+/// ```
+pub fn outer<T, S: Shape, L: Layout, R: Shape, M: Layout>(
+    a: &Slice<T, S, L>,
+    b: &Slice<T, R, M>,
+) -> Tensor<T, DynRank>
+where
+    T: Clone + Mul<Output = T>,
+{
+    FromExpression::from_expr(outer_expr(a, b))
+}
+
+/// Returns an expression for the outer product of two array slices, without
+/// allocating the result.
+///
+/// # Panics
+///
+/// Panics if the resulting length overflows.
+pub fn outer_expr<'a, T, S: Shape, L: Layout, R: Shape, M: Layout>(
+    a: &'a Slice<T, S, L>,
+    b: &'a Slice<T, R, M>,
+) -> FromFn<DynRank, impl FnMut(&[usize]) -> T + use<'a, T, S, L, R, M>>
+where
+    T: Clone + Mul<Output = T>,
+{
+    let dims = a.shape().with_dims(|x| b.shape().with_dims(|y| [x, y].concat()));
+    let shape = DynRank::from_dims(&dims);
+    let rank = a.rank();
+
+    expr::from_fn(shape, move |index| a[&index[..rank]].clone() * b[&index[rank..]].clone())
+}
@@ -0,0 +1,113 @@
+use crate::dim::Dyn;
+use crate::layout::Layout;
+use crate::slice::DSlice;
+use crate::tensor::Tensor;
+
+use super::Scalar;
+
+/// Computes the QR factorization with Householder reflections in place.
+///
+/// The factorization is `A = Q * R`, where `Q` is orthogonal and `R` is upper
+/// triangular. The elements of `R` are stored on and above the diagonal of the input
+/// matrix, and the Householder vectors below the diagonal. The returned scalar
+/// factors give the reflections `H_i = I - tau_i * v_i * v_i^T`, where `v_i` has
+/// an implicit one at index `i`, and `Q = H_0 * H_1 * ... * H_(k-1)`.
+///
+/// # Examples
+///
+/// ```
+/// use mdarray::{linalg, tensor};
+///
+/// let a = tensor![[3.0, 1.0], [4.0, 2.0]];
+/// let mut r = a.clone();
+///
+/// let tau = linalg::qr(&mut r);
+/// let q = linalg::qr_q(&r, &tau[..]);
+///
+/// assert_eq!(r[[0, 0]], -5.0);
+/// assert!((q[[0, 0]] * r[[0, 1]] + q[[0, 1]] * r[[1, 1]] - 1.0f64).abs() < 1e-15);
+/// ```
+pub fn qr<T: Scalar, L: Layout>(a: &mut DSlice<T, 2, L>) -> Tensor<T, (Dyn,)> {
+    let (m, n) = *a.shape();
+    let mut tau = Tensor::from_elem((m.min(n),), T::ZERO);
+
+    for j in 0..m.min(n) {
+        let mut norm_sqr = T::ZERO;
+
+        for i in j + 1..m {
+            norm_sqr += a[[i, j]] * a[[i, j]];
+        }
+
+        if norm_sqr == T::ZERO {
+            continue;
+        }
+
+        let alpha = a[[j, j]];
+        let norm = (alpha * alpha + norm_sqr).sqrt();
+        let beta = if alpha < T::ZERO { norm } else { -norm };
+
+        let scale = T::ONE / (alpha - beta);
+
+        for i in j + 1..m {
+            a[[i, j]] *= scale;
+        }
+
+        a[[j, j]] = beta;
+        tau[j] = (beta - alpha) / beta;
+
+        for k in j + 1..n {
+            let mut w = a[[j, k]];
+
+            for i in j + 1..m {
+                w += a[[i, j]] * a[[i, k]];
+            }
+
+            w *= tau[j];
+            a[[j, k]] -= w;
+
+            for i in j + 1..m {
+                let v = a[[i, j]];
+
+                a[[i, k]] -= v * w;
+            }
+        }
+    }
+
+    tau
+}
+
+/// Returns the orthogonal matrix from a QR factorization.
+///
+/// The input is the result from `qr`, and the returned matrix has the same number
+/// of rows as the input and the number of columns equal to the number of reflections.
+///
+/// # Panics
+///
+/// Panics if the number of reflections is larger than the matrix dimensions.
+pub fn qr_q<T: Scalar, L: Layout>(a: &DSlice<T, 2, L>, tau: &[T]) -> Tensor<T, (Dyn, Dyn)> {
+    let (m, n) = *a.shape();
+    let k = tau.len();
+
+    assert!(k <= m && k <= n, "invalid number of reflections");
+
+    let mut q = Tensor::from_fn((m, k), |i| if i[0] == i[1] { T::ONE } else { T::ZERO });
+
+    for j in (0..k).rev() {
+        for c in j..k {
+            let mut w = q[[j, c]];
+
+            for i in j + 1..m {
+                w += a[[i, j]] * q[[i, c]];
+            }
+
+            w *= tau[j];
+            q[[j, c]] -= w;
+
+            for i in j + 1..m {
+                q[[i, c]] -= a[[i, j]] * w;
+            }
+        }
+    }
+
+    q
+}
@@ -0,0 +1,220 @@
+use std::vec::Vec;
+
+use core::cmp::Ordering;
+
+use crate::dim::Dyn;
+use crate::layout::Layout;
+use crate::slice::DSlice;
+use crate::tensor::Tensor;
+
+use super::eigh::{MAX_SWEEPS, identity, rotate_cols, rotation};
+use super::{LinalgError, Norm, Scalar, norm};
+
+/// Computes the minimum-norm least squares solution to `A * X = B`.
+///
+/// The solution minimizes the 2-norm of `A * X - B` for each column in `B`, and has
+/// minimum norm if `A` is rank deficient. Singular values of `A` less than or equal
+/// to `max(m, n) * eps * s_max` are treated as zero, where `s_max` is the largest
+/// singular value.
+///
+/// # Errors
+///
+/// Returns an error if the singular value decomposition does not converge.
+///
+/// # Panics
+///
+/// Panics if the number of rows in `B` is not matching the number of rows in `A`.
+///
+/// # Examples
+///
+/// ```
+/// use mdarray::{linalg, tensor};
+///
+/// let a = tensor![[1.0, 0.0], [1.0, 1.0], [1.0, 2.0]];
+/// let b = tensor![[1.0], [2.0], [4.0]];
+///
+/// let x = linalg::lstsq(&a, &b).unwrap();
+///
+/// assert!((x[[0, 0]] - 5.0 / 6.0f64).abs() < 1e-14);
+/// assert!((x[[1, 0]] - 1.5f64).abs() < 1e-14);
+/// ```
+pub fn lstsq<T: Scalar, L: Layout, M: Layout>(
+    a: &DSlice<T, 2, L>,
+    b: &DSlice<T, 2, M>,
+) -> Result<Tensor<T, (Dyn, Dyn)>, LinalgError> {
+    assert!(b.dim(0) == a.dim(0), "shape mismatch");
+
+    let (m, n) = *a.shape();
+    let (u, s, vt) = svd(a)?;
+
+    let tol = T::from_usize(m.max(n)) * T::EPSILON * if s.is_empty() { T::ZERO } else { s[0] };
+
+    // Compute U^T * B, scaled with the inverse of the nonzero singular values.
+    let c = Tensor::from_fn((s.len(), b.dim(1)), |i| {
+        if s[i[0]] > tol {
+            (0..m).fold(T::ZERO, |acc, k| acc + u[[k, i[0]]] * b[[k, i[1]]]) / s[i[0]]
+        } else {
+            T::ZERO
+        }
+    });
+
+    Ok(Tensor::from_fn((n, b.dim(1)), |i| {
+        (0..s.len()).fold(T::ZERO, |acc, k| acc + vt[[k, i[0]]] * c[[k, i[1]]])
+    }))
+}
+
+/// Computes the thin singular value decomposition of a matrix.
+///
+/// The decomposition is `A = U * diag(s) * V^T`, where the singular values `s` are
+/// nonnegative and in descending order. For an `m x n` matrix and `k = min(m, n)`,
+/// the matrix `U` is `m x k` and `V^T` is `k x n`, both with orthonormal rows or
+/// columns.
+///
+/// The decomposition is computed with the one-sided Jacobi method, which has high
+/// relative accuracy and is suitable for small and moderate-size matrices.
+///
+/// # Errors
+///
+/// Returns an error if the matrix has elements that are infinite or NaN, or if the
+/// iteration does not converge.
+///
+/// # Examples
+///
+/// ```
+/// use mdarray::{linalg, tensor};
+///
+/// let a = tensor![[3.0, 0.0], [0.0, -4.0], [0.0, 0.0]];
+/// let (u, s, vt) = linalg::svd(&a).unwrap();
+///
+/// assert_eq!(s, tensor![4.0, 3.0]);
+/// assert_eq!(u, tensor![[0.0, 1.0], [-1.0, 0.0], [0.0, 0.0]]);
+/// assert_eq!(vt, tensor![[0.0, 1.0], [1.0, 0.0]]);
+/// ```
+pub fn svd<T: Scalar, L: Layout>(
+    a: &DSlice<T, 2, L>,
+) -> Result<(Tensor<T, (Dyn, Dyn)>, Tensor<T, (Dyn,)>, Tensor<T, (Dyn, Dyn)>), LinalgError> {
+    let (m, n) = *a.shape();
+
+    // Scale the matrix to avoid overflow and underflow in the column norms.
+    let scale = norm(a, Norm::Inf);
+
+    // The iteration cannot converge if there are elements that are infinite or NaN.
+    if !scale.is_finite() {
+        return Err(LinalgError::NoConvergence);
+    }
+
+    let scale = if scale == T::ZERO { T::ONE } else { scale };
+
+    let (u, mut s, vt) = if m >= n {
+        let (u, s, v) = jacobi(Tensor::from_fn((m, n), |i| a[[i[0], i[1]]] / scale))?;
+
+        (u, s, Tensor::from_fn((n, n), |i| v[[i[1], i[0]]]))
+    } else {
+        let (u, s, v) = jacobi(Tensor::from_fn((n, m), |i| a[[i[1], i[0]]] / scale))?;
+
+        (v, s, Tensor::from_fn((m, n), |i| u[[i[1], i[0]]]))
+    };
+
+    s.iter_mut().for_each(|x| *x *= scale);
+
+    Ok((u, s, vt))
+}
+
+// Computes the decomposition A = U * diag(s) * V^T for m >= n, with the columns in
+// A orthogonalized in place.
+fn jacobi<T: Scalar>(
+    mut a: Tensor<T, (Dyn, Dyn)>,
+) -> Result<(Tensor<T, (Dyn, Dyn)>, Tensor<T, (Dyn,)>, Tensor<T, (Dyn, Dyn)>), LinalgError> {
+    let (m, n) = *a.shape();
+    let mut v = identity(n);
+
+    for _ in 0..MAX_SWEEPS {
+        let mut rotated = false;
+
+        for p in 0..n {
+            for q in p + 1..n {
+                let mut alpha = T::ZERO;
+                let mut beta = T::ZERO;
+                let mut gamma = T::ZERO;
+
+                for k in 0..m {
+                    alpha += a[[k, p]] * a[[k, p]];
+                    beta += a[[k, q]] * a[[k, q]];
+                    gamma += a[[k, p]] * a[[k, q]];
+                }
+
+                if gamma.abs() <= T::EPSILON * alpha.sqrt() * beta.sqrt() {
+                    continue;
+                }
+
+                let (c, s) = rotation(alpha, beta, gamma);
+
+                rotate_cols(&mut a, p, q, c, s);
+                rotate_cols(&mut v, p, q, c, s);
+
+                rotated = true;
+            }
+        }
+
+        if !rotated {
+            let norms = Tensor::from_fn((n,), |i| norm(&a.view(.., i[0]), Norm::L2));
+            let mut order = (0..n).collect::<Vec<_>>();
+
+            order.sort_by(|&i, &j| norms[j].partial_cmp(&norms[i]).unwrap_or(Ordering::Equal));
+
+            let s = Tensor::from_fn((n,), |i| norms[order[i[0]]]);
+            let v = Tensor::from_fn((n, n), |i| v[[i[0], order[i[1]]]]);
+
+            let mut u = Tensor::from_fn((m, n), |i| {
+                let j = order[i[1]];
+
+                if norms[j] > T::ZERO { a[[i[0], j]] / norms[j] } else { T::ZERO }
+            });
+
+            complete_basis(&mut u, &s);
+
+            return Ok((u, s, v));
+        }
+    }
+
+    Err(LinalgError::NoConvergence)
+}
+
+// Replaces the columns for zero singular values with orthonormal vectors, by
+// orthogonalizing the unit vector with the largest remaining component.
+fn complete_basis<T: Scalar>(u: &mut DSlice<T, 2>, s: &DSlice<T, 1>) {
+    let m = u.dim(0);
+
+    for j in 0..s.len() {
+        if s[j] > T::ZERO {
+            continue;
+        }
+
+        let mut best = (T::ZERO, Tensor::from_elem((m,), T::ZERO));
+
+        for e in 0..m {
+            let mut x = Tensor::from_fn((m,), |i| if i[0] == e { T::ONE } else { T::ZERO });
+
+            // Orthogonalize twice against the other columns for numerical stability.
+            for _ in 0..2 {
+                for k in (0..s.len()).filter(|&k| k != j) {
+                    let dot = (0..m).fold(T::ZERO, |acc, i| acc + u[[i, k]] * x[i]);
+
+                    for i in 0..m {
+                        x[i] -= dot * u[[i, k]];
+                    }
+                }
+            }
+
+            let value = norm(&x, Norm::L2);
+
+            if value > best.0 {
+                best = (value, x);
+            }
+        }
+
+        for i in 0..m {
+            u[[i, j]] = best.1[i] / best.0;
+        }
+    }
+}
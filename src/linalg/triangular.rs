@@ -0,0 +1,309 @@
+use core::ops::Range;
+
+use crate::dim::Dyn;
+use crate::layout::Layout;
+use crate::shape::Shape;
+use crate::slice::{DSlice, Slice};
+use crate::tensor::Tensor;
+
+use super::{Element, LinalgError, Scalar};
+
+/// Diagonal type for triangular matrices.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Diag {
+    /// The diagonal is stored in the matrix.
+    NonUnit,
+    /// The diagonal elements are assumed to be one, and are not accessed.
+    Unit,
+}
+
+/// Triangle selection for triangular and symmetric matrices.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Triangle {
+    /// Lower triangle, with elements on and below the diagonal.
+    Lower,
+    /// Upper triangle, with elements on and above the diagonal.
+    Upper,
+}
+
+/// Triangular view of a 2-dimensional array slice.
+///
+/// The view gives access to the elements in one triangle of the matrix, without
+/// copying. If the diagonal type is `Diag::Unit`, the diagonal is excluded.
+#[derive(Debug)]
+pub struct Triangular<'a, T, L: Layout> {
+    slice: &'a DSlice<T, 2, L>,
+    triangle: Triangle,
+    diag: Diag,
+}
+
+/// Mutable triangular view of a 2-dimensional array slice.
+///
+/// The view gives access to the elements in one triangle of the matrix, without
+/// copying. If the diagonal type is `Diag::Unit`, the diagonal is excluded.
+#[derive(Debug)]
+pub struct TriangularMut<'a, T, L: Layout> {
+    slice: &'a mut DSlice<T, 2, L>,
+    triangle: Triangle,
+    diag: Diag,
+}
+
+impl<'a, T, L: Layout> Triangular<'a, T, L> {
+    /// Returns the diagonal type.
+    pub fn diag(&self) -> Diag {
+        self.diag
+    }
+
+    /// Returns an iterator over the elements in the triangle, in row-major order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mdarray::linalg::{Diag, Triangle, Triangular};
+    /// use mdarray::tensor;
+    ///
+    /// let a = tensor![[1, 2, 3], [4, 5, 6], [7, 8, 9]];
+    /// let t = Triangular::new(&a, Triangle::Upper, Diag::NonUnit);
+    ///
+    /// assert!(t.iter().eq(&[1, 2, 3, 5, 6, 9]));
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + use<'a, T, L> {
+        let slice = self.slice;
+        let (triangle, diag) = (self.triangle, self.diag);
+
+        (0..slice.dim(0))
+            .flat_map(move |i| row_range(i, slice.dim(1), triangle, diag).map(move |j| (i, j)))
+            .map(move |(i, j)| &slice[[i, j]])
+    }
+
+    /// Creates a triangular view of the array slice.
+    pub fn new(slice: &'a DSlice<T, 2, L>, triangle: Triangle, diag: Diag) -> Self {
+        Self { slice, triangle, diag }
+    }
+
+    /// Copies the triangle into a new matrix, where the other elements are set to zero
+    /// and the diagonal is set to one for `Diag::Unit`.
+    pub fn to_tensor(&self) -> Tensor<T, (Dyn, Dyn)>
+    where
+        T: Element,
+    {
+        let (m, n) = *self.slice.shape();
+
+        Tensor::from_fn((m, n), |i| {
+            if row_range(i[0], n, self.triangle, self.diag).contains(&i[1]) {
+                self.slice[[i[0], i[1]]]
+            } else if i[0] == i[1] && self.diag == Diag::Unit {
+                T::ONE
+            } else {
+                T::ZERO
+            }
+        })
+    }
+
+    /// Returns the triangle selection.
+    pub fn triangle(&self) -> Triangle {
+        self.triangle
+    }
+}
+
+impl<T, L: Layout> Clone for Triangular<'_, T, L> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, L: Layout> Copy for Triangular<'_, T, L> {}
+
+impl<'a, T, L: Layout> TriangularMut<'a, T, L> {
+    /// Assigns the elements in the triangle from the same triangle of another matrix.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the shapes are not matching.
+    pub fn assign<M: Layout>(&mut self, other: &DSlice<T, 2, M>)
+    where
+        T: Clone,
+    {
+        assert!(self.slice.shape() == other.shape(), "shape mismatch");
+
+        self.for_each_index(|slice, i, j| slice[[i, j]].clone_from(&other[[i, j]]));
+    }
+
+    /// Returns the diagonal type.
+    pub fn diag(&self) -> Diag {
+        self.diag
+    }
+
+    /// Fills the elements in the triangle by cloning `value`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mdarray::linalg::{Diag, Triangle, TriangularMut};
+    /// use mdarray::tensor;
+    ///
+    /// let mut a = tensor![[1, 2, 3], [4, 5, 6]];
+    ///
+    /// TriangularMut::new(&mut a, Triangle::Lower, Diag::Unit).fill(0);
+    ///
+    /// assert_eq!(a, tensor![[1, 2, 3], [0, 5, 6]]);
+    /// ```
+    pub fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        self.for_each_index(|slice, i, j| slice[[i, j]].clone_from(&value));
+    }
+
+    /// Returns an iterator over the elements in the triangle, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.view().iter()
+    }
+
+    /// Creates a mutable triangular view of the array slice.
+    pub fn new(slice: &'a mut DSlice<T, 2, L>, triangle: Triangle, diag: Diag) -> Self {
+        Self { slice, triangle, diag }
+    }
+
+    /// Returns the triangle selection.
+    pub fn triangle(&self) -> Triangle {
+        self.triangle
+    }
+
+    /// Returns a triangular view with the same triangle selection.
+    pub fn view(&self) -> Triangular<'_, T, L> {
+        Triangular::new(self.slice, self.triangle, self.diag)
+    }
+
+    fn for_each_index<F: FnMut(&mut DSlice<T, 2, L>, usize, usize)>(&mut self, mut f: F) {
+        let (m, n) = *self.slice.shape();
+
+        for i in 0..m {
+            for j in row_range(i, n, self.triangle, self.diag) {
+                f(self.slice, i, j);
+            }
+        }
+    }
+}
+
+/// Solves the triangular system `op(A) * X = B` in place, where `op(A)` is `A` or its
+/// transpose.
+///
+/// Only the given triangle of `A` is accessed, and the diagonal is assumed to be one
+/// for `Diag::Unit`. The right-hand side `B` is either a vector or a matrix with one
+/// column for each right-hand side, and it is overwritten by the solution.
+///
+/// # Errors
+///
+/// Returns an error if a diagonal element is zero or not finite for `Diag::NonUnit`.
+/// The right-hand side is then partially overwritten.
+///
+/// # Panics
+///
+/// Panics if the matrix `A` is not square, if the rank of `B` is not 1 or 2, or if the
+/// number of rows in `B` is not matching the size of `A`.
+///
+/// # Examples
+///
+/// ```
+/// use mdarray::linalg::{self, Diag, Triangle};
+/// use mdarray::tensor;
+///
+/// let a = tensor![[2.0, 0.0], [1.0, 4.0]];
+/// let mut b = tensor![2.0, 9.0];
+///
+/// linalg::solve_triangular(&a, &mut b, Triangle::Lower, Diag::NonUnit, false).unwrap();
+///
+/// assert_eq!(b, tensor![1.0, 2.0]);
+/// ```
+pub fn solve_triangular<T: Scalar, S: Shape, L: Layout, M: Layout>(
+    a: &DSlice<T, 2, L>,
+    b: &mut Slice<T, S, M>,
+    triangle: Triangle,
+    diag: Diag,
+    transpose: bool,
+) -> Result<(), LinalgError> {
+    assert!(a.dim(0) == a.dim(1), "matrix not square");
+    assert!(b.rank() == 1 || b.rank() == 2, "invalid rank");
+    assert!(b.dim(0) == a.dim(0), "shape mismatch");
+
+    let n = a.dim(0);
+    let nrhs = if b.rank() == 1 { 1 } else { b.dim(1) };
+
+    let mut b = b.reshape_mut((n, nrhs));
+
+    // Get elements in op(A), which is lower triangular if the flags are not equal.
+    let op = |i: usize, j: usize| if transpose { a[[j, i]] } else { a[[i, j]] };
+    let lower = (triangle == Triangle::Lower) != transpose;
+
+    for step in 0..n {
+        let i = if lower { step } else { n - 1 - step };
+        let cols = if lower { 0..i } else { i + 1..n };
+
+        for j in cols {
+            let x = op(i, j);
+
+            if x != T::ZERO {
+                for k in 0..nrhs {
+                    let value = b[[j, k]];
+
+                    b[[i, k]] -= x * value;
+                }
+            }
+        }
+
+        if diag == Diag::NonUnit {
+            let x = a[[i, i]];
+
+            if x == T::ZERO || !x.is_finite() {
+                return Err(LinalgError::Singular(i));
+            }
+
+            for k in 0..nrhs {
+                b[[i, k]] /= x;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Makes a square matrix symmetric by copying the given triangle to the other one.
+///
+/// # Panics
+///
+/// Panics if the matrix is not square.
+///
+/// # Examples
+///
+/// ```
+/// use mdarray::linalg::{self, Triangle};
+/// use mdarray::tensor;
+///
+/// let mut a = tensor![[1, 2], [3, 4]];
+///
+/// linalg::symmetrize(&mut a, Triangle::Upper);
+///
+/// assert_eq!(a, tensor![[1, 2], [2, 4]]);
+/// ```
+pub fn symmetrize<T: Clone, L: Layout>(a: &mut DSlice<T, 2, L>, triangle: Triangle) {
+    assert!(a.dim(0) == a.dim(1), "matrix not square");
+
+    for i in 0..a.dim(0) {
+        for j in 0..i {
+            match triangle {
+                Triangle::Lower => a[[j, i]] = a[[i, j]].clone(),
+                Triangle::Upper => a[[i, j]] = a[[j, i]].clone(),
+            }
+        }
+    }
+}
+
+fn row_range(i: usize, n: usize, triangle: Triangle, diag: Diag) -> Range<usize> {
+    let offset = if diag == Diag::Unit { 1 } else { 0 };
+
+    match triangle {
+        Triangle::Lower => 0..(i + 1 - offset).min(n),
+        Triangle::Upper => (i + offset).min(n)..n,
+    }
+}
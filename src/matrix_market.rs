@@ -0,0 +1,476 @@
+//! Matrix Market module, for reading and writing matrices in the `.mtx` format.
+//!
+//! The `array` format stores all elements in column-major order, and the `coordinate`
+//! format stores the row and column indices and the value for each nonzero element.
+//! Both formats are supported with the `real`, `integer` and `pattern` fields, where
+//! `pattern` is only for the `coordinate` format, and with the `general`, `symmetric`
+//! and `skew-symmetric` symmetries. The `complex` field and the `hermitian` symmetry
+//! are not supported.
+//!
+//! For symmetric and skew-symmetric matrices, only the lower triangle is stored and
+//! the upper triangle is filled in when reading. Duplicate entries in the coordinate
+//! format are added together.
+//!
+//! # Examples
+//!
+//! ```
+//! use mdarray::matrix_market::{self, Format, Symmetry};
+//! use mdarray::tensor;
+//!
+//! let a = tensor![[1.0, 2.0], [2.0, 0.0]];
+//! let mut buffer = Vec::new();
+//!
+//! matrix_market::write(&a, &mut buffer, Format::Coordinate, Symmetry::Symmetric).unwrap();
+//!
+//! let text = "%%MatrixMarket matrix coordinate real symmetric\n2 2 2\n1 1 1\n2 1 2\n";
+//!
+//! assert_eq!(String::from_utf8(buffer).unwrap(), text);
+//! assert_eq!(matrix_market::read::<f64, _>(text.as_bytes()).unwrap(), a);
+//! ```
+
+use std::collections::TryReserveError;
+use std::fmt::Display;
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::mem;
+use std::ops::{Add, Neg};
+use std::str::{FromStr, SplitWhitespace};
+use std::string::String;
+use std::vec::Vec;
+
+use thiserror::Error;
+
+use crate::dim::Dyn;
+use crate::layout::Layout;
+use crate::slice::DSlice;
+use crate::tensor::Tensor;
+
+/// Matrix Market storage format.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Format {
+    /// Dense format with all elements in column-major order.
+    Array,
+    /// Sparse format with the indices and the value of each nonzero element.
+    Coordinate,
+}
+
+/// Matrix Market field type.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Field {
+    /// Floating-point values.
+    Real,
+    /// Integer values.
+    Integer,
+    /// No values, where each stored element is one.
+    Pattern,
+}
+
+/// Matrix Market symmetry type.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Symmetry {
+    /// No symmetry, where all elements are stored.
+    General,
+    /// Symmetric matrix, where the lower triangle is stored.
+    Symmetric,
+    /// Skew-symmetric matrix, where the lower triangle without the diagonal is stored.
+    SkewSymmetric,
+}
+
+/// Error type for reading and writing the Matrix Market format.
+#[derive(Debug, Error)]
+pub enum MatrixMarketError {
+    /// Reading from the input or writing to the output failed.
+    #[error("I/O error")]
+    Io(#[from] io::Error),
+    /// The header or the size line is missing, not valid or not supported.
+    #[error("invalid header")]
+    InvalidHeader,
+    /// Allocating the matrix with the size in the header failed.
+    #[error("allocation failed")]
+    Allocation(#[from] TryReserveError),
+    /// A value or an index could not be parsed, at the given line counted from 1.
+    #[error("parse error at line {line}")]
+    Parse {
+        /// Line number.
+        line: usize,
+    },
+    /// An entry is out of bounds, not permitted for the symmetry or in excess.
+    #[error("invalid entry at line {line}")]
+    InvalidEntry {
+        /// Line number.
+        line: usize,
+    },
+    /// The input has fewer entries than given in the header.
+    #[error("missing entries")]
+    MissingEntries,
+    /// The matrix is not square and matching the symmetry when writing.
+    #[error("symmetry mismatch")]
+    SymmetryMismatch,
+}
+
+/// Trait for array element types that can be read and written in the Matrix Market format.
+pub trait Element:
+    Copy + Default + Display + FromStr + PartialEq + Add<Output = Self> + Neg<Output = Self>
+{
+    /// Field type used when writing.
+    const FIELD: Field;
+
+    /// Value for stored elements in the `pattern` field.
+    const ONE: Self;
+}
+
+struct Lines<R: BufRead> {
+    reader: R,
+    buf: String,
+    line: usize,
+}
+
+macro_rules! impl_element {
+    ($field:tt, $one:tt, $($type:ty),+) => {
+        $(
+            impl Element for $type {
+                const FIELD: Field = Field::$field;
+                const ONE: Self = $one;
+            }
+        )+
+    };
+}
+
+impl_element!(Real, 1.0, f32, f64);
+impl_element!(Integer, 1, i8, i16, i32, i64, i128, isize);
+
+impl Format {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Array => "array",
+            Self::Coordinate => "coordinate",
+        }
+    }
+}
+
+impl Field {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Real => "real",
+            Self::Integer => "integer",
+            Self::Pattern => "pattern",
+        }
+    }
+}
+
+impl Symmetry {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::General => "general",
+            Self::Symmetric => "symmetric",
+            Self::SkewSymmetric => "skew-symmetric",
+        }
+    }
+
+    // Returns the first stored row index in the column.
+    fn start(self, col: usize) -> usize {
+        match self {
+            Self::General => 0,
+            Self::Symmetric => col,
+            Self::SkewSymmetric => col + 1,
+        }
+    }
+}
+
+impl<R: BufRead> Lines<R> {
+    // Returns the next line number and tokens, skipping empty lines and comments.
+    fn next(&mut self) -> io::Result<Option<(usize, SplitWhitespace<'_>)>> {
+        loop {
+            self.buf.clear();
+
+            if self.reader.read_line(&mut self.buf)? == 0 {
+                return Ok(None);
+            }
+
+            self.line += 1;
+
+            let text = self.buf.trim();
+
+            if !text.is_empty() && !text.starts_with('%') {
+                return Ok(Some((self.line, self.buf.split_whitespace())));
+            }
+        }
+    }
+}
+
+/// Reads a matrix in the Matrix Market format into a 2-dimensional array.
+///
+/// # Errors
+///
+/// Returns an error if reading from the input fails, if the header is not valid
+/// or not supported, if the matrix cannot be allocated, or if an entry cannot be
+/// parsed or is not valid.
+pub fn read<T: Element, R: Read>(reader: R) -> Result<Tensor<T, (Dyn, Dyn)>, MatrixMarketError> {
+    let mut lines = Lines { reader: BufReader::new(reader), buf: String::new(), line: 0 };
+
+    if lines.reader.read_line(&mut lines.buf)? == 0 {
+        return Err(MatrixMarketError::InvalidHeader);
+    }
+
+    lines.line = 1;
+
+    let (format, field, symmetry) = parse_header(&lines.buf)?;
+
+    let (_, tokens) = lines.next()?.ok_or(MatrixMarketError::InvalidHeader)?;
+    let sizes = tokens.map(|x| x.parse::<usize>().ok()).collect::<Vec<_>>();
+
+    let (rows, cols, nnz) = match (format, &sizes[..]) {
+        (Format::Array, &[Some(rows), Some(cols)]) => (rows, cols, 0),
+        (Format::Coordinate, &[Some(rows), Some(cols), Some(nnz)]) => (rows, cols, nnz),
+        _ => return Err(MatrixMarketError::InvalidHeader),
+    };
+
+    if symmetry != Symmetry::General && rows != cols {
+        return Err(MatrixMarketError::InvalidHeader);
+    }
+
+    // Check the size in bytes, so that the allocation does not overflow.
+    let size = rows.checked_mul(cols).and_then(|x| x.checked_mul(mem::size_of::<T>()));
+
+    if size.is_none_or(|x| x > isize::MAX as usize) {
+        return Err(MatrixMarketError::InvalidHeader);
+    }
+
+    // Allocate with a fallible reservation, since the header is given by the input.
+    let mut vec = Vec::new();
+
+    vec.try_reserve_exact(rows * cols)?;
+    vec.resize(rows * cols, T::default());
+
+    let mut tensor = Tensor::from(vec).into_shape((rows, cols));
+
+    match format {
+        Format::Array => read_array(&mut lines, &mut tensor, symmetry)?,
+        Format::Coordinate => read_coordinate(&mut lines, &mut tensor, field, symmetry, nnz)?,
+    }
+
+    Ok(tensor)
+}
+
+/// Writes a 2-dimensional array in the Matrix Market format.
+///
+/// With the `coordinate` format, elements that are equal to the default value are
+/// not stored. With the `symmetric` or `skew-symmetric` symmetry, only the lower
+/// triangle is stored.
+///
+/// # Errors
+///
+/// Returns an error if writing to the output fails, or if the array is not square
+/// and matching the symmetry.
+pub fn write<T: Element, L: Layout, W: Write>(
+    slice: &DSlice<T, 2, L>,
+    writer: W,
+    format: Format,
+    symmetry: Symmetry,
+) -> Result<(), MatrixMarketError> {
+    let (rows, cols) = *slice.shape();
+
+    if symmetry != Symmetry::General {
+        if rows != cols {
+            return Err(MatrixMarketError::SymmetryMismatch);
+        }
+
+        for j in 0..cols {
+            for i in j..rows {
+                let (x, y) = (slice[[i, j]], slice[[j, i]]);
+
+                let valid = match symmetry {
+                    Symmetry::General => true,
+                    Symmetry::Symmetric => x == y,
+                    Symmetry::SkewSymmetric => x == -y && (i != j || x == T::default()),
+                };
+
+                if !valid {
+                    return Err(MatrixMarketError::SymmetryMismatch);
+                }
+            }
+        }
+    }
+
+    let mut writer = BufWriter::new(writer);
+
+    let (format_str, field_str) = (format.as_str(), T::FIELD.as_str());
+    let symmetry_str = symmetry.as_str();
+
+    writeln!(writer, "%%MatrixMarket matrix {} {} {}", format_str, field_str, symmetry_str)?;
+
+    match format {
+        Format::Array => {
+            writeln!(writer, "{} {}", rows, cols)?;
+
+            for j in 0..cols {
+                for i in symmetry.start(j)..rows {
+                    writeln!(writer, "{}", slice[[i, j]])?;
+                }
+            }
+        }
+        Format::Coordinate => {
+            let mut nnz = 0;
+
+            for j in 0..cols {
+                nnz += (symmetry.start(j)..rows).filter(|&i| slice[[i, j]] != T::default()).count();
+            }
+
+            writeln!(writer, "{} {} {}", rows, cols, nnz)?;
+
+            for j in 0..cols {
+                for i in symmetry.start(j)..rows {
+                    if slice[[i, j]] != T::default() {
+                        writeln!(writer, "{} {} {}", i + 1, j + 1, slice[[i, j]])?;
+                    }
+                }
+            }
+        }
+    }
+
+    writer.flush()?;
+
+    Ok(())
+}
+
+fn parse_header(text: &str) -> Result<(Format, Field, Symmetry), MatrixMarketError> {
+    let tokens = text.split_whitespace().map(|x| x.to_ascii_lowercase()).collect::<Vec<_>>();
+
+    let [banner, object, format, field, symmetry] = &tokens[..] else {
+        return Err(MatrixMarketError::InvalidHeader);
+    };
+
+    if banner != "%%matrixmarket" || object != "matrix" {
+        return Err(MatrixMarketError::InvalidHeader);
+    }
+
+    let format = match format.as_str() {
+        "array" => Format::Array,
+        "coordinate" => Format::Coordinate,
+        _ => return Err(MatrixMarketError::InvalidHeader),
+    };
+
+    let field = match field.as_str() {
+        "real" | "double" => Field::Real,
+        "integer" => Field::Integer,
+        "pattern" if format == Format::Coordinate => Field::Pattern,
+        _ => return Err(MatrixMarketError::InvalidHeader),
+    };
+
+    let symmetry = match symmetry.as_str() {
+        "general" => Symmetry::General,
+        "symmetric" => Symmetry::Symmetric,
+        "skew-symmetric" => Symmetry::SkewSymmetric,
+        _ => return Err(MatrixMarketError::InvalidHeader),
+    };
+
+    Ok((format, field, symmetry))
+}
+
+fn read_array<T: Element, R: BufRead>(
+    lines: &mut Lines<R>,
+    tensor: &mut Tensor<T, (Dyn, Dyn)>,
+    symmetry: Symmetry,
+) -> Result<(), MatrixMarketError> {
+    let (rows, cols) = *tensor.shape();
+    let (mut i, mut j) = (symmetry.start(0), 0);
+
+    while let Some((line, tokens)) = lines.next()? {
+        for token in tokens {
+            while i >= rows && j < cols {
+                j += 1;
+                i = symmetry.start(j);
+            }
+
+            if j == cols {
+                return Err(MatrixMarketError::InvalidEntry { line });
+            }
+
+            let value = token.parse().map_err(|_| MatrixMarketError::Parse { line })?;
+
+            set_entry(tensor, i, j, value, symmetry);
+            i += 1;
+        }
+    }
+
+    while i >= rows && j < cols {
+        j += 1;
+        i = symmetry.start(j);
+    }
+
+    if j < cols {
+        return Err(MatrixMarketError::MissingEntries);
+    }
+
+    Ok(())
+}
+
+fn read_coordinate<T: Element, R: BufRead>(
+    lines: &mut Lines<R>,
+    tensor: &mut Tensor<T, (Dyn, Dyn)>,
+    field: Field,
+    symmetry: Symmetry,
+    nnz: usize,
+) -> Result<(), MatrixMarketError> {
+    let (rows, cols) = *tensor.shape();
+
+    for _ in 0..nnz {
+        let (line, mut tokens) = lines.next()?.ok_or(MatrixMarketError::MissingEntries)?;
+
+        let mut index = || -> Result<usize, MatrixMarketError> {
+            let index = tokens.next().and_then(|x| x.parse::<usize>().ok());
+
+            index.ok_or(MatrixMarketError::Parse { line })
+        };
+
+        let (i, j) = (index()?, index()?);
+
+        let value = match field {
+            Field::Pattern => T::ONE,
+            _ => tokens
+                .next()
+                .and_then(|x| x.parse().ok())
+                .ok_or(MatrixMarketError::Parse { line })?,
+        };
+
+        if tokens.next().is_some() {
+            return Err(MatrixMarketError::Parse { line });
+        }
+
+        if i == 0
+            || i > rows
+            || j == 0
+            || j > cols
+            || (symmetry == Symmetry::SkewSymmetric && i == j)
+        {
+            return Err(MatrixMarketError::InvalidEntry { line });
+        }
+
+        let value = tensor[[i - 1, j - 1]] + value;
+
+        set_entry(tensor, i - 1, j - 1, value, symmetry);
+    }
+
+    if let Some((line, _)) = lines.next()? {
+        return Err(MatrixMarketError::InvalidEntry { line });
+    }
+
+    Ok(())
+}
+
+fn set_entry<T: Element>(
+    tensor: &mut Tensor<T, (Dyn, Dyn)>,
+    i: usize,
+    j: usize,
+    value: T,
+    symmetry: Symmetry,
+) {
+    tensor[[i, j]] = value;
+
+    if i != j {
+        match symmetry {
+            Symmetry::General => (),
+            Symmetry::Symmetric => tensor[[j, i]] = value,
+            Symmetry::SkewSymmetric => tensor[[j, i]] = -value,
+        }
+    }
+}
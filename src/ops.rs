@@ -1,19 +1,20 @@
-#[cfg(feature = "nightly")]
-use std::alloc::Allocator;
-
-use std::ops::{
+use core::ops::{
     Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Div, DivAssign,
     Mul, MulAssign, Neg, Not, Rem, RemAssign, Shl, ShlAssign, Shr, ShrAssign, Sub, SubAssign,
 };
 
-#[cfg(not(feature = "nightly"))]
-use crate::alloc::Allocator;
+#[cfg(feature = "alloc")]
+use crate::allocator::Allocator;
 use crate::array::Array;
 use crate::expr::{Apply, Buffer, Expression, IntoExpression};
 use crate::expr::{Fill, FillWith, FromElem, FromFn, IntoExpr, Map};
 use crate::layout::Layout;
 use crate::shape::{ConstShape, Shape};
 use crate::slice::Slice;
+#[cfg(feature = "alloc")]
+use crate::small_tensor::SmallTensor;
+use crate::stack_tensor::StackTensor;
+#[cfg(feature = "alloc")]
 use crate::tensor::Tensor;
 use crate::view::{View, ViewMut};
 
@@ -48,6 +49,10 @@ pub fn step<R, S>(range: R, step: S) -> StepRange<R, S> {
 
 impl<T: Eq, S: ConstShape> Eq for Array<T, S> {}
 impl<T: Eq, S: Shape, L: Layout> Eq for Slice<T, S, L> {}
+#[cfg(feature = "alloc")]
+impl<T: Eq, const N: usize, S: Shape> Eq for SmallTensor<T, N, S> {}
+impl<T: Eq, const N: usize, S: Shape> Eq for StackTensor<T, N, S> {}
+#[cfg(feature = "alloc")]
 impl<T: Eq, S: Shape, A: Allocator> Eq for Tensor<T, S, A> {}
 impl<T: Eq, S: Shape, L: Layout> Eq for View<'_, T, S, L> {}
 impl<T: Eq, S: Shape, L: Layout> Eq for ViewMut<'_, T, S, L> {}
@@ -110,6 +115,30 @@ where
     }
 }
 
+#[cfg(feature = "alloc")]
+impl<T, U, const N: usize, S: Shape, R: Shape, L: Layout, I: ?Sized> PartialEq<I>
+    for SmallTensor<T, N, S>
+where
+    for<'a> &'a I: IntoExpression<IntoExpr = View<'a, U, R, L>>,
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &I) -> bool {
+        (**self).eq(other)
+    }
+}
+
+impl<T, U, const N: usize, S: Shape, R: Shape, L: Layout, I: ?Sized> PartialEq<I>
+    for StackTensor<T, N, S>
+where
+    for<'a> &'a I: IntoExpression<IntoExpr = View<'a, U, R, L>>,
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &I) -> bool {
+        (**self).eq(other)
+    }
+}
+
+#[cfg(feature = "alloc")]
 impl<T, U, S: Shape, R: Shape, L: Layout, A: Allocator, I: ?Sized> PartialEq<I> for Tensor<T, S, A>
 where
     for<'a> &'a I: IntoExpression<IntoExpr = View<'a, U, R, L>>,
@@ -173,6 +202,38 @@ macro_rules! impl_binary_op {
             }
         }
 
+        #[cfg(feature = "alloc")]
+        impl<'a, T, U, const N: usize, S: Shape, I: Apply<U>> $trt<I> for &'a SmallTensor<T, N, S>
+        where
+            &'a T: $trt<I::Item, Output = U>,
+        {
+            #[cfg(not(feature = "nightly"))]
+            type Output = I::ZippedWith<Self, fn((I::Item, &'a T)) -> U>;
+
+            #[cfg(feature = "nightly")]
+            type Output = I::ZippedWith<Self, impl FnMut((I::Item, &'a T)) -> U>;
+
+            fn $fn(self, rhs: I) -> Self::Output {
+                rhs.zip_with(self, |(x, y)| y.$fn(x))
+            }
+        }
+
+        impl<'a, T, U, const N: usize, S: Shape, I: Apply<U>> $trt<I> for &'a StackTensor<T, N, S>
+        where
+            &'a T: $trt<I::Item, Output = U>,
+        {
+            #[cfg(not(feature = "nightly"))]
+            type Output = I::ZippedWith<Self, fn((I::Item, &'a T)) -> U>;
+
+            #[cfg(feature = "nightly")]
+            type Output = I::ZippedWith<Self, impl FnMut((I::Item, &'a T)) -> U>;
+
+            fn $fn(self, rhs: I) -> Self::Output {
+                rhs.zip_with(self, |(x, y)| y.$fn(x))
+            }
+        }
+
+        #[cfg(feature = "alloc")]
         impl<'a, T, U, S: Shape, A: Allocator, I: Apply<U>> $trt<I> for &'a Tensor<T, S, A>
         where
             &'a T: $trt<I::Item, Output = U>,
@@ -319,6 +380,30 @@ macro_rules! impl_binary_op {
             }
         }
 
+        #[cfg(feature = "alloc")]
+        impl<T, const N: usize, S: Shape, I: IntoExpression> $trt<I> for SmallTensor<T, N, S>
+        where
+            T: $trt<I::Item, Output = T>,
+        {
+            type Output = Self;
+
+            fn $fn(self, rhs: I) -> Self {
+                self.zip_with(rhs, |(x, y)| x.$fn(y))
+            }
+        }
+
+        impl<T, const N: usize, S: Shape, I: IntoExpression> $trt<I> for StackTensor<T, N, S>
+        where
+            T: $trt<I::Item, Output = T>,
+        {
+            type Output = Self;
+
+            fn $fn(self, rhs: I) -> Self {
+                self.zip_with(rhs, |(x, y)| x.$fn(y))
+            }
+        }
+
+        #[cfg(feature = "alloc")]
         impl<T, S: Shape, A: Allocator, I: IntoExpression> $trt<I> for Tensor<T, S, A>
         where
             T: $trt<I::Item, Output = T>,
@@ -378,6 +463,26 @@ macro_rules! impl_op_assign {
             }
         }
 
+        #[cfg(feature = "alloc")]
+        impl<T, const N: usize, S: Shape, I: IntoExpression> $trt<I> for SmallTensor<T, N, S>
+        where
+            T: $trt<I::Item>,
+        {
+            fn $fn(&mut self, rhs: I) {
+                self.expr_mut().zip(rhs).for_each(|(x, y)| x.$fn(y));
+            }
+        }
+
+        impl<T, const N: usize, S: Shape, I: IntoExpression> $trt<I> for StackTensor<T, N, S>
+        where
+            T: $trt<I::Item>,
+        {
+            fn $fn(&mut self, rhs: I) {
+                self.expr_mut().zip(rhs).for_each(|(x, y)| x.$fn(y));
+            }
+        }
+
+        #[cfg(feature = "alloc")]
         impl<T, S: Shape, A: Allocator, I: IntoExpression> $trt<I> for Tensor<T, S, A>
         where
             T: $trt<I::Item>,
@@ -441,6 +546,38 @@ macro_rules! impl_unary_op {
             }
         }
 
+        #[cfg(feature = "alloc")]
+        impl<'a, T, U, const N: usize, S: Shape> $trt for &'a SmallTensor<T, N, S>
+        where
+            &'a T: $trt<Output = U>,
+        {
+            #[cfg(not(feature = "nightly"))]
+            type Output = <Self as Apply<U>>::Output<fn(&'a T) -> U>;
+
+            #[cfg(feature = "nightly")]
+            type Output = <Self as Apply<U>>::Output<impl FnMut(&'a T) -> U>;
+
+            fn $fn(self) -> Self::Output {
+                self.apply(|x| x.$fn())
+            }
+        }
+
+        impl<'a, T, U, const N: usize, S: Shape> $trt for &'a StackTensor<T, N, S>
+        where
+            &'a T: $trt<Output = U>,
+        {
+            #[cfg(not(feature = "nightly"))]
+            type Output = <Self as Apply<U>>::Output<fn(&'a T) -> U>;
+
+            #[cfg(feature = "nightly")]
+            type Output = <Self as Apply<U>>::Output<impl FnMut(&'a T) -> U>;
+
+            fn $fn(self) -> Self::Output {
+                self.apply(|x| x.$fn())
+            }
+        }
+
+        #[cfg(feature = "alloc")]
         impl<'a, T, U, S: Shape, A: Allocator> $trt for &'a Tensor<T, S, A>
         where
             &'a T: $trt<Output = U>,
@@ -587,6 +724,30 @@ macro_rules! impl_unary_op {
             }
         }
 
+        #[cfg(feature = "alloc")]
+        impl<T, const N: usize, S: Shape> $trt for SmallTensor<T, N, S>
+        where
+            T: $trt<Output = T>,
+        {
+            type Output = Self;
+
+            fn $fn(self) -> Self {
+                self.apply(|x| x.$fn())
+            }
+        }
+
+        impl<T, const N: usize, S: Shape> $trt for StackTensor<T, N, S>
+        where
+            T: $trt<Output = T>,
+        {
+            type Output = Self;
+
+            fn $fn(self) -> Self {
+                self.apply(|x| x.$fn())
+            }
+        }
+
+        #[cfg(feature = "alloc")]
         impl<T, S: Shape, A: Allocator> $trt for Tensor<T, S, A>
         where
             T: $trt<Output = T>,
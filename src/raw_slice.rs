@@ -1,5 +1,8 @@
-use std::mem;
-use std::ptr::NonNull;
+use core::cell::UnsafeCell;
+use core::mem;
+use core::ptr::NonNull;
+#[cfg(target_has_atomic = "ptr")]
+use core::sync::atomic::{AtomicPtr, Ordering};
 
 use crate::layout::Layout;
 use crate::shape::Shape;
@@ -62,6 +65,33 @@ impl<T, S: Shape, L: Layout> RawSlice<T, S, L> {
     pub(crate) unsafe fn set_ptr(&mut self, new_ptr: *mut T) {
         self.ptr = unsafe { NonNull::new_unchecked(new_ptr) };
     }
+
+    // Updates the pointer through a shared reference if it is changed. With atomic
+    // pointer operations, only one thread writes the pointer and other threads are
+    // synchronized with the write before reading. Otherwise the caller must ensure
+    // that the slice is not shared between threads.
+    pub(crate) unsafe fn update_ptr(this: &UnsafeCell<Self>, new_ptr: *mut T) {
+        let ptr = unsafe { &raw mut (*this.get()).ptr } as *mut *mut T;
+
+        #[cfg(target_has_atomic = "ptr")]
+        {
+            const { assert!(mem::align_of::<AtomicPtr<T>>() == mem::align_of::<*mut T>()) };
+
+            let atomic = unsafe { AtomicPtr::from_ptr(ptr) };
+            let old_ptr = atomic.load(Ordering::Acquire);
+
+            if old_ptr != new_ptr {
+                _ = atomic.compare_exchange(old_ptr, new_ptr, Ordering::Release, Ordering::Acquire);
+            }
+        }
+
+        #[cfg(not(target_has_atomic = "ptr"))]
+        unsafe {
+            if *ptr != new_ptr {
+                *ptr = new_ptr;
+            }
+        }
+    }
 }
 
 impl<T, S: Shape, L: Layout> Clone for RawSlice<T, S, L> {
@@ -1,25 +1,22 @@
-#[cfg(feature = "nightly")]
-use std::alloc::Allocator;
-use std::marker::PhantomData;
-use std::mem::{self, ManuallyDrop};
-use std::ptr;
-
-#[cfg(not(feature = "nightly"))]
-use crate::alloc::Allocator;
+use core::marker::PhantomData;
+use core::mem::{self, ManuallyDrop};
+use core::ptr;
+
+use crate::allocator::{Allocator, Vec};
 use crate::layout::Dense;
 use crate::mapping::{DenseMapping, Mapping};
 use crate::raw_slice::RawSlice;
 use crate::shape::Shape;
 use crate::slice::Slice;
 
-#[cfg(not(feature = "nightly"))]
+#[cfg(not(any(feature = "nightly", feature = "allocator-api2")))]
 macro_rules! vec_t {
     ($type:ty, $alloc:ty) => {
         Vec<$type>
     };
 }
 
-#[cfg(feature = "nightly")]
+#[cfg(any(feature = "nightly", feature = "allocator-api2"))]
 macro_rules! vec_t {
     ($type:ty, $alloc:ty) => {
         Vec<$type, $alloc>
@@ -29,23 +26,23 @@ macro_rules! vec_t {
 pub(crate) struct RawTensor<T, S: Shape, A: Allocator> {
     slice: RawSlice<T, S, Dense>,
     capacity: usize,
-    #[cfg(not(feature = "nightly"))]
+    #[cfg(not(any(feature = "nightly", feature = "allocator-api2")))]
     phantom: PhantomData<A>,
-    #[cfg(feature = "nightly")]
+    #[cfg(any(feature = "nightly", feature = "allocator-api2"))]
     alloc: ManuallyDrop<A>,
 }
 
 struct DropGuard<'a, T, A: Allocator> {
     ptr: *mut T,
     len: usize,
-    #[cfg(not(feature = "nightly"))]
+    #[cfg(not(any(feature = "nightly", feature = "allocator-api2")))]
     phantom: PhantomData<(&'a mut Vec<T>, &'a A)>,
-    #[cfg(feature = "nightly")]
+    #[cfg(any(feature = "nightly", feature = "allocator-api2"))]
     phantom: PhantomData<&'a mut Vec<T, A>>,
 }
 
 impl<T, S: Shape, A: Allocator> RawTensor<T, S, A> {
-    #[cfg(feature = "nightly")]
+    #[cfg(any(feature = "nightly", feature = "allocator-api2"))]
     pub(crate) fn allocator(&self) -> &A {
         &self.alloc
     }
@@ -62,7 +59,7 @@ impl<T, S: Shape, A: Allocator> RawTensor<T, S, A> {
         if mem::size_of::<T>() > 0 { self.capacity } else { usize::MAX }
     }
 
-    #[cfg(not(feature = "nightly"))]
+    #[cfg(not(any(feature = "nightly", feature = "allocator-api2")))]
     pub(crate) unsafe fn from_parts(vec: Vec<T>, mapping: DenseMapping<S>) -> Self {
         debug_assert!(Some(vec.len()) == mapping.shape().checked_len(), "length mismatch");
 
@@ -75,7 +72,7 @@ impl<T, S: Shape, A: Allocator> RawTensor<T, S, A> {
         }
     }
 
-    #[cfg(feature = "nightly")]
+    #[cfg(any(feature = "nightly", feature = "allocator-api2"))]
     pub(crate) unsafe fn from_parts(vec: Vec<T, A>, mapping: DenseMapping<S>) -> Self {
         debug_assert!(Some(vec.len()) == mapping.shape().checked_len(), "length mismatch");
 
@@ -91,11 +88,11 @@ impl<T, S: Shape, A: Allocator> RawTensor<T, S, A> {
     pub(crate) fn into_parts(self) -> (vec_t!(T, A), DenseMapping<S>) {
         let mut me = ManuallyDrop::new(self);
 
-        #[cfg(not(feature = "nightly"))]
+        #[cfg(not(any(feature = "nightly", feature = "allocator-api2")))]
         let vec = unsafe {
             Vec::from_raw_parts(me.slice.as_mut_ptr(), me.slice.mapping().len(), me.capacity)
         };
-        #[cfg(feature = "nightly")]
+        #[cfg(any(feature = "nightly", feature = "allocator-api2"))]
         let vec = unsafe {
             Vec::from_raw_parts_in(
                 me.slice.as_mut_ptr(),
@@ -126,9 +123,9 @@ impl<T, S: Shape, A: Allocator> RawTensor<T, S, A> {
                         } else if new_dims[1..] == old_dims[1..] {
                             vec.resize_with(new_len, &mut f);
                         } else {
-                            #[cfg(not(feature = "nightly"))]
+                            #[cfg(not(any(feature = "nightly", feature = "allocator-api2")))]
                             let mut new_vec = Vec::with_capacity(new_len);
-                            #[cfg(feature = "nightly")]
+                            #[cfg(any(feature = "nightly", feature = "allocator-api2"))]
                             let mut new_vec =
                                 Vec::with_capacity_in(new_len, vec.allocator().clone());
 
@@ -159,7 +156,7 @@ impl<T, S: Shape, A: Allocator> RawTensor<T, S, A> {
         }
     }
 
-    #[cfg(not(feature = "nightly"))]
+    #[cfg(not(any(feature = "nightly", feature = "allocator-api2")))]
     pub(crate) unsafe fn with_mut_parts<U, F>(&mut self, f: F) -> U
     where
         F: FnOnce(&mut Vec<T>, &mut DenseMapping<S>) -> U,
@@ -207,7 +204,7 @@ impl<T, S: Shape, A: Allocator> RawTensor<T, S, A> {
         result
     }
 
-    #[cfg(feature = "nightly")]
+    #[cfg(any(feature = "nightly", feature = "allocator-api2"))]
     pub(crate) unsafe fn with_mut_parts<U, F>(&mut self, f: F) -> U
     where
         F: FnOnce(&mut Vec<T, A>, &mut DenseMapping<S>) -> U,
@@ -261,7 +258,7 @@ impl<T, S: Shape, A: Allocator> RawTensor<T, S, A> {
     }
 
     pub(crate) fn with_vec<U, F: FnOnce(&vec_t!(T, A)) -> U>(&self, f: F) -> U {
-        #[cfg(not(feature = "nightly"))]
+        #[cfg(not(any(feature = "nightly", feature = "allocator-api2")))]
         let vec = unsafe {
             Vec::from_raw_parts(
                 self.slice.as_ptr() as *mut T,
@@ -269,7 +266,7 @@ impl<T, S: Shape, A: Allocator> RawTensor<T, S, A> {
                 self.capacity,
             )
         };
-        #[cfg(feature = "nightly")]
+        #[cfg(any(feature = "nightly", feature = "allocator-api2"))]
         let vec = unsafe {
             Vec::from_raw_parts_in(
                 self.slice.as_ptr() as *mut T,
@@ -299,14 +296,14 @@ impl<T: Clone, S: Shape, A: Allocator + Clone> Clone for RawTensor<T, S, A> {
 }
 
 impl<T, S: Shape, A: Allocator> Drop for RawTensor<T, S, A> {
-    #[cfg(not(feature = "nightly"))]
+    #[cfg(not(any(feature = "nightly", feature = "allocator-api2")))]
     fn drop(&mut self) {
         _ = unsafe {
             Vec::from_raw_parts(self.slice.as_mut_ptr(), self.slice.mapping().len(), self.capacity)
         };
     }
 
-    #[cfg(feature = "nightly")]
+    #[cfg(any(feature = "nightly", feature = "allocator-api2"))]
     fn drop(&mut self) {
         _ = unsafe {
             Vec::from_raw_parts_in(
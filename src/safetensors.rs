@@ -0,0 +1,767 @@
+//! Safetensors module, for reading and writing arrays in the safetensors format.
+//!
+//! A safetensors buffer starts with the header size as a little-endian `u64`, followed
+//! by a JSON header and the element data. The header maps each array name to the data
+//! type, the shape and the data offsets, and can contain string metadata. The element
+//! data is in row-major order and little-endian byte order.
+//!
+//! Arrays are read with `SafeTensors`, either copied into a `Tensor` or borrowed as
+//! a `View` into the buffer. Arrays are written with `Writer`, which orders the arrays
+//! so that the element data is aligned if the buffer is aligned to 8 bytes.
+//!
+//! # Examples
+//!
+//! ```
+//! use mdarray::safetensors::{SafeTensors, Writer};
+//! use mdarray::tensor;
+//!
+//! let a = tensor![[1.0f32, 2.0], [3.0, 4.0]];
+//! let b = tensor![1i64, 2, 3];
+//!
+//! let mut writer = Writer::new();
+//!
+//! writer.add("a", &a).unwrap();
+//! writer.add("b", &b).unwrap();
+//!
+//! let buffer = writer.finish();
+//! let st = SafeTensors::parse(&buffer).unwrap();
+//!
+//! assert_eq!(st.tensor::<f32>("a").unwrap(), a);
+//! assert_eq!(st.tensor::<i64>("b").unwrap(), b);
+//! ```
+
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::mem::{self, MaybeUninit};
+use core::ptr::NonNull;
+use core::slice;
+use core::str;
+
+use thiserror::Error;
+
+use crate::allocator;
+use crate::layout::Layout;
+use crate::mapping::DenseMapping;
+use crate::shape::{DynRank, Shape};
+use crate::slice::Slice;
+use crate::tensor::Tensor;
+use crate::view::View;
+
+// Upper limit for the header size, to protect against invalid input.
+const MAX_HEADER_SIZE: usize = 100_000_000;
+
+const METADATA_KEY: &str = "__metadata__";
+
+/// Safetensors data type.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Dtype {
+    /// Boolean type stored as a byte with the value 0 or 1.
+    Bool,
+    /// 8-bit floating point type with 5 exponent bits.
+    F8E5M2,
+    /// 8-bit floating point type with 4 exponent bits.
+    F8E4M3,
+    /// Signed 8-bit integer type.
+    I8,
+    /// Unsigned 8-bit integer type.
+    U8,
+    /// Signed 16-bit integer type.
+    I16,
+    /// Unsigned 16-bit integer type.
+    U16,
+    /// 16-bit floating point type.
+    F16,
+    /// 16-bit brain floating point type.
+    BF16,
+    /// Signed 32-bit integer type.
+    I32,
+    /// Unsigned 32-bit integer type.
+    U32,
+    /// 32-bit floating point type.
+    F32,
+    /// Signed 64-bit integer type.
+    I64,
+    /// Unsigned 64-bit integer type.
+    U64,
+    /// 64-bit floating point type.
+    F64,
+}
+
+/// Error type for reading and writing the safetensors format.
+#[derive(Debug, Error)]
+pub enum SafeTensorsError {
+    /// The header is missing, too large or not valid.
+    #[error("invalid header")]
+    InvalidHeader,
+    /// The data offsets are not matching the shape, or the element data has gaps.
+    #[error("invalid data offsets")]
+    InvalidOffsets(String),
+    /// The element data is not valid for the element type.
+    #[error("invalid data")]
+    InvalidData,
+    /// The data type is not matching the element type.
+    #[error("data type mismatch")]
+    DataTypeMismatch(Dtype),
+    /// The element data is not aligned for an array view.
+    #[error("invalid alignment")]
+    InvalidAlignment,
+    /// The native byte order is not little-endian for an array view.
+    #[error("unsupported byte order")]
+    UnsupportedByteOrder,
+    /// The array name is not found.
+    #[error("array not found")]
+    NotFound(String),
+    /// The array name is already used or reserved.
+    #[error("invalid name")]
+    InvalidName(String),
+}
+
+/// Array information in the safetensors header.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct TensorInfo {
+    /// Data type of the elements.
+    pub dtype: Dtype,
+    /// Array shape.
+    pub shape: DynRank,
+    /// Start and end offsets of the element data in bytes.
+    pub data_offsets: (usize, usize),
+}
+
+/// Safetensors buffer with the parsed header.
+#[derive(Clone, Debug)]
+pub struct SafeTensors<'a> {
+    data: &'a [u8],
+    tensors: BTreeMap<String, TensorInfo>,
+    metadata: BTreeMap<String, String>,
+}
+
+/// Writer for the safetensors format.
+#[derive(Clone, Debug, Default)]
+pub struct Writer {
+    tensors: BTreeMap<String, (Dtype, DynRank, Vec<u8>)>,
+    metadata: BTreeMap<String, String>,
+}
+
+/// Trait for array element types that have a safetensors data type.
+///
+/// # Safety
+///
+/// The data type must match the size and representation of the element type.
+pub unsafe trait Element: Copy {
+    /// Safetensors data type for the element type.
+    const DTYPE: Dtype;
+}
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+macro_rules! impl_element {
+    ($($type:ty: $dtype:tt),+) => {
+        $(
+            unsafe impl Element for $type {
+                const DTYPE: Dtype = Dtype::$dtype;
+            }
+        )+
+    };
+}
+
+impl_element!(bool: Bool, i8: I8, u8: U8, i16: I16, u16: U16, i32: I32, u32: U32);
+impl_element!(f32: F32, i64: I64, u64: U64, f64: F64);
+
+impl Dtype {
+    /// Returns the data type name in the safetensors header.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Bool => "BOOL",
+            Self::F8E5M2 => "F8_E5M2",
+            Self::F8E4M3 => "F8_E4M3",
+            Self::I8 => "I8",
+            Self::U8 => "U8",
+            Self::I16 => "I16",
+            Self::U16 => "U16",
+            Self::F16 => "F16",
+            Self::BF16 => "BF16",
+            Self::I32 => "I32",
+            Self::U32 => "U32",
+            Self::F32 => "F32",
+            Self::I64 => "I64",
+            Self::U64 => "U64",
+            Self::F64 => "F64",
+        }
+    }
+
+    /// Returns the size of an element in bytes.
+    pub fn size(self) -> usize {
+        match self {
+            Self::Bool | Self::F8E5M2 | Self::F8E4M3 | Self::I8 | Self::U8 => 1,
+            Self::I16 | Self::U16 | Self::F16 | Self::BF16 => 2,
+            Self::I32 | Self::U32 | Self::F32 => 4,
+            Self::I64 | Self::U64 | Self::F64 => 8,
+        }
+    }
+
+    fn from_str(s: &str) -> Option<Self> {
+        let dtype = match s {
+            "BOOL" => Self::Bool,
+            "F8_E5M2" => Self::F8E5M2,
+            "F8_E4M3" => Self::F8E4M3,
+            "I8" => Self::I8,
+            "U8" => Self::U8,
+            "I16" => Self::I16,
+            "U16" => Self::U16,
+            "F16" => Self::F16,
+            "BF16" => Self::BF16,
+            "I32" => Self::I32,
+            "U32" => Self::U32,
+            "F32" => Self::F32,
+            "I64" => Self::I64,
+            "U64" => Self::U64,
+            "F64" => Self::F64,
+            _ => return None,
+        };
+
+        Some(dtype)
+    }
+}
+
+impl<'a> SafeTensors<'a> {
+    /// Returns the element data in bytes for the array with the given name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the array name is not found.
+    pub fn bytes(&self, name: &str) -> Result<&'a [u8], SafeTensorsError> {
+        let info = self.info(name).ok_or_else(|| SafeTensorsError::NotFound(name.to_string()))?;
+
+        Ok(&self.data[info.data_offsets.0..info.data_offsets.1])
+    }
+
+    /// Returns the array information for the array with the given name.
+    pub fn info(&self, name: &str) -> Option<&TensorInfo> {
+        self.tensors.get(name)
+    }
+
+    /// Returns `true` if the buffer contains no arrays.
+    pub fn is_empty(&self) -> bool {
+        self.tensors.is_empty()
+    }
+
+    /// Returns the number of arrays in the buffer.
+    pub fn len(&self) -> usize {
+        self.tensors.len()
+    }
+
+    /// Returns an iterator over the metadata key-value pairs, in order of the keys.
+    pub fn metadata(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.metadata.iter().map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    /// Returns an iterator over the array names, in sorted order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.tensors.keys().map(|name| name.as_str())
+    }
+
+    /// Parses the header of a safetensors buffer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the header is not valid, or if the data offsets are not
+    /// matching the shapes and the size of the element data.
+    pub fn parse(buffer: &'a [u8]) -> Result<Self, SafeTensorsError> {
+        let (size, rest) =
+            buffer.split_first_chunk::<8>().ok_or(SafeTensorsError::InvalidHeader)?;
+        let size = usize::try_from(u64::from_le_bytes(*size)).unwrap_or(usize::MAX);
+
+        if size > MAX_HEADER_SIZE || size > rest.len() {
+            return Err(SafeTensorsError::InvalidHeader);
+        }
+
+        let (header, data) = rest.split_at(size);
+        let header = str::from_utf8(header).map_err(|_| SafeTensorsError::InvalidHeader)?;
+
+        let mut parser = Parser { bytes: header.as_bytes(), pos: 0 };
+        let (tensors, metadata) = parser.header()?;
+
+        let mut offsets =
+            tensors.iter().map(|(name, info)| (info.data_offsets, name)).collect::<Vec<_>>();
+        let mut end = 0;
+
+        offsets.sort();
+
+        for ((first, last), name) in offsets {
+            let size = tensors[name]
+                .shape
+                .checked_len()
+                .and_then(|x| x.checked_mul(tensors[name].dtype.size()));
+
+            if first != end || last < first || size != Some(last - first) {
+                return Err(SafeTensorsError::InvalidOffsets(name.clone()));
+            }
+
+            end = last;
+        }
+
+        if end != data.len() {
+            return Err(SafeTensorsError::InvalidOffsets(String::new()));
+        }
+
+        Ok(Self { data, tensors, metadata })
+    }
+
+    /// Copies the array with the given name into a new array.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the array name is not found, if the data type is not
+    /// matching the element type, or if the element data is not valid.
+    pub fn tensor<T: Element>(&self, name: &str) -> Result<Tensor<T>, SafeTensorsError> {
+        let (info, bytes) = self.checked_bytes::<T>(name)?;
+        let vec = bytes
+            .chunks_exact(mem::size_of::<T>())
+            .map(read_element)
+            .collect::<allocator::Vec<T>>();
+
+        Ok(Tensor::from(vec).into_shape(info.shape.clone()))
+    }
+
+    /// Returns an array view of the array with the given name, borrowing the buffer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the array name is not found, if the data type is not
+    /// matching the element type, if the element data is not valid or aligned,
+    /// or if the native byte order is not little-endian.
+    pub fn view<T: Element>(&self, name: &str) -> Result<View<'a, T>, SafeTensorsError> {
+        let (info, bytes) = self.checked_bytes::<T>(name)?;
+
+        if cfg!(target_endian = "big") && mem::size_of::<T>() > 1 {
+            return Err(SafeTensorsError::UnsupportedByteOrder);
+        }
+
+        let ptr = if bytes.is_empty() {
+            NonNull::<T>::dangling().as_ptr()
+        } else if bytes.as_ptr().align_offset(mem::align_of::<T>()) == 0 {
+            bytes.as_ptr().cast::<T>()
+        } else {
+            return Err(SafeTensorsError::InvalidAlignment);
+        };
+
+        Ok(unsafe { View::new_unchecked(ptr, DenseMapping::new(info.shape.clone())) })
+    }
+
+    fn checked_bytes<T: Element>(
+        &self,
+        name: &str,
+    ) -> Result<(&TensorInfo, &'a [u8]), SafeTensorsError> {
+        let info = self.info(name).ok_or_else(|| SafeTensorsError::NotFound(name.to_string()))?;
+        let bytes = &self.data[info.data_offsets.0..info.data_offsets.1];
+
+        if info.dtype != T::DTYPE {
+            return Err(SafeTensorsError::DataTypeMismatch(info.dtype));
+        }
+
+        if T::DTYPE == Dtype::Bool && bytes.iter().any(|&x| x > 1) {
+            return Err(SafeTensorsError::InvalidData);
+        }
+
+        Ok((info, bytes))
+    }
+}
+
+impl Writer {
+    /// Creates a new writer with no arrays.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an array with the given name, copying the element data.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the name is already used or reserved.
+    pub fn add<T: Element, S: Shape, L: Layout>(
+        &mut self,
+        name: &str,
+        slice: &Slice<T, S, L>,
+    ) -> Result<(), SafeTensorsError> {
+        if name == METADATA_KEY || self.tensors.contains_key(name) {
+            return Err(SafeTensorsError::InvalidName(name.to_string()));
+        }
+
+        let mut bytes = Vec::with_capacity(slice.len() * mem::size_of::<T>());
+
+        for x in slice.iter() {
+            write_element(x, &mut bytes);
+        }
+
+        let shape = slice.shape().with_dims(DynRank::from_dims);
+
+        _ = self.tensors.insert(name.to_string(), (T::DTYPE, shape, bytes));
+
+        Ok(())
+    }
+
+    /// Adds a metadata key-value pair, replacing any previous value for the key.
+    pub fn add_metadata(&mut self, key: &str, value: &str) {
+        _ = self.metadata.insert(key.to_string(), value.to_string());
+    }
+
+    /// Returns the safetensors buffer with the header and the element data.
+    ///
+    /// Arrays are stored in order of decreasing element size and then by name,
+    /// so that the element data is aligned if the buffer is aligned to 8 bytes.
+    pub fn finish(self) -> Vec<u8> {
+        let mut tensors = self.tensors.iter().collect::<Vec<_>>();
+
+        tensors.sort_by(|(x, (dx, _, _)), (y, (dy, _, _))| {
+            dy.size().cmp(&dx.size()).then_with(|| x.cmp(y))
+        });
+
+        let mut header = String::from("{");
+        let mut offset = 0;
+
+        if !self.metadata.is_empty() {
+            write_string(METADATA_KEY, &mut header);
+            header.push_str(":{");
+
+            for (i, (key, value)) in self.metadata.iter().enumerate() {
+                if i > 0 {
+                    header.push(',');
+                }
+
+                write_string(key, &mut header);
+                header.push(':');
+                write_string(value, &mut header);
+            }
+
+            header.push('}');
+        }
+
+        for (i, (name, (dtype, shape, bytes))) in tensors.iter().enumerate() {
+            if i > 0 || !self.metadata.is_empty() {
+                header.push(',');
+            }
+
+            write_string(name, &mut header);
+            header.push_str(&format!(":{{\"dtype\":\"{}\",\"shape\":[", dtype.as_str()));
+
+            for (j, dim) in shape.dims().iter().enumerate() {
+                header.push_str(&format!("{}{}", if j > 0 { "," } else { "" }, dim));
+            }
+
+            header.push_str(&format!("],\"data_offsets\":[{},{}]}}", offset, offset + bytes.len()));
+            offset += bytes.len();
+        }
+
+        header.push('}');
+
+        // Pad with spaces to align the element data to 8 bytes.
+        while header.len() % 8 != 0 {
+            header.push(' ');
+        }
+
+        let mut buffer = Vec::with_capacity(8 + header.len() + offset);
+
+        buffer.extend_from_slice(&(header.len() as u64).to_le_bytes());
+        buffer.extend_from_slice(header.as_bytes());
+
+        for (_, (_, _, bytes)) in tensors {
+            buffer.extend_from_slice(bytes);
+        }
+
+        buffer
+    }
+}
+
+impl Parser<'_> {
+    fn consume(&mut self, byte: u8) -> bool {
+        self.skip_whitespace();
+
+        if self.bytes.get(self.pos) == Some(&byte) {
+            self.pos += 1;
+
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), SafeTensorsError> {
+        if self.consume(byte) { Ok(()) } else { Err(SafeTensorsError::InvalidHeader) }
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn header(
+        &mut self,
+    ) -> Result<(BTreeMap<String, TensorInfo>, BTreeMap<String, String>), SafeTensorsError> {
+        let mut tensors = BTreeMap::new();
+        let mut metadata = None;
+
+        self.object(|parser, key| {
+            if key == METADATA_KEY {
+                if metadata.is_some() {
+                    return Err(SafeTensorsError::InvalidHeader);
+                }
+
+                let mut map = BTreeMap::new();
+
+                parser.object(|parser, key| {
+                    let value = parser.string()?;
+
+                    match map.insert(key, value) {
+                        Some(_) => Err(SafeTensorsError::InvalidHeader),
+                        None => Ok(()),
+                    }
+                })?;
+
+                metadata = Some(map);
+            } else {
+                let info = parser.tensor_info()?;
+
+                if tensors.insert(key, info).is_some() {
+                    return Err(SafeTensorsError::InvalidHeader);
+                }
+            }
+
+            Ok(())
+        })?;
+
+        // Trailing whitespace is used for padding.
+        self.skip_whitespace();
+
+        if self.pos != self.bytes.len() {
+            return Err(SafeTensorsError::InvalidHeader);
+        }
+
+        Ok((tensors, metadata.unwrap_or_default()))
+    }
+
+    fn hex4(&mut self) -> Result<u32, SafeTensorsError> {
+        let digits =
+            self.bytes.get(self.pos..self.pos + 4).ok_or(SafeTensorsError::InvalidHeader)?;
+        let digits = str::from_utf8(digits).map_err(|_| SafeTensorsError::InvalidHeader)?;
+
+        self.pos += 4;
+
+        if digits.bytes().all(|x| x.is_ascii_hexdigit()) {
+            Ok(u32::from_str_radix(digits, 16).unwrap())
+        } else {
+            Err(SafeTensorsError::InvalidHeader)
+        }
+    }
+
+    fn object<F>(&mut self, mut f: F) -> Result<(), SafeTensorsError>
+    where
+        F: FnMut(&mut Self, String) -> Result<(), SafeTensorsError>,
+    {
+        self.expect(b'{')?;
+
+        if self.consume(b'}') {
+            return Ok(());
+        }
+
+        loop {
+            let key = self.string()?;
+
+            self.expect(b':')?;
+            f(self, key)?;
+
+            if !self.consume(b',') {
+                return self.expect(b'}');
+            }
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.bytes.get(self.pos) {
+            self.pos += 1;
+        }
+    }
+
+    fn string(&mut self) -> Result<String, SafeTensorsError> {
+        self.expect(b'"')?;
+
+        let mut string = String::new();
+        let mut start = self.pos;
+
+        loop {
+            let byte = *self.bytes.get(self.pos).ok_or(SafeTensorsError::InvalidHeader)?;
+
+            match byte {
+                b'"' | b'\\' => {
+                    // The header is valid UTF-8, and the segment ends at an ASCII character.
+                    string.push_str(str::from_utf8(&self.bytes[start..self.pos]).unwrap());
+                    self.pos += 1;
+
+                    if byte == b'"' {
+                        return Ok(string);
+                    }
+
+                    let escape =
+                        *self.bytes.get(self.pos).ok_or(SafeTensorsError::InvalidHeader)?;
+
+                    self.pos += 1;
+
+                    match escape {
+                        b'"' => string.push('"'),
+                        b'\\' => string.push('\\'),
+                        b'/' => string.push('/'),
+                        b'b' => string.push('\u{8}'),
+                        b'f' => string.push('\u{c}'),
+                        b'n' => string.push('\n'),
+                        b'r' => string.push('\r'),
+                        b't' => string.push('\t'),
+                        b'u' => string.push(self.unicode_escape()?),
+                        _ => return Err(SafeTensorsError::InvalidHeader),
+                    }
+
+                    start = self.pos;
+                }
+                0..0x20 => return Err(SafeTensorsError::InvalidHeader),
+                _ => self.pos += 1,
+            }
+        }
+    }
+
+    fn tensor_info(&mut self) -> Result<TensorInfo, SafeTensorsError> {
+        let mut dtype = None;
+        let mut shape = None;
+        let mut data_offsets = None;
+
+        self.object(|parser, key| {
+            let found = match key.as_str() {
+                "dtype" => {
+                    let value = Dtype::from_str(&parser.string()?);
+
+                    dtype.replace(value.ok_or(SafeTensorsError::InvalidHeader)?).is_some()
+                }
+                "shape" => shape.replace(parser.uint_array()?).is_some(),
+                "data_offsets" => match parser.uint_array()?[..] {
+                    [first, last] => data_offsets.replace((first, last)).is_some(),
+                    _ => return Err(SafeTensorsError::InvalidHeader),
+                },
+                _ => return Err(SafeTensorsError::InvalidHeader),
+            };
+
+            if found { Err(SafeTensorsError::InvalidHeader) } else { Ok(()) }
+        })?;
+
+        match (dtype, shape, data_offsets) {
+            (Some(dtype), Some(shape), Some(data_offsets)) => {
+                Ok(TensorInfo { dtype, shape: DynRank::from_dims(&shape), data_offsets })
+            }
+            _ => Err(SafeTensorsError::InvalidHeader),
+        }
+    }
+
+    fn uint(&mut self) -> Result<usize, SafeTensorsError> {
+        self.skip_whitespace();
+
+        let start = self.pos;
+        let mut value = 0usize;
+
+        while let Some(&byte @ b'0'..=b'9') = self.bytes.get(self.pos) {
+            let digit = (byte - b'0') as usize;
+
+            value = value
+                .checked_mul(10)
+                .and_then(|x| x.checked_add(digit))
+                .ok_or(SafeTensorsError::InvalidHeader)?;
+            self.pos += 1;
+        }
+
+        // Leading zeros are not permitted in JSON.
+        if self.pos == start || (self.bytes[start] == b'0' && self.pos > start + 1) {
+            return Err(SafeTensorsError::InvalidHeader);
+        }
+
+        Ok(value)
+    }
+
+    fn uint_array(&mut self) -> Result<Vec<usize>, SafeTensorsError> {
+        let mut vec = Vec::new();
+
+        self.expect(b'[')?;
+
+        if self.consume(b']') {
+            return Ok(vec);
+        }
+
+        loop {
+            vec.push(self.uint()?);
+
+            if !self.consume(b',') {
+                self.expect(b']')?;
+
+                return Ok(vec);
+            }
+        }
+    }
+
+    fn unicode_escape(&mut self) -> Result<char, SafeTensorsError> {
+        let high = self.hex4()?;
+
+        let code = if (0xd800..0xdc00).contains(&high) {
+            if !self.bytes[self.pos..].starts_with(b"\\u") {
+                return Err(SafeTensorsError::InvalidHeader);
+            }
+
+            self.pos += 2;
+
+            let low = self.hex4()?;
+
+            if !(0xdc00..0xe000).contains(&low) {
+                return Err(SafeTensorsError::InvalidHeader);
+            }
+
+            0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+        } else {
+            high
+        };
+
+        char::from_u32(code).ok_or(SafeTensorsError::InvalidHeader)
+    }
+}
+
+fn read_element<T: Element>(bytes: &[u8]) -> T {
+    let mut value = MaybeUninit::<T>::uninit();
+    let ptr = value.as_mut_ptr().cast::<u8>();
+
+    for (i, &byte) in bytes.iter().enumerate() {
+        let j = if cfg!(target_endian = "little") { i } else { bytes.len() - 1 - i };
+
+        unsafe { ptr.add(j).write(byte) };
+    }
+
+    unsafe { value.assume_init() }
+}
+
+fn write_element<T: Element>(value: &T, vec: &mut Vec<u8>) {
+    let ptr = (value as *const T).cast::<u8>();
+    let bytes = unsafe { slice::from_raw_parts(ptr, mem::size_of::<T>()) };
+
+    if cfg!(target_endian = "little") {
+        vec.extend_from_slice(bytes);
+    } else {
+        vec.extend(bytes.iter().rev());
+    }
+}
+
+fn write_string(s: &str, header: &mut String) {
+    header.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => header.push_str("\\\""),
+            '\\' => header.push_str("\\\\"),
+            '\0'..'\u{20}' => header.push_str(&format!("\\u{:04x}", c as u32)),
+            _ => header.push(c),
+        }
+    }
+
+    header.push('"');
+}
@@ -0,0 +1,272 @@
+//! Compact representation with the shape and a flat sequence of elements.
+//!
+//! An array is serialized as a struct with the fields `shape` and `data`, where
+//! `data` contains the elements in row-major order. When deserializing, the shape
+//! is validated against the array type and the elements are stored in a single
+//! buffer. The buffer is preallocated from the shape up to a limit of 1 MiB, and
+//! grows as needed for larger arrays so that invalid input cannot cause a large
+//! allocation. The module is used with the `serde(with = "...")` field attribute.
+//!
+//! # Examples
+//!
+//! ```
+//! use mdarray::DTensor;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Model {
+//!     #[serde(with = "mdarray::serde::compact")]
+//!     weights: DTensor<f32, 2>,
+//! }
+//! ```
+
+use alloc::format;
+use core::fmt::{self, Formatter};
+use core::marker::PhantomData;
+use core::mem;
+use core::ops::Deref;
+
+use serde::de::{DeserializeSeed, Error, MapAccess, SeqAccess, Visitor};
+use serde::ser::{SerializeSeq, SerializeStruct};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::allocator::Vec;
+use crate::layout::Layout;
+use crate::shape::Shape;
+use crate::slice::Slice;
+use crate::tensor::Tensor;
+
+use super::cautious_capacity;
+
+struct CompactVisitor<T, O> {
+    phantom: PhantomData<(T, O)>,
+}
+
+struct Data<'a, T, S: Shape, L: Layout> {
+    slice: &'a Slice<T, S, L>,
+}
+
+struct DataSeed<T> {
+    len: Option<usize>,
+    phantom: PhantomData<T>,
+}
+
+enum Field {
+    Shape,
+    Data,
+}
+
+/// Serializes an array with the shape and the elements in row-major order.
+///
+/// # Errors
+///
+/// Returns an error if the serializer fails.
+pub fn serialize<T, S, L, O, R>(value: &O, serializer: R) -> Result<R::Ok, R::Error>
+where
+    T: Serialize,
+    S: Shape,
+    L: Layout,
+    O: Deref<Target = Slice<T, S, L>>,
+    R: Serializer,
+{
+    let mut state = serializer.serialize_struct("Compact", 2)?;
+
+    value.shape().with_dims(|dims| state.serialize_field("shape", dims))?;
+    state.serialize_field("data", &Data { slice: &**value })?;
+    state.end()
+}
+
+/// Deserializes an array from the shape and the elements in row-major order.
+///
+/// # Errors
+///
+/// Returns an error if the deserializer fails, if the shape is not matching the
+/// array type, or if the number of elements is not matching the shape.
+pub fn deserialize<'a, T, S, O, D>(deserializer: D) -> Result<O, D::Error>
+where
+    T: Deserialize<'a>,
+    S: Shape,
+    O: Deref<Target = Slice<T, S>> + From<Tensor<T, S>>,
+    D: Deserializer<'a>,
+{
+    let visitor = CompactVisitor { phantom: PhantomData };
+
+    deserializer.deserialize_struct("Compact", &["shape", "data"], visitor)
+}
+
+impl<T, S: Shape, O: Deref<Target = Slice<T, S>> + From<Tensor<T, S>>> CompactVisitor<T, O> {
+    fn shape<E: Error>(dims: &[usize]) -> Result<(S, usize), E> {
+        if let Some(rank) = S::RANK {
+            if dims.len() != rank {
+                return Err(E::custom(format!(
+                    "invalid rank {:?}, expected {:?}",
+                    dims.len(),
+                    rank
+                )));
+            }
+        }
+
+        for (i, &dim) in dims.iter().enumerate() {
+            if let Some(size) = S::static_dim(i) {
+                if dim != size {
+                    return Err(E::custom(format!(
+                        "invalid dimension {:?}, expected {:?}",
+                        dim, size
+                    )));
+                }
+            }
+        }
+
+        let len = dims.iter().try_fold(1usize, |acc, &x| acc.checked_mul(x));
+        let len = len.filter(|&x| {
+            x.checked_mul(mem::size_of::<T>()).is_some_and(|x| x <= isize::MAX as usize)
+        });
+        let len = len.ok_or_else(|| E::custom("array size overflow"))?;
+
+        Ok((S::from_dims(dims), len))
+    }
+
+    fn tensor<E: Error>(shape: S, len: usize, vec: Vec<T>) -> Result<O, E> {
+        if vec.len() != len {
+            return Err(E::custom(format!("invalid length {:?}, expected {:?}", vec.len(), len)));
+        }
+
+        Ok(Tensor::from(vec).into_shape(shape).into())
+    }
+}
+
+impl<'a, T, S, O> Visitor<'a> for CompactVisitor<T, O>
+where
+    T: Deserialize<'a>,
+    S: Shape,
+    O: Deref<Target = Slice<T, S>> + From<Tensor<T, S>>,
+{
+    type Value = O;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "an array with shape and data")
+    }
+
+    fn visit_map<A: MapAccess<'a>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut shape = None;
+        let mut data = None;
+
+        while let Some(key) = map.next_key()? {
+            match key {
+                Field::Shape => {
+                    if shape.is_some() {
+                        return Err(A::Error::duplicate_field("shape"));
+                    }
+
+                    shape = Some(Self::shape(&map.next_value::<Tensor<usize, (usize,)>>()?[..])?);
+                }
+                Field::Data => {
+                    if data.is_some() {
+                        return Err(A::Error::duplicate_field("data"));
+                    }
+
+                    // The length is validated early if the shape is given first.
+                    let len = shape.as_ref().map(|&(_, len)| len);
+
+                    data = Some(map.next_value_seed(DataSeed { len, phantom: PhantomData })?);
+                }
+            }
+        }
+
+        let (shape, len) = shape.ok_or_else(|| A::Error::missing_field("shape"))?;
+        let data = data.ok_or_else(|| A::Error::missing_field("data"))?;
+
+        Self::tensor(shape, len, data)
+    }
+
+    fn visit_seq<A: SeqAccess<'a>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let dims: Tensor<usize, (usize,)> =
+            seq.next_element()?.ok_or_else(|| A::Error::invalid_length(0, &self))?;
+        let (shape, len) = Self::shape(&dims[..])?;
+
+        let seed = DataSeed { len: Some(len), phantom: PhantomData };
+        let data =
+            seq.next_element_seed(seed)?.ok_or_else(|| A::Error::invalid_length(1, &self))?;
+
+        Self::tensor(shape, len, data)
+    }
+}
+
+impl<T: Serialize, S: Shape, L: Layout> Serialize for Data<'_, T, S, L> {
+    fn serialize<R: Serializer>(&self, serializer: R) -> Result<R::Ok, R::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.slice.len()))?;
+
+        for x in self.slice.iter() {
+            seq.serialize_element(x)?;
+        }
+
+        seq.end()
+    }
+}
+
+impl<'a, T: Deserialize<'a>> DeserializeSeed<'a> for DataSeed<T> {
+    type Value = Vec<T>;
+
+    fn deserialize<D: Deserializer<'a>>(self, deserializer: D) -> Result<Vec<T>, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'a, T: Deserialize<'a>> Visitor<'a> for DataSeed<T> {
+    type Value = Vec<T>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self.len {
+            Some(len) => write!(formatter, "a sequence of {} elements", len),
+            None => write!(formatter, "a sequence of elements"),
+        }
+    }
+
+    fn visit_seq<A: SeqAccess<'a>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        // Limit preallocation to protect against invalid input, also if the length matches
+        // the size hint since both are given by the input.
+        let mut vec = Vec::with_capacity(cautious_capacity::<T>(self.len.or(seq.size_hint())));
+
+        while let Some(value) = seq.next_element()? {
+            if self.len.is_some_and(|len| vec.len() == len) {
+                return Err(A::Error::invalid_length(vec.len() + 1, &self));
+            }
+
+            vec.push(value);
+        }
+
+        Ok(vec)
+    }
+}
+
+impl<'a> Deserialize<'a> for Field {
+    fn deserialize<D: Deserializer<'a>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FieldVisitor;
+
+        impl Visitor<'_> for FieldVisitor {
+            type Value = Field;
+
+            fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                write!(formatter, "`shape` or `data`")
+            }
+
+            fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+                match v {
+                    "shape" => Ok(Field::Shape),
+                    "data" => Ok(Field::Data),
+                    _ => Err(E::unknown_field(v, &["shape", "data"])),
+                }
+            }
+
+            fn visit_u64<E: Error>(self, v: u64) -> Result<Self::Value, E> {
+                match v {
+                    0 => Ok(Field::Shape),
+                    1 => Ok(Field::Data),
+                    _ => Err(E::custom("invalid field index")),
+                }
+            }
+        }
+
+        deserializer.deserialize_identifier(FieldVisitor)
+    }
+}
@@ -0,0 +1,590 @@
+//! Serde module, with an optional compact representation for arrays.
+//!
+//! The default representation for arrays is nested sequences, where the shape is
+//! given by the nesting. The `compact` module gives a flat representation with
+//! the shape and the elements in row-major order.
+
+pub mod compact;
+
+use alloc::format;
+use alloc::string::String;
+use core::fmt::{self, Formatter};
+use core::marker::PhantomData;
+use core::mem;
+
+use serde::de::value::{
+    BorrowedBytesDeserializer, BorrowedStrDeserializer, EnumAccessDeserializer,
+    MapAccessDeserializer, UnitDeserializer,
+};
+use serde::de::{
+    DeserializeSeed, EnumAccess, Error, IntoDeserializer, MapAccess, SeqAccess, Visitor,
+};
+use serde::ser::{SerializeSeq, SerializeStruct};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::allocator::{Allocator, Vec};
+use crate::array::Array;
+use crate::dim::Const;
+use crate::layout::Layout;
+use crate::mapping::{DenseMapping, Mapping, StridedMapping};
+use crate::shape::{ConstShape, DynRank, Shape};
+use crate::slice::Slice;
+use crate::tensor::Tensor;
+use crate::view::{View, ViewMut};
+use crate::{array, tensor};
+
+struct DenseMappingVisitor<S: Shape> {
+    phantom: PhantomData<S>,
+}
+
+struct DynRankSeed<'a, T> {
+    state: &'a mut TensorState<T>,
+    depth: usize,
+}
+
+// Deserializer for an element found by the depth search, where the value is
+// given directly also if the element type is an option.
+struct ElementDeserializer<D> {
+    deserializer: D,
+}
+
+enum StridedMappingField {
+    Shape,
+    Strides,
+}
+
+struct StridedMappingVisitor<S: Shape> {
+    phantom: PhantomData<S>,
+}
+
+struct TensorSeed<'a, T> {
+    state: &'a mut TensorState<T>,
+    depth: usize,
+}
+
+struct TensorState<T> {
+    vec: Vec<T>,
+    dims: Vec<Option<usize>>,
+    index: Vec<usize>,
+    rank: Option<usize>,
+    seq_depth: usize,
+}
+
+// Upper limit in bytes for preallocation, since the size hint is not trusted.
+const MAX_PREALLOC_BYTES: usize = 1 << 20;
+
+impl<'a, S: Shape + Deserialize<'a>> Visitor<'a> for DenseMappingVisitor<S> {
+    type Value = DenseMapping<S>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "a dense layout mapping")
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'a>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        Ok(DenseMapping::new(S::deserialize(deserializer)?))
+    }
+}
+
+impl<'a, T: Deserialize<'a>> DeserializeSeed<'a> for DynRankSeed<'_, T> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'a>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'a, T: Deserialize<'a>> DynRankSeed<'_, T> {
+    fn push<E: Error>(self, value: T) -> Result<(), E> {
+        match self.state.rank {
+            None if self.depth < self.state.seq_depth => {
+                let msg =
+                    format!("invalid depth {:?}, expected {:?}", self.depth, self.state.seq_depth);
+
+                return Err(self.state.error(msg));
+            }
+            None => self.state.rank = Some(self.depth),
+            Some(rank) if rank != self.depth => {
+                let msg = format!("invalid depth {:?}, expected {:?}", self.depth, rank);
+
+                return Err(self.state.error(msg));
+            }
+            Some(_) => (),
+        }
+
+        self.state.vec.push(value);
+
+        Ok(())
+    }
+
+    fn visit_value<E: Error, D: IntoDeserializer<'a, E>>(self, value: D) -> Result<(), E> {
+        let value =
+            T::deserialize(ElementDeserializer { deserializer: value.into_deserializer() })?;
+
+        self.push(value)
+    }
+}
+
+macro_rules! forward_deserialize {
+    ($($name:ident($($arg:ident: $type:ty),*)),+) => {
+        $(
+            fn $name<V: Visitor<'a>>(self, $($arg: $type,)* visitor: V) -> Result<V::Value, D::Error> {
+                self.deserializer.$name($($arg,)* visitor)
+            }
+        )+
+    };
+}
+
+impl<'a, D: Deserializer<'a>> Deserializer<'a> for ElementDeserializer<D> {
+    type Error = D::Error;
+
+    forward_deserialize!(deserialize_any(), deserialize_bool(), deserialize_char());
+    forward_deserialize!(deserialize_f32(), deserialize_f64());
+    forward_deserialize!(deserialize_i8(), deserialize_i16(), deserialize_i32());
+    forward_deserialize!(deserialize_i64(), deserialize_i128());
+    forward_deserialize!(deserialize_u8(), deserialize_u16(), deserialize_u32());
+    forward_deserialize!(deserialize_u64(), deserialize_u128());
+    forward_deserialize!(deserialize_bytes(), deserialize_byte_buf());
+    forward_deserialize!(deserialize_str(), deserialize_string(), deserialize_identifier());
+    forward_deserialize!(deserialize_map(), deserialize_seq(), deserialize_tuple(len: usize));
+    forward_deserialize!(deserialize_unit(), deserialize_unit_struct(name: &'static str));
+    forward_deserialize!(deserialize_newtype_struct(name: &'static str));
+    forward_deserialize!(deserialize_tuple_struct(name: &'static str, len: usize));
+    forward_deserialize!(deserialize_struct(name: &'static str, fields: &'static [&'static str]));
+    forward_deserialize!(deserialize_enum(name: &'static str, variants: &'static [&'static str]));
+    forward_deserialize!(deserialize_ignored_any());
+
+    fn deserialize_option<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value, D::Error> {
+        visitor.visit_some(self.deserializer)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.deserializer.is_human_readable()
+    }
+}
+
+macro_rules! visit_value {
+    ($($name:ident: $type:ty),+) => {
+        $(
+            fn $name<E: Error>(self, v: $type) -> Result<(), E> {
+                self.visit_value(v)
+            }
+        )+
+    };
+}
+
+impl<'a, T: Deserialize<'a>> Visitor<'a> for DynRankSeed<'_, T> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "an array or an element")
+    }
+
+    visit_value!(visit_bool: bool, visit_char: char, visit_f32: f32, visit_f64: f64);
+    visit_value!(visit_i8: i8, visit_i16: i16, visit_i32: i32, visit_i64: i64, visit_i128: i128);
+    visit_value!(visit_u8: u8, visit_u16: u16, visit_u32: u32, visit_u64: u64, visit_u128: u128);
+    visit_value!(visit_bytes: &[u8], visit_str: &str, visit_string: String);
+
+    fn visit_borrowed_bytes<E: Error>(self, v: &'a [u8]) -> Result<(), E> {
+        self.visit_value(BorrowedBytesDeserializer::new(v))
+    }
+
+    fn visit_borrowed_str<E: Error>(self, v: &'a str) -> Result<(), E> {
+        self.visit_value(BorrowedStrDeserializer::new(v))
+    }
+
+    fn visit_enum<A: EnumAccess<'a>>(self, data: A) -> Result<(), A::Error> {
+        self.visit_value(EnumAccessDeserializer::new(data))
+    }
+
+    fn visit_map<A: MapAccess<'a>>(self, map: A) -> Result<(), A::Error> {
+        self.visit_value(MapAccessDeserializer::new(map))
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'a>>(self, deserializer: D) -> Result<(), D::Error> {
+        let value = T::deserialize(deserializer)?;
+
+        self.push(value)
+    }
+
+    fn visit_none<E: Error>(self) -> Result<(), E> {
+        self.visit_unit()
+    }
+
+    fn visit_seq<A: SeqAccess<'a>>(self, mut seq: A) -> Result<(), A::Error> {
+        let depth = self.depth;
+
+        if let Some(rank) = self.state.rank.filter(|&rank| depth >= rank) {
+            let msg = format!("invalid depth {:?}, expected {:?}", depth + 1, rank);
+
+            return Err(self.state.error(msg));
+        }
+
+        self.state.seq_depth = self.state.seq_depth.max(depth + 1);
+
+        let mut size = 0;
+
+        loop {
+            self.state.index.push(size);
+
+            let seed = DynRankSeed { state: &mut *self.state, depth: depth + 1 };
+            let next = seq.next_element_seed(seed)?;
+
+            _ = self.state.index.pop();
+
+            if next.is_none() {
+                break;
+            }
+
+            size += 1;
+        }
+
+        self.state.visit_dim(depth, size)
+    }
+
+    fn visit_some<D: Deserializer<'a>>(self, deserializer: D) -> Result<(), D::Error> {
+        let value = T::deserialize(ElementDeserializer { deserializer })?;
+
+        self.push(value)
+    }
+
+    fn visit_unit<E: Error>(self) -> Result<(), E> {
+        // A unit value is passed as is, so that it is deserialized as none for an option.
+        let value = T::deserialize(UnitDeserializer::new())?;
+
+        self.push(value)
+    }
+}
+
+impl<'a> Deserialize<'a> for StridedMappingField {
+    fn deserialize<D: Deserializer<'a>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FieldVisitor;
+
+        impl Visitor<'_> for FieldVisitor {
+            type Value = StridedMappingField;
+
+            fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                write!(formatter, "`shape` or `strides`")
+            }
+
+            fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+                match v {
+                    "shape" => Ok(StridedMappingField::Shape),
+                    "strides" => Ok(StridedMappingField::Strides),
+                    _ => Err(E::unknown_field(v, &["shape", "strides"])),
+                }
+            }
+
+            fn visit_u64<E: Error>(self, v: u64) -> Result<Self::Value, E> {
+                match v {
+                    0 => Ok(StridedMappingField::Shape),
+                    1 => Ok(StridedMappingField::Strides),
+                    _ => Err(E::custom("invalid field index")),
+                }
+            }
+        }
+
+        deserializer.deserialize_identifier(FieldVisitor)
+    }
+}
+
+impl<S: Shape> StridedMappingVisitor<S> {
+    fn mapping<E: Error>(
+        shape: S,
+        strides: Tensor<isize, (usize,)>,
+    ) -> Result<StridedMapping<S>, E> {
+        if strides.len() != shape.rank() {
+            let msg = format!("invalid rank {:?}, expected {:?}", strides.len(), shape.rank());
+
+            return Err(E::custom(msg));
+        }
+
+        Ok(StridedMapping::new(shape, &strides[..]))
+    }
+}
+
+impl<'a, S: Shape + Deserialize<'a>> Visitor<'a> for StridedMappingVisitor<S> {
+    type Value = StridedMapping<S>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "a strided layout mapping")
+    }
+
+    fn visit_map<A: MapAccess<'a>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut shape = None;
+        let mut strides = None;
+
+        while let Some(key) = map.next_key()? {
+            match key {
+                StridedMappingField::Shape => {
+                    if shape.is_some() {
+                        return Err(A::Error::duplicate_field("shape"));
+                    }
+
+                    shape = Some(map.next_value()?);
+                }
+                StridedMappingField::Strides => {
+                    if strides.is_some() {
+                        return Err(A::Error::duplicate_field("strides"));
+                    }
+
+                    strides = Some(map.next_value()?);
+                }
+            }
+        }
+
+        let shape = shape.ok_or_else(|| A::Error::missing_field("shape"))?;
+        let strides = strides.ok_or_else(|| A::Error::missing_field("strides"))?;
+
+        Self::mapping(shape, strides)
+    }
+
+    fn visit_seq<A: SeqAccess<'a>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let shape = seq.next_element()?.ok_or_else(|| A::Error::invalid_length(0, &self))?;
+        let strides = seq.next_element()?.ok_or_else(|| A::Error::invalid_length(1, &self))?;
+
+        Self::mapping(shape, strides)
+    }
+}
+
+impl<'a, T: Deserialize<'a>> DeserializeSeed<'a> for TensorSeed<'_, T> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'a>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'a, T: Deserialize<'a>> Visitor<'a> for TensorSeed<'_, T> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "an array of rank {}", self.state.dims.len() - self.depth)
+    }
+
+    fn visit_seq<A: SeqAccess<'a>>(self, mut seq: A) -> Result<(), A::Error> {
+        let depth = self.depth;
+        let mut size = 0;
+
+        if depth + 1 == self.state.dims.len() {
+            self.state.vec.reserve(cautious_capacity::<T>(seq.size_hint()));
+        }
+
+        loop {
+            self.state.index.push(size);
+
+            let next = if depth + 1 == self.state.dims.len() {
+                seq.next_element()?.map(|value| self.state.vec.push(value))
+            } else {
+                seq.next_element_seed(TensorSeed { state: &mut *self.state, depth: depth + 1 })?
+            };
+
+            _ = self.state.index.pop();
+
+            if next.is_none() {
+                break;
+            }
+
+            size += 1;
+        }
+
+        self.state.visit_dim(depth, size)
+    }
+}
+
+impl<T> TensorState<T> {
+    fn new(rank: Option<usize>, dims: Vec<Option<usize>>) -> Self {
+        Self { vec: Vec::new(), dims, index: Vec::new(), rank, seq_depth: 0 }
+    }
+
+    fn error<E: Error>(&self, msg: String) -> E {
+        if self.index.is_empty() {
+            E::custom(msg)
+        } else {
+            E::custom(format!("{} at index {:?}", msg, &self.index[..]))
+        }
+    }
+
+    fn into_tensor<S: Shape, E: Error>(self) -> Result<Tensor<T, S>, E> {
+        let rank = self.rank.unwrap_or(self.seq_depth);
+        let dims = self.dims[..rank].iter().map(|&x| x.unwrap_or(0)).collect::<Vec<_>>();
+        let shape = S::from_dims(&dims);
+
+        match shape.checked_len() {
+            Some(len) if len == self.vec.len() => Ok(Tensor::from(self.vec).into_shape(shape)),
+            Some(len) => {
+                Err(E::custom(format!("invalid length {:?}, expected {:?}", self.vec.len(), len)))
+            }
+            None => Err(E::custom("array size overflow")),
+        }
+    }
+
+    fn visit_dim<E: Error>(&mut self, depth: usize, size: usize) -> Result<(), E> {
+        if self.dims.len() <= depth {
+            self.dims.resize(depth + 1, None);
+        }
+
+        match self.dims[depth] {
+            None => self.dims[depth] = Some(size),
+            Some(dim) if dim != size => {
+                let msg = format!("invalid dimension {:?}, expected {:?}", size, dim);
+
+                return Err(self.error(msg));
+            }
+            Some(_) => (),
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a, T: Deserialize<'a>, S: ConstShape> Deserialize<'a> for Array<T, S> {
+    fn deserialize<R: Deserializer<'a>>(deserializer: R) -> Result<Self, R::Error> {
+        if S::RANK != Some(0) {
+            Ok(<Tensor<T, S> as Deserialize>::deserialize(deserializer)?.into())
+        } else {
+            let value = <T as Deserialize>::deserialize(deserializer)?;
+
+            Ok(array![value].into_shape())
+        }
+    }
+}
+
+impl<'a, const N: usize> Deserialize<'a> for Const<N> {
+    fn deserialize<R: Deserializer<'a>>(deserializer: R) -> Result<Self, R::Error> {
+        let size = usize::deserialize(deserializer)?;
+
+        if size != N {
+            let msg = format!("invalid dimension {:?}, expected {:?}", size, N);
+
+            return Err(R::Error::custom(msg));
+        }
+
+        Ok(Self)
+    }
+}
+
+impl<'a, S: Shape + Deserialize<'a>> Deserialize<'a> for DenseMapping<S> {
+    fn deserialize<R: Deserializer<'a>>(deserializer: R) -> Result<Self, R::Error> {
+        let visitor = DenseMappingVisitor { phantom: PhantomData };
+
+        deserializer.deserialize_newtype_struct("DenseMapping", visitor)
+    }
+}
+
+impl<'a> Deserialize<'a> for DynRank {
+    fn deserialize<R: Deserializer<'a>>(deserializer: R) -> Result<Self, R::Error> {
+        let dims = <Tensor<usize, (usize,)> as Deserialize>::deserialize(deserializer)?;
+
+        Ok(Shape::from_dims(&dims[..]))
+    }
+}
+
+impl<'a, S: Shape + Deserialize<'a>> Deserialize<'a> for StridedMapping<S> {
+    fn deserialize<R: Deserializer<'a>>(deserializer: R) -> Result<Self, R::Error> {
+        let visitor = StridedMappingVisitor { phantom: PhantomData };
+
+        deserializer.deserialize_struct("StridedMapping", &["shape", "strides"], visitor)
+    }
+}
+
+impl<'a, T: Deserialize<'a>, S: Shape> Deserialize<'a> for Tensor<T, S> {
+    fn deserialize<R: Deserializer<'a>>(deserializer: R) -> Result<Self, R::Error> {
+        match S::RANK {
+            None => {
+                // The rank is given by the nesting depth, which requires a self-describing format.
+                let mut state = TensorState::new(None, Vec::new());
+
+                DynRankSeed { state: &mut state, depth: 0 }.deserialize(deserializer)?;
+                state.into_tensor()
+            }
+            Some(0) => {
+                let value = <T as Deserialize>::deserialize(deserializer)?;
+
+                Ok(tensor![value].into_shape(S::default()))
+            }
+            Some(rank) => {
+                let dims = (0..rank).map(|i| S::static_dim(i)).collect::<Vec<_>>();
+                let mut state = TensorState::new(S::RANK, dims);
+
+                TensorSeed { state: &mut state, depth: 0 }.deserialize(deserializer)?;
+                state.into_tensor()
+            }
+        }
+    }
+}
+
+impl<T: Serialize, S: ConstShape> Serialize for Array<T, S> {
+    fn serialize<R: Serializer>(&self, serializer: R) -> Result<R::Ok, R::Error> {
+        (**self).serialize(serializer)
+    }
+}
+
+impl<const N: usize> Serialize for Const<N> {
+    fn serialize<R: Serializer>(&self, serializer: R) -> Result<R::Ok, R::Error> {
+        N.serialize(serializer)
+    }
+}
+
+impl<S: Shape + Serialize> Serialize for DenseMapping<S> {
+    fn serialize<R: Serializer>(&self, serializer: R) -> Result<R::Ok, R::Error> {
+        serializer.serialize_newtype_struct("DenseMapping", self.shape())
+    }
+}
+
+impl Serialize for DynRank {
+    fn serialize<R: Serializer>(&self, serializer: R) -> Result<R::Ok, R::Error> {
+        self.dims().serialize(serializer)
+    }
+}
+
+impl<T: Serialize, S: Shape, L: Layout> Serialize for Slice<T, S, L> {
+    fn serialize<R: Serializer>(&self, serializer: R) -> Result<R::Ok, R::Error> {
+        if self.rank() == 0 {
+            self[[]].serialize(serializer)
+        } else {
+            let mut seq = serializer.serialize_seq(Some(self.dim(0)))?;
+
+            for x in self.outer_expr() {
+                seq.serialize_element(&x)?;
+            }
+
+            seq.end()
+        }
+    }
+}
+
+impl<S: Shape + Serialize> Serialize for StridedMapping<S> {
+    fn serialize<R: Serializer>(&self, serializer: R) -> Result<R::Ok, R::Error> {
+        let mut state = serializer.serialize_struct("StridedMapping", 2)?;
+
+        state.serialize_field("shape", self.shape())?;
+        state.serialize_field("strides", self.strides())?;
+        state.end()
+    }
+}
+
+impl<T: Serialize, S: Shape, A: Allocator> Serialize for Tensor<T, S, A> {
+    fn serialize<R: Serializer>(&self, serializer: R) -> Result<R::Ok, R::Error> {
+        (**self).serialize(serializer)
+    }
+}
+
+impl<T: Serialize, S: Shape, L: Layout> Serialize for View<'_, T, S, L> {
+    fn serialize<R: Serializer>(&self, serializer: R) -> Result<R::Ok, R::Error> {
+        (**self).serialize(serializer)
+    }
+}
+
+impl<T: Serialize, S: Shape, L: Layout> Serialize for ViewMut<'_, T, S, L> {
+    fn serialize<R: Serializer>(&self, serializer: R) -> Result<R::Ok, R::Error> {
+        (**self).serialize(serializer)
+    }
+}
+
+// Returns the capacity to preallocate, with a limit to protect against invalid input.
+fn cautious_capacity<T>(size_hint: Option<usize>) -> usize {
+    size_hint.unwrap_or(0).min(MAX_PREALLOC_BYTES / mem::size_of::<T>().max(1))
+}
@@ -1,12 +1,19 @@
-use std::cmp::Ordering;
-use std::fmt::Debug;
-use std::hash::{Hash, Hasher};
-use std::slice;
-
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt::Debug;
+use core::hash::{Hash, Hasher};
+use core::slice;
+
+#[cfg(feature = "alloc")]
 use crate::array::Array;
 use crate::dim::{Const, Dim, Dims, Dyn};
 use crate::layout::{Layout, Strided};
+#[cfg(feature = "alloc")]
 use crate::tensor::Tensor;
+#[cfg(feature = "alloc")]
 use crate::traits::Owned;
 
 /// Array shape trait.
@@ -34,6 +41,7 @@ pub trait Shape: Clone + Debug + Default + Hash + Ord + Send + Sync {
     type Layout<L: Layout>: Layout;
 
     /// Corresponding array type owning its contents.
+    #[cfg(feature = "alloc")]
     type Owned<T>: Owned<T, Self>;
 
     #[doc(hidden)]
@@ -83,6 +91,9 @@ pub trait Shape: Clone + Debug + Default + Hash + Ord + Send + Sync {
     #[doc(hidden)]
     fn new(rank: usize) -> Self;
 
+    #[doc(hidden)]
+    fn static_dim(index: usize) -> Option<usize>;
+
     #[doc(hidden)]
     fn with_dims<T, F: FnOnce(&[usize]) -> T>(&self, f: F) -> T;
 
@@ -183,6 +194,7 @@ pub trait ConstShape: Copy + Shape {
     #[doc(hidden)]
     type Inner<T>;
 
+    #[cfg(feature = "alloc")]
     #[doc(hidden)]
     type WithConst<T, const N: usize, A: Owned<T, Self>>: Owned<T, Self::Prepend<Const<N>>>;
 }
@@ -202,9 +214,11 @@ pub trait IntoShape {
 /// Array shape type with dynamic rank.
 ///
 /// If the rank is 0 or 1, no heap allocation is necessary. The default value
-/// will have rank 1 and contain no elements.
+/// will have rank 1 and contain no elements. Without the `alloc` feature, only
+/// rank 1 is supported.
 pub enum DynRank {
     /// Shape variant with dynamic rank.
+    #[cfg(feature = "alloc")]
     Dyn(Box<[usize]>),
     /// Shape variant with rank 1.
     One(usize),
@@ -217,6 +231,7 @@ impl DynRank {
     /// Returns the number of elements in each dimension.
     pub fn dims(&self) -> &[usize] {
         match self {
+            #[cfg(feature = "alloc")]
             Self::Dyn(dims) => dims,
             Self::One(size) => slice::from_ref(size),
         }
@@ -227,6 +242,7 @@ impl Clone for DynRank {
     fn clone(&self) -> Self {
         match self {
             Self::One(dim) => Self::One(*dim),
+            #[cfg(feature = "alloc")]
             Self::Dyn(dims) => {
                 if dims.len() == 1 {
                     Self::One(dims[0])
@@ -238,6 +254,7 @@ impl Clone for DynRank {
     }
 
     fn clone_from(&mut self, source: &Self) {
+        #[cfg(feature = "alloc")]
         if let Self::Dyn(dims) = self {
             if let Self::Dyn(src) = source {
                 if dims.len() == src.len() {
@@ -253,7 +270,7 @@ impl Clone for DynRank {
 }
 
 impl Debug for DynRank {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         self.with_dims(|dims| f.debug_tuple("DynRank").field(&dims).finish())
     }
 }
@@ -301,18 +318,35 @@ impl Shape for DynRank {
     type Merge<S: Shape> = Self;
 
     type Layout<L: Layout> = Strided;
+    #[cfg(feature = "alloc")]
     type Owned<T> = Tensor<T>;
 
+    #[cfg(feature = "alloc")]
     type Dims<T: Copy + Debug + Default + Eq + Hash + Send + Sync> = Box<[T]>;
+    #[cfg(not(feature = "alloc"))]
+    type Dims<T: Copy + Debug + Default + Eq + Hash + Send + Sync> = [T; 1];
 
     const RANK: Option<usize> = None;
 
+    #[cfg(feature = "alloc")]
     fn new(rank: usize) -> Self {
         if rank == 1 { Self::One(0) } else { Self::Dyn(Dims::new(rank)) }
     }
 
+    #[cfg(not(feature = "alloc"))]
+    fn new(rank: usize) -> Self {
+        assert!(rank == 1, "invalid rank");
+
+        Self::One(0)
+    }
+
+    fn static_dim(_: usize) -> Option<usize> {
+        None
+    }
+
     fn with_dims<T, F: FnOnce(&[usize]) -> T>(&self, f: F) -> T {
         let dims = match self {
+            #[cfg(feature = "alloc")]
             Self::Dyn(dims) => dims,
             Self::One(size) => slice::from_ref(size),
         };
@@ -322,6 +356,7 @@ impl Shape for DynRank {
 
     fn with_mut_dims<T, F: FnOnce(&mut [usize]) -> T>(&mut self, f: F) -> T {
         let dims = match self {
+            #[cfg(feature = "alloc")]
             Self::Dyn(dims) => dims,
             Self::One(size) => slice::from_mut(size),
         };
@@ -341,6 +376,7 @@ impl Shape for () {
     type Merge<S: Shape> = S;
 
     type Layout<L: Layout> = L;
+    #[cfg(feature = "alloc")]
     type Owned<T> = Array<T, ()>;
 
     type Dims<T: Copy + Debug + Default + Eq + Hash + Send + Sync> = [T; 0];
@@ -351,6 +387,10 @@ impl Shape for () {
         assert!(rank == 0, "invalid rank");
     }
 
+    fn static_dim(_: usize) -> Option<usize> {
+        panic!("invalid dimension");
+    }
+
     fn with_dims<T, F: FnOnce(&[usize]) -> T>(&self, f: F) -> T {
         f(&[])
     }
@@ -371,6 +411,7 @@ impl<X: Dim> Shape for (X,) {
     type Merge<S: Shape> = <S::Tail as Shape>::Prepend<X::Merge<S::Head>>;
 
     type Layout<L: Layout> = Strided;
+    #[cfg(feature = "alloc")]
     type Owned<T> = X::Owned<T, ()>;
 
     type Dims<T: Copy + Debug + Default + Eq + Hash + Send + Sync> = [T; 1];
@@ -383,6 +424,12 @@ impl<X: Dim> Shape for (X,) {
         Self::default()
     }
 
+    fn static_dim(index: usize) -> Option<usize> {
+        assert!(index < 1, "invalid dimension");
+
+        X::SIZE
+    }
+
     fn with_dims<T, F: FnOnce(&[usize]) -> T>(&self, f: F) -> T {
         f(&[self.0.size()])
     }
@@ -425,6 +472,7 @@ macro_rules! impl_shape {
                 <<Self::Tail as Shape>::Merge<S::Tail> as Shape>::Prepend<X::Merge<S::Head>>;
 
             type Layout<L: Layout> = Strided;
+            #[cfg(feature = "alloc")]
             type Owned<T> = X::Owned<T, Self::Tail>;
 
             type Dims<T: Copy + Debug + Default + Eq + Hash + Send + Sync> = [T; $n];
@@ -437,6 +485,12 @@ macro_rules! impl_shape {
                 Self::default()
             }
 
+            fn static_dim(index: usize) -> Option<usize> {
+                assert!(index < $n, "invalid dimension");
+
+                [X::SIZE $(,$yz::SIZE)+][index]
+            }
+
             fn with_dims<T, F: FnOnce(&[usize]) -> T>(&self, f: F) -> T {
                 f(&[self.0.size() $(,self.$jk.size())+])
             }
@@ -463,6 +517,7 @@ macro_rules! impl_const_shape {
     (($($xyz:tt),*), $inner:ty, $with_const:tt) => {
         impl<$(const $xyz: usize),*> ConstShape for ($(Const<$xyz>,)*) {
             type Inner<T> = $inner;
+            #[cfg(feature = "alloc")]
             type WithConst<T, const N: usize, A: Owned<T, Self>> =
                 $with_const<T, Self::Prepend<Const<N>>>;
         }
@@ -513,6 +568,7 @@ impl IntoShape for &[usize] {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl IntoShape for Box<[usize]> {
     type IntoShape = DynRank;
 
@@ -549,6 +605,7 @@ impl IntoShape for Dyn {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl IntoShape for Vec<usize> {
     type IntoShape = DynRank;
 
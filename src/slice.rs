@@ -1,23 +1,32 @@
-#[cfg(feature = "nightly")]
-use std::alloc::Allocator;
-use std::fmt::{Debug, Formatter, Result};
-use std::hash::{Hash, Hasher};
-use std::marker::PhantomData;
-use std::mem;
-use std::ops::{Index, IndexMut};
-use std::ptr::NonNull;
-
+#[cfg(feature = "alloc")]
+use alloc::borrow::ToOwned;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt::{Debug, Formatter, Result};
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::mem;
+use core::ops::{Index, IndexMut, Range};
+use core::ptr::NonNull;
+
+#[cfg(all(feature = "alloc", any(feature = "nightly", feature = "allocator-api2")))]
+use crate::allocator::{self, Allocator};
 use crate::array::Array;
 use crate::dim::{Const, Dim, Dyn};
-use crate::expr::{Apply, Expression, FromExpression, IntoExpression};
+#[cfg(feature = "alloc")]
+use crate::expr::{self, FromExpression, PadMode};
+use crate::expr::{Apply, Expression, IntoExpression};
 use crate::expr::{AxisExpr, AxisExprMut, Iter, Lanes, LanesMut, Map, Zip};
 use crate::index::{Axis, Cols, DimIndex, Permutation, Resize, Rows, SliceIndex, Split, ViewIndex};
 use crate::layout::{Dense, Layout, Strided};
 use crate::mapping::Mapping;
 use crate::raw_slice::RawSlice;
 use crate::shape::{ConstShape, DynRank, IntoShape, Rank, Shape};
+#[cfg(feature = "alloc")]
 use crate::tensor::Tensor;
-use crate::traits::{IntoCloned, Owned};
+use crate::traits::IntoCloned;
+#[cfg(feature = "alloc")]
+use crate::traits::Owned;
 use crate::view::{View, ViewMut};
 
 /// Multidimensional array slice.
@@ -61,7 +70,7 @@ impl<T, S: Shape, L: Layout> Slice<T, S, L> {
     /// # Panics
     ///
     /// Panics if the index is out of bounds, or if the rank is not at least 1.
-    pub fn at(&self, index: usize) -> View<T, S::Tail, L> {
+    pub fn at(&self, index: usize) -> View<'_, T, S::Tail, L> {
         self.axis_at(Const::<0>, index)
     }
 
@@ -70,7 +79,7 @@ impl<T, S: Shape, L: Layout> Slice<T, S, L> {
     /// # Panics
     ///
     /// Panics if the index is out of bounds, or if the rank is not at least 1.
-    pub fn at_mut(&mut self, index: usize) -> ViewMut<T, S::Tail, L> {
+    pub fn at_mut(&mut self, index: usize) -> ViewMut<'_, T, S::Tail, L> {
         self.axis_at_mut(Const::<0>, index)
     }
 
@@ -83,7 +92,11 @@ impl<T, S: Shape, L: Layout> Slice<T, S, L> {
     /// # Panics
     ///
     /// Panics if the dimension or the index is out of bounds.
-    pub fn axis_at<A: Axis>(&self, axis: A, index: usize) -> View<T, A::Remove<S>, Split<A, S, L>> {
+    pub fn axis_at<A: Axis>(
+        &self,
+        axis: A,
+        index: usize,
+    ) -> View<'_, T, A::Remove<S>, Split<A, S, L>> {
         unsafe { View::axis_at(self.as_ptr(), self.mapping(), axis, index) }
     }
 
@@ -100,7 +113,7 @@ impl<T, S: Shape, L: Layout> Slice<T, S, L> {
         &mut self,
         axis: A,
         index: usize,
-    ) -> ViewMut<T, A::Remove<S>, Split<A, S, L>> {
+    ) -> ViewMut<'_, T, A::Remove<S>, Split<A, S, L>> {
         unsafe { ViewMut::axis_at(self.as_mut_ptr(), self.mapping(), axis, index) }
     }
 
@@ -113,7 +126,7 @@ impl<T, S: Shape, L: Layout> Slice<T, S, L> {
     /// # Panics
     ///
     /// Panics if the dimension is out of bounds.
-    pub fn axis_expr<A: Axis>(&self, axis: A) -> AxisExpr<T, S, L, A> {
+    pub fn axis_expr<A: Axis>(&self, axis: A) -> AxisExpr<'_, T, S, L, A> {
         AxisExpr::new(self, axis)
     }
 
@@ -126,7 +139,7 @@ impl<T, S: Shape, L: Layout> Slice<T, S, L> {
     /// # Panics
     ///
     /// Panics if the dimension is out of bounds.
-    pub fn axis_expr_mut<A: Axis>(&mut self, axis: A) -> AxisExprMut<T, S, L, A> {
+    pub fn axis_expr_mut<A: Axis>(&mut self, axis: A) -> AxisExprMut<'_, T, S, L, A> {
         AxisExprMut::new(self, axis)
     }
 
@@ -135,7 +148,7 @@ impl<T, S: Shape, L: Layout> Slice<T, S, L> {
     /// # Panics
     ///
     /// Panics if the rank is not equal to 2, or if the index is out of bounds.
-    pub fn col(&self, index: usize) -> View<T, (S::Head,), Strided> {
+    pub fn col(&self, index: usize) -> View<'_, T, (S::Head,), Strided> {
         let shape = self.shape().with_dims(<(_, <S::Tail as Shape>::Head)>::from_dims);
 
         self.reshape(shape).into_view(.., index)
@@ -146,7 +159,7 @@ impl<T, S: Shape, L: Layout> Slice<T, S, L> {
     /// # Panics
     ///
     /// Panics if the rank is not equal to 2, or if the index is out of bounds.
-    pub fn col_mut(&mut self, index: usize) -> ViewMut<T, (S::Head,), Strided> {
+    pub fn col_mut(&mut self, index: usize) -> ViewMut<'_, T, (S::Head,), Strided> {
         let shape = self.shape().with_dims(<(_, <S::Tail as Shape>::Head)>::from_dims);
 
         self.reshape_mut(shape).into_view(.., index)
@@ -157,7 +170,7 @@ impl<T, S: Shape, L: Layout> Slice<T, S, L> {
     /// # Panics
     ///
     /// Panics if the rank is not at least 2.
-    pub fn cols(&self) -> Lanes<T, S, L, Cols> {
+    pub fn cols(&self) -> Lanes<'_, T, S, L, Cols> {
         self.lanes(Cols)
     }
 
@@ -166,7 +179,7 @@ impl<T, S: Shape, L: Layout> Slice<T, S, L> {
     /// # Panics
     ///
     /// Panics if the rank is not at least 2.
-    pub fn cols_mut(&mut self) -> LanesMut<T, S, L, Cols> {
+    pub fn cols_mut(&mut self) -> LanesMut<'_, T, S, L, Cols> {
         self.lanes_mut(Cols)
     }
 
@@ -185,7 +198,7 @@ impl<T, S: Shape, L: Layout> Slice<T, S, L> {
     ///
     /// Panics if the rank is not equal to 2, or if the absolute index is larger
     /// than the number of columns or rows.
-    pub fn diag(&self, index: isize) -> View<T, (Dyn,), Strided> {
+    pub fn diag(&self, index: isize) -> View<'_, T, (Dyn,), Strided> {
         let shape = self.shape().with_dims(<(S::Head, <S::Tail as Shape>::Head)>::from_dims);
 
         self.reshape(shape).into_diag(index)
@@ -198,7 +211,7 @@ impl<T, S: Shape, L: Layout> Slice<T, S, L> {
     ///
     /// Panics if the rank is not equal to 2, or if the absolute index is larger
     /// than the number of columns or rows.
-    pub fn diag_mut(&mut self, index: isize) -> ViewMut<T, (Dyn,), Strided> {
+    pub fn diag_mut(&mut self, index: isize) -> ViewMut<'_, T, (Dyn,), Strided> {
         let shape = self.shape().with_dims(<(S::Head, <S::Tail as Shape>::Head)>::from_dims);
 
         self.reshape_mut(shape).into_diag(index)
@@ -214,12 +227,12 @@ impl<T, S: Shape, L: Layout> Slice<T, S, L> {
     }
 
     /// Returns an expression over the array slice.
-    pub fn expr(&self) -> View<T, S, L> {
+    pub fn expr(&self) -> View<'_, T, S, L> {
         unsafe { View::new_unchecked(self.as_ptr(), self.mapping().clone()) }
     }
 
     /// Returns a mutable expression over the array slice.
-    pub fn expr_mut(&mut self) -> ViewMut<T, S, L> {
+    pub fn expr_mut(&mut self) -> ViewMut<'_, T, S, L> {
         unsafe { ViewMut::new_unchecked(self.as_mut_ptr(), self.mapping().clone()) }
     }
 
@@ -241,7 +254,7 @@ impl<T, S: Shape, L: Layout> Slice<T, S, L> {
     /// # Panics
     ///
     /// Panics if the array layout is not uniformly strided.
-    pub fn flatten(&self) -> View<T, (Dyn,), L> {
+    pub fn flatten(&self) -> View<'_, T, (Dyn,), L> {
         self.reshape([self.len()])
     }
 
@@ -250,7 +263,7 @@ impl<T, S: Shape, L: Layout> Slice<T, S, L> {
     /// # Panics
     ///
     /// Panics if the array layout is not uniformly strided.
-    pub fn flatten_mut(&mut self) -> ViewMut<T, (Dyn,), L> {
+    pub fn flatten_mut(&mut self) -> ViewMut<'_, T, (Dyn,), L> {
         self.reshape_mut([self.len()])
     }
 
@@ -302,7 +315,7 @@ impl<T, S: Shape, L: Layout> Slice<T, S, L> {
     /// # Panics
     ///
     /// Panics if the dimension is out of bounds.
-    pub fn lanes<A: Axis>(&self, axis: A) -> Lanes<T, S, L, A> {
+    pub fn lanes<A: Axis>(&self, axis: A) -> Lanes<'_, T, S, L, A> {
         Lanes::new(self, axis)
     }
 
@@ -316,7 +329,7 @@ impl<T, S: Shape, L: Layout> Slice<T, S, L> {
     /// # Panics
     ///
     /// Panics if the dimension is out of bounds.
-    pub fn lanes_mut<A: Axis>(&mut self, axis: A) -> LanesMut<T, S, L, A> {
+    pub fn lanes_mut<A: Axis>(&mut self, axis: A) -> LanesMut<'_, T, S, L, A> {
         LanesMut::new(self, axis)
     }
 
@@ -342,7 +355,7 @@ impl<T, S: Shape, L: Layout> Slice<T, S, L> {
     /// # Panics
     ///
     /// Panics if the rank is not at least 1.
-    pub fn outer_expr(&self) -> AxisExpr<T, S, L, Const<0>> {
+    pub fn outer_expr(&self) -> AxisExpr<'_, T, S, L, Const<0>> {
         self.axis_expr(Const::<0>)
     }
 
@@ -354,10 +367,38 @@ impl<T, S: Shape, L: Layout> Slice<T, S, L> {
     /// # Panics
     ///
     /// Panics if the rank is not at least 1.
-    pub fn outer_expr_mut(&mut self) -> AxisExprMut<T, S, L, Const<0>> {
+    pub fn outer_expr_mut(&mut self) -> AxisExprMut<'_, T, S, L, Const<0>> {
         self.axis_expr_mut(Const::<0>)
     }
 
+    /// Copies the array slice into a new array with padding along each dimension.
+    ///
+    /// The padding is given as the number of elements before and after for each dimension.
+    /// See also `expr::pad` for the lazy expression form.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mdarray::expr::PadMode;
+    /// use mdarray::view;
+    ///
+    /// let v = view![[1, 2], [3, 4]];
+    ///
+    /// assert_eq!(v.pad(&[(1, 0), (0, 1)], PadMode::Edge), view![[1, 2, 2], [1, 2, 2], [3, 4, 4]]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the padding length is not equal to the rank, if the resulting length
+    /// overflows, or if a dimension to be padded is empty and the mode is not constant.
+    #[cfg(feature = "alloc")]
+    pub fn pad(&self, padding: &[(usize, usize)], mode: PadMode<T>) -> Tensor<T, S::Dyn>
+    where
+        T: Clone,
+    {
+        FromExpression::from_expr(expr::pad(self, padding, mode))
+    }
+
     /// Returns an array view with the dimensions permuted.
     ///
     /// If the permutation is an identity permutation and known at compile time, the
@@ -371,8 +412,12 @@ impl<T, S: Shape, L: Layout> Slice<T, S, L> {
     pub fn permute<I: IntoShape<IntoShape: Permutation>>(
         &self,
         perm: I,
-    ) -> View<T, <I::IntoShape as Permutation>::Shape<S>, <I::IntoShape as Permutation>::Layout<L>>
-    {
+    ) -> View<
+        '_,
+        T,
+        <I::IntoShape as Permutation>::Shape<S>,
+        <I::IntoShape as Permutation>::Layout<L>,
+    > {
         let mapping = perm.into_dims(|dims| Mapping::permute(self.mapping(), dims));
 
         unsafe { View::new_unchecked(self.as_ptr(), mapping) }
@@ -391,8 +436,12 @@ impl<T, S: Shape, L: Layout> Slice<T, S, L> {
     pub fn permute_mut<I: IntoShape<IntoShape: Permutation>>(
         &mut self,
         perm: I,
-    ) -> ViewMut<T, <I::IntoShape as Permutation>::Shape<S>, <I::IntoShape as Permutation>::Layout<L>>
-    {
+    ) -> ViewMut<
+        '_,
+        T,
+        <I::IntoShape as Permutation>::Shape<S>,
+        <I::IntoShape as Permutation>::Layout<L>,
+    > {
         let mapping = perm.into_dims(|dims| Mapping::permute(self.mapping(), dims));
 
         unsafe { ViewMut::new_unchecked(self.as_mut_ptr(), mapping) }
@@ -408,7 +457,7 @@ impl<T, S: Shape, L: Layout> Slice<T, S, L> {
     /// # Panics
     ///
     /// Panics if the memory layout is not compatible with the new array layout.
-    pub fn remap<R: Shape, K: Layout>(&self) -> View<T, R, K> {
+    pub fn remap<R: Shape, K: Layout>(&self) -> View<'_, T, R, K> {
         let mapping = Mapping::remap(self.mapping());
 
         unsafe { View::new_unchecked(self.as_ptr(), mapping) }
@@ -419,21 +468,21 @@ impl<T, S: Shape, L: Layout> Slice<T, S, L> {
     /// # Panics
     ///
     /// Panics if the memory layout is not compatible with the new array layout.
-    pub fn remap_mut<R: Shape, K: Layout>(&mut self) -> ViewMut<T, R, K> {
+    pub fn remap_mut<R: Shape, K: Layout>(&mut self) -> ViewMut<'_, T, R, K> {
         let mapping = Mapping::remap(self.mapping());
 
         unsafe { ViewMut::new_unchecked(self.as_mut_ptr(), mapping) }
     }
 
     /// Returns a reordered array view of the array slice.
-    pub fn reorder(&self) -> View<T, S::Reverse, <S::Tail as Shape>::Layout<L>> {
+    pub fn reorder(&self) -> View<'_, T, S::Reverse, <S::Tail as Shape>::Layout<L>> {
         let mapping = Mapping::reorder(self.mapping());
 
         unsafe { View::new_unchecked(self.as_ptr(), mapping) }
     }
 
     /// Returns a mutable reordered array view of the array slice.
-    pub fn reorder_mut(&mut self) -> ViewMut<T, S::Reverse, <S::Tail as Shape>::Layout<L>> {
+    pub fn reorder_mut(&mut self) -> ViewMut<'_, T, S::Reverse, <S::Tail as Shape>::Layout<L>> {
         let mapping = Mapping::reorder(self.mapping());
 
         unsafe { ViewMut::new_unchecked(self.as_mut_ptr(), mapping) }
@@ -457,7 +506,7 @@ impl<T, S: Shape, L: Layout> Slice<T, S, L> {
     /// # Panics
     ///
     /// Panics if the array length is changed, or if the memory layout is not compatible.
-    pub fn reshape<I: IntoShape>(&self, shape: I) -> View<T, I::IntoShape, L> {
+    pub fn reshape<I: IntoShape>(&self, shape: I) -> View<'_, T, I::IntoShape, L> {
         let mapping = self.mapping().reshape(shape.into_shape());
 
         unsafe { View::new_unchecked(self.as_ptr(), mapping) }
@@ -473,7 +522,7 @@ impl<T, S: Shape, L: Layout> Slice<T, S, L> {
     /// # Panics
     ///
     /// Panics if the array length is changed, or if the memory layout is not compatible.
-    pub fn reshape_mut<I: IntoShape>(&mut self, shape: I) -> ViewMut<T, I::IntoShape, L> {
+    pub fn reshape_mut<I: IntoShape>(&mut self, shape: I) -> ViewMut<'_, T, I::IntoShape, L> {
         let mapping = self.mapping().reshape(shape.into_shape());
 
         unsafe { ViewMut::new_unchecked(self.as_mut_ptr(), mapping) }
@@ -484,7 +533,7 @@ impl<T, S: Shape, L: Layout> Slice<T, S, L> {
     /// # Panics
     ///
     /// Panics if the rank is not equal to 2, or if the index is out of bounds.
-    pub fn row(&self, index: usize) -> View<T, (<S::Tail as Shape>::Head,), L> {
+    pub fn row(&self, index: usize) -> View<'_, T, (<S::Tail as Shape>::Head,), L> {
         let shape = self.shape().with_dims(<(S::Head, _)>::from_dims);
 
         self.reshape(shape).into_view(index, ..)
@@ -495,7 +544,7 @@ impl<T, S: Shape, L: Layout> Slice<T, S, L> {
     /// # Panics
     ///
     /// Panics if the rank is not equal to 2, or if the index is out of bounds.
-    pub fn row_mut(&mut self, index: usize) -> ViewMut<T, (<S::Tail as Shape>::Head,), L> {
+    pub fn row_mut(&mut self, index: usize) -> ViewMut<'_, T, (<S::Tail as Shape>::Head,), L> {
         let shape = self.shape().with_dims(<(S::Head, _)>::from_dims);
 
         self.reshape_mut(shape).into_view(index, ..)
@@ -506,7 +555,7 @@ impl<T, S: Shape, L: Layout> Slice<T, S, L> {
     /// # Panics
     ///
     /// Panics if the rank is not at least 1.
-    pub fn rows(&self) -> Lanes<T, S, L, Rows> {
+    pub fn rows(&self) -> Lanes<'_, T, S, L, Rows> {
         self.lanes(Rows)
     }
 
@@ -515,7 +564,7 @@ impl<T, S: Shape, L: Layout> Slice<T, S, L> {
     /// # Panics
     ///
     /// Panics if the rank is not at least 1.
-    pub fn rows_mut(&mut self) -> LanesMut<T, S, L, Rows> {
+    pub fn rows_mut(&mut self) -> LanesMut<'_, T, S, L, Rows> {
         self.lanes_mut(Rows)
     }
 
@@ -533,7 +582,7 @@ impl<T, S: Shape, L: Layout> Slice<T, S, L> {
     pub fn split_at(
         &self,
         mid: usize,
-    ) -> (View<T, Resize<Const<0>, S>, L>, View<T, Resize<Const<0>, S>, L>) {
+    ) -> (View<'_, T, Resize<Const<0>, S>, L>, View<'_, T, Resize<Const<0>, S>, L>) {
         self.split_axis_at(Const::<0>, mid)
     }
 
@@ -546,7 +595,7 @@ impl<T, S: Shape, L: Layout> Slice<T, S, L> {
     pub fn split_at_mut(
         &mut self,
         mid: usize,
-    ) -> (ViewMut<T, Resize<Const<0>, S>, L>, ViewMut<T, Resize<Const<0>, S>, L>) {
+    ) -> (ViewMut<'_, T, Resize<Const<0>, S>, L>, ViewMut<'_, T, Resize<Const<0>, S>, L>) {
         self.split_axis_at_mut(Const::<0>, mid)
     }
 
@@ -564,7 +613,8 @@ impl<T, S: Shape, L: Layout> Slice<T, S, L> {
         &self,
         axis: A,
         mid: usize,
-    ) -> (View<T, Resize<A, S>, Split<A, S, L>>, View<T, Resize<A, S>, Split<A, S, L>>) {
+    ) -> (View<'_, T, Resize<A, S>, Split<A, S, L>>, View<'_, T, Resize<A, S>, Split<A, S, L>>)
+    {
         unsafe { View::split_axis_at(self.as_ptr(), self.mapping(), axis, mid) }
     }
 
@@ -582,7 +632,8 @@ impl<T, S: Shape, L: Layout> Slice<T, S, L> {
         &mut self,
         axis: A,
         mid: usize,
-    ) -> (ViewMut<T, Resize<A, S>, Split<A, S, L>>, ViewMut<T, Resize<A, S>, Split<A, S, L>>) {
+    ) -> (ViewMut<'_, T, Resize<A, S>, Split<A, S, L>>, ViewMut<'_, T, Resize<A, S>, Split<A, S, L>>)
+    {
         unsafe { ViewMut::split_axis_at(self.as_mut_ptr(), self.mapping(), axis, mid) }
     }
 
@@ -605,6 +656,7 @@ impl<T, S: Shape, L: Layout> Slice<T, S, L> {
     }
 
     /// Copies the array slice into a new array.
+    #[cfg(feature = "alloc")]
     pub fn to_tensor(&self) -> Tensor<T, S>
     where
         T: Clone,
@@ -613,7 +665,7 @@ impl<T, S: Shape, L: Layout> Slice<T, S, L> {
     }
 
     /// Copies the array slice into a new array with the specified allocator.
-    #[cfg(feature = "nightly")]
+    #[cfg(all(feature = "alloc", any(feature = "nightly", feature = "allocator-api2")))]
     pub fn to_tensor_in<A: Allocator>(&self, alloc: A) -> Tensor<T, S, A>
     where
         T: Clone,
@@ -622,20 +674,21 @@ impl<T, S: Shape, L: Layout> Slice<T, S, L> {
     }
 
     /// Copies the array slice into a new vector.
+    #[cfg(feature = "alloc")]
     pub fn to_vec(&self) -> Vec<T>
     where
         T: Clone,
     {
-        self.to_tensor().into_vec()
+        self.iter().cloned().collect()
     }
 
     /// Copies the array slice into a new vector with the specified allocator.
-    #[cfg(feature = "nightly")]
-    pub fn to_vec_in<A: Allocator>(&self, alloc: A) -> Vec<T, A>
+    #[cfg(all(feature = "alloc", any(feature = "nightly", feature = "allocator-api2")))]
+    pub fn to_vec_in<A: Allocator>(&self, alloc: A) -> allocator::Vec<T, A>
     where
         T: Clone,
     {
-        self.to_tensor_in(alloc).into_vec()
+        self.to_tensor_in(alloc).into_vec_in()
     }
 }
 
@@ -653,6 +706,67 @@ impl<T, S: Shape> Slice<T, S, Strided> {
     }
 }
 
+impl<T, X: Dim, Y: Dim, L: Layout> Slice<T, (X, Y), L> {
+    /// Copies the transpose of the matrix into the target matrix.
+    ///
+    /// The matrix is divided recursively into blocks, so that the elements are copied
+    /// with good cache efficiency for any layout and matrix size.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the target shape is not the transposed shape.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mdarray::tensor;
+    ///
+    /// let a = tensor![[1, 2, 3], [4, 5, 6]];
+    /// let mut b = tensor![[0; 2]; 3];
+    ///
+    /// a.transpose_into(&mut b);
+    ///
+    /// assert_eq!(b, tensor![[1, 4], [2, 5], [3, 6]]);
+    /// ```
+    pub fn transpose_into<M: Layout>(&self, target: &mut Slice<T, (Y, X), M>)
+    where
+        T: Clone,
+    {
+        assert!(target.dim(0) == self.dim(1) && target.dim(1) == self.dim(0), "shape mismatch");
+
+        self.transpose_block(target, 0..self.dim(0), 0..self.dim(1));
+    }
+
+    fn transpose_block<M: Layout>(
+        &self,
+        target: &mut Slice<T, (Y, X), M>,
+        rows: Range<usize>,
+        cols: Range<usize>,
+    ) where
+        T: Clone,
+    {
+        const BLOCK: usize = 16;
+
+        if rows.len() <= BLOCK && cols.len() <= BLOCK {
+            for i in rows {
+                for j in cols.clone() {
+                    target[[j, i]].clone_from(&self[[i, j]]);
+                }
+            }
+        } else if rows.len() >= cols.len() {
+            let mid = rows.start + rows.len() / 2;
+
+            self.transpose_block(target, rows.start..mid, cols.clone());
+            self.transpose_block(target, mid..rows.end, cols);
+        } else {
+            let mid = cols.start + cols.len() / 2;
+
+            self.transpose_block(target, rows.clone(), cols.start..mid);
+            self.transpose_block(target, rows, mid..cols.end);
+        }
+    }
+}
+
 macro_rules! impl_view {
     (($($xyz:tt),+), ($($abc:tt),+), ($($idx:tt),+)) => {
         impl<T, $($xyz: Dim,)+ L: Layout> Slice<T, ($($xyz,)+), L> {
@@ -677,6 +791,7 @@ macro_rules! impl_view {
             /// # Panics
             ///
             /// Panics if the subarray is out of bounds.
+            #[cfg(feature = "alloc")]
             pub fn tensor<$($abc: DimIndex),+>(
                 &self,
                 $($idx: $abc),+
@@ -695,7 +810,7 @@ macro_rules! impl_view {
             pub fn view<$($abc: DimIndex),+>(
                 &self,
                 $($idx: $abc),+
-            ) -> View<
+            ) -> View<'_,
                 T,
                 <($($abc,)+) as ViewIndex>::Shape<($($xyz,)+)>,
                 <($($abc,)+) as ViewIndex>::Layout<L>,
@@ -711,7 +826,7 @@ macro_rules! impl_view {
             pub fn view_mut<$($abc: DimIndex),+>(
                 &mut self,
                 $($idx: $abc),+,
-            ) -> ViewMut<
+            ) -> ViewMut<'_,
                 T,
                 <($($abc,)+) as ViewIndex>::Shape<($($xyz,)+)>,
                 <($($abc,)+) as ViewIndex>::Layout<L>,
@@ -883,6 +998,7 @@ impl<'a, T, S: Shape, L: Layout> IntoIterator for &'a mut Slice<T, S, L> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Clone, S: Shape> ToOwned for Slice<T, S> {
     type Owned = S::Owned<T>;
 
@@ -0,0 +1,179 @@
+use core::borrow::{Borrow, BorrowMut};
+use core::cell::UnsafeCell;
+use core::fmt::{self, Debug, Formatter};
+use core::hash::{Hash, Hasher};
+use core::mem::{self, ManuallyDrop, MaybeUninit};
+use core::ops::{Deref, DerefMut, Index, IndexMut};
+use core::ptr::{self, NonNull};
+
+use crate::allocator::Vec;
+use crate::dim::Const;
+use crate::expr::{self, Apply, Buffer, Expression, FromExpression, IntoExpr, IntoExpression};
+use crate::expr::{Iter, Map, Zip};
+use crate::index::SliceIndex;
+use crate::inline_tensor::impl_inline_tensor;
+use crate::layout::{Dense, Layout};
+use crate::mapping::{DenseMapping, Mapping};
+use crate::raw_slice::RawSlice;
+use crate::shape::{DynRank, IntoShape, Shape};
+use crate::slice::Slice;
+use crate::tensor::Tensor;
+use crate::traits::Owned;
+use crate::view::{View, ViewMut};
+
+/// Dense multidimensional array with inline storage for up to `N` elements.
+///
+/// The elements are stored inline if the array length is at most `N`, and otherwise
+/// in a heap allocation.
+pub struct SmallTensor<T, const N: usize, S: Shape = DynRank> {
+    slice: UnsafeCell<RawSlice<T, S, Dense>>,
+    capacity: usize,
+    buffer: MaybeUninit<[T; N]>,
+}
+
+impl<T, const N: usize, S: Shape> SmallTensor<T, N, S> {
+    /// Returns the number of elements the array can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        if self.is_inline() {
+            if mem::size_of::<T>() > 0 { N } else { usize::MAX }
+        } else {
+            self.capacity
+        }
+    }
+
+    /// Creates an array from the given element.
+    pub fn from_elem<I: IntoShape<IntoShape = S>>(shape: I, elem: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_expr(expr::from_elem(shape, elem))
+    }
+
+    /// Creates an array with the results from the given function.
+    pub fn from_fn<I: IntoShape<IntoShape = S>, F>(shape: I, f: F) -> Self
+    where
+        F: FnMut(&[usize]) -> T,
+    {
+        Self::from_expr(expr::from_fn(shape, f))
+    }
+
+    /// Converts the array into a reshaped array, which must have the same length.
+    ///
+    /// At most one dimension can have dynamic size `usize::MAX`, and is then inferred
+    /// from the other dimensions and the array length.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the array length is changed.
+    pub fn into_shape<I: IntoShape>(self, shape: I) -> SmallTensor<T, N, I::IntoShape> {
+        let mut me = ManuallyDrop::new(self);
+        let mapping = me.raw_slice_mut().mapping().reshape(shape.into_shape());
+
+        unsafe {
+            SmallTensor {
+                slice: UnsafeCell::new(RawSlice::new_unchecked(me.as_mut_ptr(), mapping)),
+                capacity: me.capacity,
+                buffer: ptr::read(&me.buffer),
+            }
+        }
+    }
+
+    /// Returns `true` if the elements are stored inline, or `false` if they are
+    /// stored in a heap allocation.
+    pub fn is_inline(&self) -> bool {
+        self.capacity == 0
+    }
+
+    /// Creates a new, empty array.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the default array length for the layout mapping is not zero.
+    pub fn new() -> Self {
+        assert!(S::default().checked_len() == Some(0), "default length not zero");
+
+        // The pointer is updated to the inline storage when dereferencing.
+        let ptr = NonNull::dangling().as_ptr();
+
+        unsafe {
+            Self {
+                slice: UnsafeCell::new(RawSlice::new_unchecked(ptr, DenseMapping::default())),
+                capacity: 0,
+                buffer: MaybeUninit::uninit(),
+            }
+        }
+    }
+
+    fn from_expr<E: Expression<Item = T, Shape = S>>(expr: E) -> Self {
+        let mapping = DenseMapping::new(expr.shape().clone());
+
+        if mem::size_of::<T>() > 0 && mapping.len() > N {
+            let mut vec = Vec::with_capacity(mapping.len());
+
+            expr.clone_into_vec(&mut vec);
+
+            let mut vec = ManuallyDrop::new(vec);
+
+            unsafe {
+                Self {
+                    slice: UnsafeCell::new(RawSlice::new_unchecked(vec.as_mut_ptr(), mapping)),
+                    capacity: vec.capacity(),
+                    buffer: MaybeUninit::uninit(),
+                }
+            }
+        } else {
+            let mut buffer = MaybeUninit::<[T; N]>::uninit();
+            let ptr = buffer.as_mut_ptr() as *mut T;
+
+            let mut len = 0;
+
+            // Elements are leaked in case of a panic.
+            expr.for_each(|x| unsafe {
+                ptr.add(len).write(x);
+                len += 1;
+            });
+
+            unsafe {
+                Self {
+                    slice: UnsafeCell::new(RawSlice::new_unchecked(ptr, mapping)),
+                    capacity: 0,
+                    buffer,
+                }
+            }
+        }
+    }
+
+    fn raw_slice(&self) -> &RawSlice<T, S, Dense> {
+        // Update the pointer to inline storage in case the array is moved.
+        if self.is_inline() {
+            unsafe {
+                RawSlice::update_ptr(&self.slice, self.buffer.as_ptr() as *mut T);
+            }
+        }
+
+        unsafe { &*self.slice.get() }
+    }
+
+    fn raw_slice_mut(&mut self) -> &mut RawSlice<T, S, Dense> {
+        let inline = self.is_inline();
+        let raw_slice = self.slice.get_mut();
+
+        if inline {
+            unsafe {
+                raw_slice.set_ptr(self.buffer.as_mut_ptr() as *mut T);
+            }
+        }
+
+        raw_slice
+    }
+
+    unsafe fn free(&mut self) {
+        if !self.is_inline() {
+            unsafe {
+                _ = Vec::from_raw_parts(self.raw_slice_mut().as_mut_ptr(), 0, self.capacity);
+            }
+        }
+    }
+}
+
+impl_inline_tensor!(SmallTensor);
@@ -0,0 +1,431 @@
+use core::borrow::{Borrow, BorrowMut};
+use core::cell::UnsafeCell;
+use core::fmt::{self, Debug, Formatter};
+use core::hash::{Hash, Hasher};
+use core::mem::{self, ManuallyDrop, MaybeUninit};
+use core::ops::{Deref, DerefMut, Index, IndexMut};
+use core::ptr::{self, NonNull};
+
+#[cfg(feature = "alloc")]
+use crate::dim::Const;
+use crate::dim::{Dim, Dims};
+use crate::error::TensorError;
+use crate::expr::{self, Apply, Buffer, Expression, FromExpression, IntoExpr, IntoExpression};
+use crate::expr::{Iter, Map, Zip};
+use crate::index::SliceIndex;
+use crate::inline_tensor::impl_inline_tensor;
+use crate::layout::{Dense, Layout};
+use crate::mapping::{DenseMapping, Mapping};
+use crate::raw_slice::RawSlice;
+use crate::shape::{DynRank, IntoShape, Shape};
+use crate::slice::Slice;
+#[cfg(feature = "alloc")]
+use crate::tensor::Tensor;
+use crate::traits::{IntoCloned, Owned};
+use crate::view::{View, ViewMut};
+
+/// Dense multidimensional array with inline storage and a fixed capacity of `N` elements.
+///
+/// The array shape can have dynamic dimensions or a dynamic rank, but the array length
+/// is limited by the capacity. Operations that change the array shape return an error
+/// if the capacity is exceeded.
+pub struct StackTensor<T, const N: usize, S: Shape = DynRank> {
+    slice: UnsafeCell<RawSlice<T, S, Dense>>,
+    buffer: MaybeUninit<[T; N]>,
+}
+
+impl<T, const N: usize, S: Shape> StackTensor<T, N, S> {
+    /// Returns the number of elements the array can hold.
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Clears the array, removing all values.
+    ///
+    /// If the array type has dynamic rank, the rank is set to 1.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the default array length for the layout mapping is not zero.
+    pub fn clear(&mut self) -> Result<(), TensorError> {
+        if S::default().len() != 0 {
+            return Err(TensorError::DefaultLength(S::default().len()));
+        }
+
+        let len = self.len();
+        let ptr = self.as_mut_ptr();
+
+        // Set the mapping before dropping elements, so that they are leaked in case of a panic.
+        unsafe {
+            *self.raw_slice_mut().mapping_mut() = DenseMapping::default();
+            ptr::slice_from_raw_parts_mut(ptr, len).drop_in_place();
+        }
+
+        Ok(())
+    }
+
+    /// Appends an expression to the array along the first dimension with broadcasting,
+    /// cloning elements if needed.
+    ///
+    /// If the array is empty, it is reshaped to match the shape of the expression.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the rank is not at least 1, if the first dimension is not
+    /// dynamically-sized, or if the new array length exceeds the capacity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the inner dimensions do not match, or if the rank is not the same.
+    pub fn expand<I: IntoExpression<Item: IntoCloned<T>>>(
+        &mut self,
+        expr: I,
+    ) -> Result<(), TensorError> {
+        if self.rank() == 0 {
+            return Err(TensorError::InvalidRank(self.rank()));
+        }
+
+        if let Some(n) = S::Head::SIZE {
+            return Err(TensorError::FirstDimNotDyn(n));
+        }
+
+        let expr = expr.into_expr();
+        let len = expr.len();
+
+        if len > 0 {
+            let new_mapping = expr.shape().with_dims(|src| {
+                if self.is_empty() {
+                    DenseMapping::new(Shape::from_dims(src))
+                } else {
+                    let mut shape = self.shape().clone();
+
+                    shape.with_mut_dims(|dims| {
+                        assert!(src.len() == dims.len(), "invalid rank");
+                        assert!(src[1..] == dims[1..], "inner dimensions mismatch");
+
+                        dims[0] += src[0];
+                    });
+
+                    DenseMapping::new(shape)
+                }
+            });
+
+            let new_len = self.len().saturating_add(len);
+
+            if new_len > N {
+                return Err(TensorError::CapacityExceeded(new_len));
+            }
+
+            let ptr = self.as_mut_ptr();
+            let mut index = self.len();
+
+            // Elements are leaked in case of a panic.
+            expr.for_each(|x| unsafe {
+                ptr.add(index).write(x.into_cloned());
+                index += 1;
+            });
+
+            unsafe {
+                *self.raw_slice_mut().mapping_mut() = new_mapping;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Creates an array from the given element.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the array length exceeds the capacity.
+    pub fn from_elem<I: IntoShape<IntoShape = S>>(shape: I, elem: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_expr(expr::from_elem(shape, elem))
+    }
+
+    /// Creates an array with the results from the given function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the array length exceeds the capacity.
+    pub fn from_fn<I: IntoShape<IntoShape = S>, F>(shape: I, f: F) -> Self
+    where
+        F: FnMut(&[usize]) -> T,
+    {
+        Self::from_expr(expr::from_fn(shape, f))
+    }
+
+    /// Converts the array into a reshaped array, which must have the same length.
+    ///
+    /// At most one dimension can have dynamic size `usize::MAX`, and is then inferred
+    /// from the other dimensions and the array length.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the array length is changed.
+    pub fn into_shape<I: IntoShape>(self, shape: I) -> StackTensor<T, N, I::IntoShape> {
+        let mut me = ManuallyDrop::new(self);
+        let mapping = me.raw_slice_mut().mapping().reshape(shape.into_shape());
+
+        unsafe {
+            StackTensor {
+                slice: UnsafeCell::new(RawSlice::new_unchecked(me.as_mut_ptr(), mapping)),
+                buffer: ptr::read(&me.buffer),
+            }
+        }
+    }
+
+    /// Returns `true` if the array length is equal to the capacity.
+    pub fn is_full(&self) -> bool {
+        self.len() == N
+    }
+
+    /// Creates a new, empty array.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the default array length for the layout mapping is not zero.
+    pub fn new() -> Self {
+        assert!(S::default().checked_len() == Some(0), "default length not zero");
+
+        // The pointer is updated to the inline storage when dereferencing.
+        let ptr = NonNull::dangling().as_ptr();
+
+        unsafe {
+            Self {
+                slice: UnsafeCell::new(RawSlice::new_unchecked(ptr, DenseMapping::default())),
+                buffer: MaybeUninit::uninit(),
+            }
+        }
+    }
+
+    /// Returns the number of elements that can be added before the capacity is reached.
+    pub fn remaining_capacity(&self) -> usize {
+        N - self.len()
+    }
+
+    /// Resizes the array to the new shape, creating new elements with the given value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the new array length exceeds the capacity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rank is not the same, or if a dimension that is not dynamically-sized
+    /// is changed.
+    pub fn resize(&mut self, new_dims: &[usize], value: T) -> Result<(), TensorError>
+    where
+        T: Clone,
+    {
+        self.resize_with(new_dims, || value.clone())
+    }
+
+    /// Resizes the array to the new shape, creating new elements from the given closure.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the new array length exceeds the capacity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rank is not the same, or if a dimension that is not dynamically-sized
+    /// is changed.
+    pub fn resize_with<F: FnMut() -> T>(
+        &mut self,
+        new_dims: &[usize],
+        mut f: F,
+    ) -> Result<(), TensorError> {
+        assert!(new_dims.len() == self.rank(), "invalid rank");
+
+        let mut new_shape = self.shape().clone();
+
+        new_shape.with_mut_dims(|dims| dims.copy_from_slice(new_dims));
+
+        let new_len = new_shape.checked_len().unwrap_or(usize::MAX);
+
+        if new_len > N {
+            return Err(TensorError::CapacityExceeded(new_len));
+        }
+
+        if new_shape != *self.shape() {
+            let old_shape = self.shape().clone();
+            let ptr = self.as_mut_ptr();
+
+            // Elements are leaked in case of a panic. Note that the default length is zero,
+            // since at least one dimension is dynamically-sized.
+            unsafe {
+                *self.raw_slice_mut().mapping_mut() = DenseMapping::default();
+
+                old_shape.with_dims(|old_dims| {
+                    let mut mid_dims = S::Dims::<usize>::new(old_dims.len());
+
+                    for (i, dim) in mid_dims.as_mut().iter_mut().enumerate() {
+                        *dim = old_dims[i].min(new_dims[i]);
+                    }
+
+                    let mid_dims = mid_dims.as_ref();
+
+                    shrink_dims(ptr, ptr, old_dims, mid_dims);
+                    move_dims(ptr, ptr, mid_dims, new_dims);
+                    fill_dims(ptr, mid_dims, new_dims, &mut f);
+                });
+
+                *self.raw_slice_mut().mapping_mut() = DenseMapping::new(new_shape);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Shortens the array along the first dimension, keeping the first `size` indices.
+    ///
+    /// If `size` is greater or equal to the current dimension size, this has no effect.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the rank is not at least 1, or if the first dimension
+    /// is not dynamically-sized.
+    pub fn truncate(&mut self, size: usize) -> Result<(), TensorError> {
+        if self.rank() == 0 {
+            return Err(TensorError::InvalidRank(self.rank()));
+        }
+
+        if let Some(n) = S::Head::SIZE {
+            return Err(TensorError::FirstDimNotDyn(n));
+        }
+
+        if size < self.dim(0) {
+            let old_len = self.len();
+            let ptr = self.as_mut_ptr();
+
+            // Set the mapping before dropping elements, so that they are leaked in case of a panic.
+            unsafe {
+                let mapping = self.raw_slice_mut().mapping_mut();
+
+                mapping.shape_mut().with_mut_dims(|dims| dims[0] = size);
+
+                let new_len = mapping.len();
+
+                ptr::slice_from_raw_parts_mut(ptr.add(new_len), old_len - new_len).drop_in_place();
+            }
+        }
+
+        Ok(())
+    }
+
+    fn from_expr<E: Expression<Item = T, Shape = S>>(expr: E) -> Self {
+        let mapping = DenseMapping::new(expr.shape().clone());
+
+        assert!(mapping.len() <= N, "capacity exceeded");
+
+        let mut buffer = MaybeUninit::<[T; N]>::uninit();
+        let ptr = buffer.as_mut_ptr() as *mut T;
+
+        let mut len = 0;
+
+        // Elements are leaked in case of a panic.
+        expr.for_each(|x| unsafe {
+            ptr.add(len).write(x);
+            len += 1;
+        });
+
+        unsafe { Self { slice: UnsafeCell::new(RawSlice::new_unchecked(ptr, mapping)), buffer } }
+    }
+
+    fn raw_slice(&self) -> &RawSlice<T, S, Dense> {
+        // Update the pointer to inline storage in case the array is moved.
+        unsafe {
+            RawSlice::update_ptr(&self.slice, self.buffer.as_ptr() as *mut T);
+
+            &*self.slice.get()
+        }
+    }
+
+    fn raw_slice_mut(&mut self) -> &mut RawSlice<T, S, Dense> {
+        let raw_slice = self.slice.get_mut();
+
+        unsafe {
+            raw_slice.set_ptr(self.buffer.as_mut_ptr() as *mut T);
+        }
+
+        raw_slice
+    }
+
+    // There is no heap allocation to free, since the elements are always stored inline.
+    unsafe fn free(&mut self) {}
+}
+
+impl_inline_tensor!(StackTensor);
+
+// Fill the new elements in the expanded dimensions, in order of the array layout.
+unsafe fn fill_dims<T, F: FnMut() -> T>(
+    ptr: *mut T,
+    old_dims: &[usize],
+    new_dims: &[usize],
+    f: &mut F,
+) {
+    let stride: usize = new_dims[1..].iter().product();
+
+    unsafe {
+        if new_dims.len() > 1 {
+            for i in 0..old_dims[0] {
+                fill_dims(ptr.add(i * stride), &old_dims[1..], &new_dims[1..], f);
+            }
+        }
+
+        let start = if new_dims.len() > 1 { old_dims[0] * stride } else { old_dims[0] };
+        let end = if new_dims.len() > 1 { new_dims[0] * stride } else { new_dims[0] };
+
+        for i in start..end {
+            ptr.add(i).write(f());
+        }
+    }
+}
+
+// Move elements to the expanded dimensions, in reverse order since the new positions
+// are never before the old positions.
+unsafe fn move_dims<T>(src: *mut T, dst: *mut T, old_dims: &[usize], new_dims: &[usize]) {
+    unsafe {
+        if new_dims.len() > 1 {
+            let old_stride: usize = old_dims[1..].iter().product();
+            let new_stride: usize = new_dims[1..].iter().product();
+
+            for i in (0..old_dims[0]).rev() {
+                let src = src.add(i * old_stride);
+                let dst = dst.add(i * new_stride);
+
+                move_dims(src, dst, &old_dims[1..], &new_dims[1..]);
+            }
+        } else {
+            ptr::copy(src, dst, old_dims[0]);
+        }
+    }
+}
+
+// Move elements to the shrunk dimensions and drop the removed elements, in order
+// since the new positions are never after the old positions.
+unsafe fn shrink_dims<T>(src: *mut T, dst: *mut T, old_dims: &[usize], new_dims: &[usize]) {
+    let old_stride: usize = old_dims[1..].iter().product();
+    let new_stride: usize = new_dims[1..].iter().product();
+
+    unsafe {
+        if old_dims.len() > 1 {
+            for i in 0..new_dims[0] {
+                let src = src.add(i * old_stride);
+                let dst = dst.add(i * new_stride);
+
+                shrink_dims(src, dst, &old_dims[1..], &new_dims[1..]);
+            }
+        } else {
+            ptr::copy(src, dst, new_dims[0]);
+        }
+
+        let start = new_dims[0] * old_stride;
+        let end = old_dims[0] * old_stride;
+
+        ptr::slice_from_raw_parts_mut(src.add(start), end - start).drop_in_place();
+    }
+}
@@ -1,22 +1,18 @@
-#[cfg(feature = "nightly")]
-use std::alloc::{Allocator, Global};
-use std::borrow::{Borrow, BorrowMut};
-use std::collections::TryReserveError;
-use std::fmt::{self, Debug, Formatter};
-use std::hash::{Hash, Hasher};
-use std::mem::{self, ManuallyDrop, MaybeUninit};
-use std::ops::{Deref, DerefMut, Index, IndexMut, RangeBounds};
-use std::{ptr, result, slice};
-
-use thiserror::Error;
-
-#[cfg(not(feature = "nightly"))]
-use crate::alloc::{Allocator, Global};
+use alloc::boxed::Box;
+use core::borrow::{Borrow, BorrowMut};
+use core::fmt::{self, Debug, Formatter};
+use core::hash::{Hash, Hasher};
+use core::mem::{self, ManuallyDrop, MaybeUninit};
+use core::ops::{Deref, DerefMut, Index, IndexMut, RangeBounds};
+use core::{ptr, slice};
+
+use crate::allocator::{Allocator, Global, TryReserveError, Vec};
 use crate::array::Array;
 use crate::dim::{Const, Dim, Dyn};
+use crate::error::TensorError;
 use crate::expr::{self, Drain, IntoExpr, Iter, Map, Zip};
 use crate::expr::{Apply, Expression, FromExpression, IntoExpression};
-use crate::index::SliceIndex;
+use crate::index::{Axis, SliceIndex, Split};
 use crate::layout::{Dense, Layout};
 use crate::mapping::{DenseMapping, Mapping};
 use crate::raw_tensor::RawTensor;
@@ -25,14 +21,14 @@ use crate::slice::Slice;
 use crate::traits::{IntoCloned, Owned};
 use crate::view::{View, ViewMut};
 
-#[cfg(not(feature = "nightly"))]
+#[cfg(not(any(feature = "nightly", feature = "allocator-api2")))]
 macro_rules! vec_t {
     ($type:ty, $alloc:ty) => {
         Vec<$type>
     };
 }
 
-#[cfg(feature = "nightly")]
+#[cfg(any(feature = "nightly", feature = "allocator-api2"))]
 macro_rules! vec_t {
     ($type:ty, $alloc:ty) => {
         Vec<$type, $alloc>
@@ -49,7 +45,7 @@ pub type DTensor<T, const N: usize, A = Global> = Tensor<T, Rank<N>, A>;
 
 impl<T, S: Shape, A: Allocator> Tensor<T, S, A> {
     /// Returns a reference to the underlying allocator.
-    #[cfg(feature = "nightly")]
+    #[cfg(any(feature = "nightly", feature = "allocator-api2"))]
     pub fn allocator(&self) -> &A {
         self.tensor.allocator()
     }
@@ -58,12 +54,16 @@ impl<T, S: Shape, A: Allocator> Tensor<T, S, A> {
     ///
     /// If the array is empty, it is reshaped to match the shape of the other array.
     ///
+    /// # Errors
+    ///
+    /// Returns an error if the rank is not at least 1, or if the first dimension
+    /// is not dynamically-sized.
+    ///
     /// # Panics
     ///
-    /// Panics if the inner dimensions do not match, if the rank is not the same and
-    /// at least 1, or if the first dimension is not dynamically-sized.
-    pub fn append(&mut self, other: &mut Self) -> result::Result<(), TensorError> {
-        self.expand(other.drain(..)?)?;
+    /// Panics if the inner dimensions do not match, or if the rank is not the same.
+    pub fn append(&mut self, other: &mut Self) -> Result<(), TensorError> {
+        self.expand(other.drain(..)?)
     }
 
     /// Returns the number of elements the array can hold without reallocating.
@@ -76,8 +76,11 @@ impl<T, S: Shape, A: Allocator> Tensor<T, S, A> {
     /// If the array type has dynamic rank, the rank is set to 1.
     ///
     /// Note that this method has no effect on the allocated capacity of the array.
-    pub fn clear(&mut self) -> result::Result<(), TensorError> {
-        // assert!(S::default().len() == 0, "default length not zero");
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the default array length for the layout mapping is not zero.
+    pub fn clear(&mut self) -> Result<(), TensorError> {
         if S::default().len() != 0 {
             return Err(TensorError::DefaultLength(S::default().len()));
         }
@@ -88,15 +91,25 @@ impl<T, S: Shape, A: Allocator> Tensor<T, S, A> {
                 *mapping = DenseMapping::default();
             });
         }
+
         Ok(())
     }
 
     /// Removes the specified range from the array along the first dimension,
     /// and returns the removed range as an expression.
-    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> result::Result<IntoExpr<Drain<T, S, A>>, TensorError> {
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the rank is not at least 1, or if the first dimension
+    /// is not dynamically-sized.
+    pub fn drain<R: RangeBounds<usize>>(
+        &mut self,
+        range: R,
+    ) -> Result<IntoExpr<Drain<'_, T, S, A>>, TensorError> {
         if self.rank() == 0 {
             return Err(TensorError::InvalidRank(self.rank()));
         }
+
         if let Some(n) = S::Head::SIZE {
             return Err(TensorError::FirstDimNotDyn(n));
         }
@@ -114,14 +127,22 @@ impl<T, S: Shape, A: Allocator> Tensor<T, S, A> {
     ///
     /// If the array is empty, it is reshaped to match the shape of the expression.
     ///
+    /// # Errors
+    ///
+    /// Returns an error if the rank is not at least 1, or if the first dimension
+    /// is not dynamically-sized.
+    ///
     /// # Panics
     ///
-    /// Panics if the inner dimensions do not match, if the rank is not the same and
-    /// at least 1, or if the first dimension is not dynamically-sized.
-    pub fn expand<I: IntoExpression<Item: IntoCloned<T>>>(&mut self, expr: I) -> result::Result<(), TensorError> {
+    /// Panics if the inner dimensions do not match, or if the rank is not the same.
+    pub fn expand<I: IntoExpression<Item: IntoCloned<T>>>(
+        &mut self,
+        expr: I,
+    ) -> Result<(), TensorError> {
         if self.rank() == 0 {
             return Err(TensorError::InvalidRank(self.rank()));
         }
+
         if let Some(n) = S::Head::SIZE {
             return Err(TensorError::FirstDimNotDyn(n));
         }
@@ -155,10 +176,12 @@ impl<T, S: Shape, A: Allocator> Tensor<T, S, A> {
                 });
             }
         }
+
+        Ok(())
     }
 
     /// Creates an array from the given element with the specified allocator.
-    #[cfg(feature = "nightly")]
+    #[cfg(any(feature = "nightly", feature = "allocator-api2"))]
     pub fn from_elem_in<I: IntoShape<IntoShape = S>>(shape: I, elem: T, alloc: A) -> Self
     where
         T: Clone,
@@ -167,7 +190,7 @@ impl<T, S: Shape, A: Allocator> Tensor<T, S, A> {
     }
 
     /// Creates an array with the results from the given function with the specified allocator.
-    #[cfg(feature = "nightly")]
+    #[cfg(any(feature = "nightly", feature = "allocator-api2"))]
     pub fn from_fn_in<I: IntoShape<IntoShape = S>, F>(shape: I, f: F, alloc: A) -> Self
     where
         F: FnMut(&[usize]) -> T,
@@ -180,7 +203,7 @@ impl<T, S: Shape, A: Allocator> Tensor<T, S, A> {
     /// # Safety
     ///
     /// The pointer must be a valid allocation given the mapping, capacity and allocator.
-    #[cfg(feature = "nightly")]
+    #[cfg(any(feature = "nightly", feature = "allocator-api2"))]
     pub unsafe fn from_raw_parts_in(
         ptr: *mut T,
         mapping: DenseMapping<S>,
@@ -192,6 +215,121 @@ impl<T, S: Shape, A: Allocator> Tensor<T, S, A> {
         }
     }
 
+    /// Inserts an expression into the array along the specified axis before the given
+    /// index, cloning elements if needed.
+    ///
+    /// Elements are moved within the existing allocation if the capacity is sufficient.
+    /// If the array is empty, it is reshaped to match the shape of the expression.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mdarray::{tensor, view};
+    ///
+    /// let mut t = tensor![[1, 2], [3, 4]];
+    ///
+    /// t.insert_axis_at(1, 1, &view![[5], [6]]).unwrap();
+    ///
+    /// assert_eq!(t, view![[1, 5, 2], [3, 6, 4]]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the rank is not at least 1, or if the dimension for the axis
+    /// is not dynamically-sized.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds, if the rank is not the same, or if
+    /// the other dimensions do not match.
+    pub fn insert_axis_at<B: Axis, I: IntoExpression<Item: IntoCloned<T>>>(
+        &mut self,
+        axis: B,
+        index: usize,
+        expr: I,
+    ) -> Result<(), TensorError> {
+        let k = self.dyn_axis(axis)?;
+        let expr = expr.into_expr();
+
+        assert!(index <= self.dim(k), "index out of bounds");
+
+        expr.shape().with_dims(|src| {
+            self.shape().with_dims(|dims| {
+                assert!(src.len() == dims.len(), "invalid rank");
+
+                if !self.is_empty() {
+                    assert!(src[..k] == dims[..k], "dimensions mismatch");
+                    assert!(src[k + 1..] == dims[k + 1..], "dimensions mismatch");
+                }
+            });
+        });
+
+        let len = expr.len();
+
+        if len > 0 {
+            unsafe {
+                self.tensor.with_mut_parts(|vec, mapping| {
+                    vec.reserve(len);
+
+                    if mapping.is_empty() {
+                        expr.shape().with_dims(|src| {
+                            mapping.shape_mut().with_mut_dims(|dims| dims.copy_from_slice(src));
+                        });
+
+                        expr.clone_into_vec(vec);
+                    } else {
+                        let (outer, size, inner): (usize, usize, usize) =
+                            mapping.shape().with_dims(|dims| {
+                                (
+                                    dims[..k].iter().product(),
+                                    dims[k],
+                                    dims[k + 1..].iter().product(),
+                                )
+                            });
+
+                        let count = len / (outer * inner);
+
+                        let old_len = vec.len();
+                        let ptr = vec.as_mut_ptr();
+
+                        // Leak all elements in case of a panic while cloning.
+                        vec.set_len(0);
+
+                        let head = index * inner;
+                        let gap = count * inner;
+
+                        // Move subarrays from the back to make room for the new elements.
+                        for i in (0..outer).rev() {
+                            let src = ptr.add(i * size * inner);
+                            let dst = ptr.add(i * (size + count) * inner);
+
+                            ptr::copy(src.add(head), dst.add(head + gap), (size - index) * inner);
+                            ptr::copy(src, dst, head);
+                        }
+
+                        let mut offset = head;
+                        let mut j = 0;
+
+                        expr.for_each(|x| {
+                            ptr.add(offset + j).write(x.into_cloned());
+                            j += 1;
+
+                            if j == gap {
+                                offset += (size + count) * inner;
+                                j = 0;
+                            }
+                        });
+
+                        mapping.shape_mut().with_mut_dims(|dims| dims[k] += count);
+                        vec.set_len(old_len + len);
+                    }
+                });
+            }
+        }
+
+        Ok(())
+    }
+
     /// Converts the array into an array with dynamic rank.
     pub fn into_dyn(self) -> Tensor<T, DynRank, A> {
         self.into_mapping()
@@ -199,7 +337,9 @@ impl<T, S: Shape, A: Allocator> Tensor<T, S, A> {
 
     /// Converts the array into a one-dimensional array.
     pub fn into_flat(self) -> Tensor<T, (Dyn,), A> {
-        self.into_vec().into()
+        let (vec, _) = self.tensor.into_parts();
+
+        vec.into()
     }
 
     /// Converts the array into a remapped array.
@@ -214,7 +354,7 @@ impl<T, S: Shape, A: Allocator> Tensor<T, S, A> {
     }
 
     /// Decomposes an array into its raw components including the allocator.
-    #[cfg(feature = "nightly")]
+    #[cfg(any(feature = "nightly", feature = "allocator-api2"))]
     pub fn into_raw_parts_with_alloc(self) -> (*mut T, DenseMapping<S>, usize, A) {
         let (vec, mapping) = self.tensor.into_parts();
         let (ptr, _, capacity, alloc) = vec.into_raw_parts_with_alloc();
@@ -230,7 +370,9 @@ impl<T, S: Shape, A: Allocator> Tensor<T, S, A> {
     pub fn into_scalar(self) -> T {
         assert!(self.len() == 1, "invalid length");
 
-        self.into_vec().pop().unwrap()
+        let (mut vec, _) = self.tensor.into_parts();
+
+        vec.pop().unwrap()
     }
 
     /// Converts the array into a reshaped array, which must have the same length.
@@ -257,8 +399,9 @@ impl<T, S: Shape, A: Allocator> Tensor<T, S, A> {
         unsafe { Tensor::from_parts(vec, mapping.reshape(shape.into_shape())) }
     }
 
-    /// Converts the array into a vector.
-    pub fn into_vec(self) -> vec_t!(T, A) {
+    /// Converts the array into a vector with the same allocator.
+    #[cfg(any(feature = "nightly", feature = "allocator-api2"))]
+    pub fn into_vec_in(self) -> Vec<T, A> {
         let (vec, _) = self.tensor.into_parts();
 
         vec
@@ -274,13 +417,55 @@ impl<T, S: Shape, A: Allocator> Tensor<T, S, A> {
     /// # Panics
     ///
     /// Panics if the default array length for the layout mapping is not zero.
-    #[cfg(feature = "nightly")]
+    #[cfg(any(feature = "nightly", feature = "allocator-api2"))]
     pub fn new_in(alloc: A) -> Self {
         assert!(S::default().checked_len() == Some(0), "default length not zero");
 
         unsafe { Self::from_parts(Vec::new_in(alloc), DenseMapping::default()) }
     }
 
+    /// Removes the specified range from the array along the specified axis.
+    ///
+    /// The remaining elements are moved within the existing allocation, and the capacity
+    /// is not changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mdarray::{tensor, view};
+    ///
+    /// let mut t = tensor![[1, 2, 3, 4], [5, 6, 7, 8]];
+    ///
+    /// t.remove_axis_range(1, 1..3).unwrap();
+    ///
+    /// assert_eq!(t, view![[1, 4], [5, 8]]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the rank is not at least 1, or if the dimension for the axis
+    /// is not dynamically-sized.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds.
+    pub fn remove_axis_range<B: Axis, R: RangeBounds<usize>>(
+        &mut self,
+        axis: B,
+        range: R,
+    ) -> Result<(), TensorError> {
+        let k = self.dyn_axis(axis)?;
+
+        #[cfg(not(feature = "nightly"))]
+        let range = crate::index::range(range, ..self.dim(k));
+        #[cfg(feature = "nightly")]
+        let range = slice::range(range, ..self.dim(k));
+
+        self.retain_dim(k, |i| !range.contains(&i));
+
+        Ok(())
+    }
+
     /// Reserves capacity for at least the additional number of elements in the array.
     pub fn reserve(&mut self, additional: usize) {
         unsafe {
@@ -312,6 +497,39 @@ impl<T, S: Shape, A: Allocator> Tensor<T, S, A> {
         self.tensor.resize_with(new_dims, f);
     }
 
+    /// Retains only the subarrays along the specified axis given by the predicate.
+    ///
+    /// The predicate is called once for each subarray in order. The remaining elements
+    /// are moved within the existing allocation, and the capacity is not changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mdarray::{tensor, view};
+    ///
+    /// let mut t = tensor![[1, 2], [3, 4], [5, 6]];
+    ///
+    /// t.retain_axis(0, |x| x[0] != 3).unwrap();
+    ///
+    /// assert_eq!(t, view![[1, 2], [5, 6]]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the rank is not at least 1, or if the dimension for the axis
+    /// is not dynamically-sized.
+    pub fn retain_axis<B: Axis, F>(&mut self, axis: B, mut f: F) -> Result<(), TensorError>
+    where
+        F: FnMut(View<'_, T, B::Remove<S>, Split<B, S, Dense>>) -> bool,
+    {
+        let k = self.dyn_axis(axis)?;
+        let retain: Vec<bool> = self.axis_expr(axis).into_iter().map(&mut f).collect();
+
+        self.retain_dim(k, |i| retain[i]);
+
+        Ok(())
+    }
+
     /// Forces the array layout mapping to the new mapping.
     ///
     /// # Safety
@@ -377,7 +595,7 @@ impl<T, S: Shape, A: Allocator> Tensor<T, S, A> {
     /// # Errors
     ///
     /// If the capacity overflows, or the allocator reports a failure, then an error is returned.
-    pub fn try_reserve(&mut self, additional: usize) -> result::Result<(), TryReserveError> {
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
         unsafe { self.tensor.with_mut_parts(|vec, _| vec.try_reserve(additional)) }
     }
 
@@ -386,23 +604,54 @@ impl<T, S: Shape, A: Allocator> Tensor<T, S, A> {
     /// # Errors
     ///
     /// If the capacity overflows, or the allocator reports a failure, then an error is returned.
-    pub fn try_reserve_exact(&mut self, additional: usize) -> result::Result<(), TryReserveError> {
+    pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), TryReserveError> {
         unsafe { self.tensor.with_mut_parts(|vec, _| vec.try_reserve_exact(additional)) }
     }
 
+    /// Creates an array with uninitialized elements with the specified allocator.
+    #[cfg(any(feature = "nightly", feature = "allocator-api2"))]
+    pub fn uninit_in<I: IntoShape<IntoShape = S>>(
+        shape: I,
+        alloc: A,
+    ) -> Tensor<MaybeUninit<T>, S, A> {
+        let shape = shape.into_shape();
+        let len = shape.checked_len().expect("invalid length");
+        let mut vec = Vec::with_capacity_in(len, alloc);
+
+        unsafe {
+            vec.set_len(len);
+
+            Tensor::from_parts(vec, DenseMapping::new(shape))
+        }
+    }
+
     /// Creates a new, empty array with the specified capacity and allocator.
     ///
     /// # Panics
     ///
     /// Panics if the default array length for the layout mapping is not zero.
-    #[cfg(feature = "nightly")]
+    #[cfg(any(feature = "nightly", feature = "allocator-api2"))]
     pub fn with_capacity_in(capacity: usize, alloc: A) -> Self {
         assert!(S::default().checked_len() == Some(0), "default length not zero");
 
         unsafe { Self::from_parts(Vec::with_capacity_in(capacity, alloc), DenseMapping::default()) }
     }
 
-    #[cfg(not(feature = "nightly"))]
+    fn dyn_axis<B: Axis>(&self, axis: B) -> Result<usize, TensorError> {
+        if self.rank() == 0 {
+            return Err(TensorError::InvalidRank(self.rank()));
+        }
+
+        let index = axis.index(self.rank());
+
+        if let Some(n) = S::static_dim(index) {
+            return Err(TensorError::DimNotDyn(n));
+        }
+
+        Ok(index)
+    }
+
+    #[cfg(not(any(feature = "nightly", feature = "allocator-api2")))]
     fn from_expr<E: Expression<Item = T, Shape = S>>(expr: E) -> Self {
         let shape = expr.shape().clone();
         let mut vec = Vec::with_capacity(shape.len());
@@ -412,7 +661,7 @@ impl<T, S: Shape, A: Allocator> Tensor<T, S, A> {
         unsafe { Self::from_parts(vec, DenseMapping::new(shape)) }
     }
 
-    #[cfg(feature = "nightly")]
+    #[cfg(any(feature = "nightly", feature = "allocator-api2"))]
     pub(crate) fn from_expr_in<E>(expr: E, alloc: A) -> Self
     where
         E: Expression<Item = T, Shape = S>,
@@ -429,6 +678,41 @@ impl<T, S: Shape, A: Allocator> Tensor<T, S, A> {
         unsafe { Self { tensor: RawTensor::from_parts(vec, mapping) } }
     }
 
+    fn retain_dim<F: FnMut(usize) -> bool>(&mut self, index: usize, mut f: F) {
+        let size = self.dim(index);
+        let retain = (0..size).filter(|&i| f(i)).count();
+
+        if retain < size {
+            unsafe {
+                self.tensor.with_mut_parts(|vec, mapping| {
+                    let (outer, inner): (usize, usize) = mapping.shape().with_dims(|dims| {
+                        (dims[..index].iter().product(), dims[index + 1..].iter().product())
+                    });
+
+                    let ptr = vec.as_mut_ptr();
+                    let mut dst = ptr;
+
+                    // Leak all elements in case of a panic while dropping.
+                    vec.set_len(0);
+
+                    for i in 0..outer * size {
+                        let src = ptr.add(i * inner);
+
+                        if f(i % size) {
+                            ptr::copy(src, dst, inner);
+                            dst = dst.add(inner);
+                        } else {
+                            ptr::slice_from_raw_parts_mut(src, inner).drop_in_place();
+                        }
+                    }
+
+                    mapping.shape_mut().with_mut_dims(|dims| dims[index] = retain);
+                    vec.set_len(mapping.len());
+                });
+            }
+        }
+    }
+
     fn zip_with<I: IntoExpression, F>(self, expr: I, mut f: F) -> Self
     where
         F: FnMut((T, I::Item)) -> T,
@@ -445,7 +729,7 @@ impl<T, S: Shape, A: Allocator> Tensor<T, S, A> {
 
                 // Drop all elements except the current one, which is read but not written back.
                 unsafe {
-                    let mut vec = ManuallyDrop::take(&mut self.tensor).into_vec();
+                    let (mut vec, _) = ManuallyDrop::take(&mut self.tensor).tensor.into_parts();
 
                     vec.set_len(0);
 
@@ -474,7 +758,7 @@ impl<T, S: Shape, A: Allocator> Tensor<T, S, A> {
     }
 }
 
-#[cfg(not(feature = "nightly"))]
+#[cfg(not(any(feature = "nightly", feature = "allocator-api2")))]
 impl<T, S: Shape> Tensor<T, S> {
     /// Creates an array from the given element.
     pub fn from_elem<I: IntoShape<IntoShape = S>>(shape: I, elem: T) -> Self
@@ -509,6 +793,13 @@ impl<T, S: Shape> Tensor<T, S> {
         (vec.as_mut_ptr(), mapping, vec.capacity())
     }
 
+    /// Converts the array into a vector.
+    pub fn into_vec(self) -> Vec<T> {
+        let (vec, _) = self.tensor.into_parts();
+
+        vec
+    }
+
     /// Creates a new, empty array.
     ///
     /// # Panics
@@ -520,6 +811,35 @@ impl<T, S: Shape> Tensor<T, S> {
         unsafe { Self::from_parts(Vec::new(), DenseMapping::default()) }
     }
 
+    /// Creates an array with uninitialized elements.
+    ///
+    /// The elements can be written using array views and expressions, and the array
+    /// is then converted with `assume_init` once all elements are initialized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mdarray::expr::{self, Expression};
+    /// use mdarray::{DTensor, view};
+    ///
+    /// let mut t = DTensor::<usize, 2>::uninit([2, 3]);
+    ///
+    /// expr::from_fn([2, 3], |i| i[0] + i[1]).eval_into_uninit(&mut t);
+    ///
+    /// assert_eq!(unsafe { t.assume_init() }, view![[0, 1, 2], [1, 2, 3]]);
+    /// ```
+    pub fn uninit<I: IntoShape<IntoShape = S>>(shape: I) -> Tensor<MaybeUninit<T>, S> {
+        let shape = shape.into_shape();
+        let len = shape.checked_len().expect("invalid length");
+        let mut vec = Vec::with_capacity(len);
+
+        unsafe {
+            vec.set_len(len);
+
+            Tensor::from_parts(vec, DenseMapping::new(shape))
+        }
+    }
+
     /// Creates a new, empty array with the specified capacity.
     ///
     /// # Panics
@@ -532,7 +852,7 @@ impl<T, S: Shape> Tensor<T, S> {
     }
 }
 
-#[cfg(feature = "nightly")]
+#[cfg(any(feature = "nightly", feature = "allocator-api2"))]
 impl<T, S: Shape> Tensor<T, S> {
     /// Creates an array from the given element.
     pub fn from_elem<I: IntoShape<IntoShape = S>>(shape: I, elem: T) -> Self
@@ -566,6 +886,14 @@ impl<T, S: Shape> Tensor<T, S> {
         (ptr, mapping, capacity)
     }
 
+    /// Converts the array into a vector.
+    pub fn into_vec(self) -> alloc::vec::Vec<T> {
+        let (ptr, mapping, capacity) = self.into_raw_parts();
+
+        // The global allocator is the same for the vector types.
+        unsafe { alloc::vec::Vec::from_raw_parts(ptr, mapping.len(), capacity) }
+    }
+
     /// Creates a new, empty array.
     ///
     /// # Panics
@@ -575,6 +903,27 @@ impl<T, S: Shape> Tensor<T, S> {
         Self::new_in(Global)
     }
 
+    /// Creates an array with uninitialized elements.
+    ///
+    /// The elements can be written using array views and expressions, and the array
+    /// is then converted with `assume_init` once all elements are initialized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mdarray::expr::{self, Expression};
+    /// use mdarray::{DTensor, view};
+    ///
+    /// let mut t = DTensor::<usize, 2>::uninit([2, 3]);
+    ///
+    /// expr::from_fn([2, 3], |i| i[0] + i[1]).eval_into_uninit(&mut t);
+    ///
+    /// assert_eq!(unsafe { t.assume_init() }, view![[0, 1, 2], [1, 2, 3]]);
+    /// ```
+    pub fn uninit<I: IntoShape<IntoShape = S>>(shape: I) -> Tensor<MaybeUninit<T>, S> {
+        Self::uninit_in(shape, Global)
+    }
+
     /// Creates a new, empty array with the specified capacity.
     ///
     /// # Panics
@@ -585,6 +934,70 @@ impl<T, S: Shape> Tensor<T, S> {
     }
 }
 
+impl<T, S: Shape, A: Allocator> Tensor<MaybeUninit<T>, S, A> {
+    /// Converts the array into an array with initialized elements.
+    ///
+    /// # Safety
+    ///
+    /// All elements within the array length must be initialized.
+    pub unsafe fn assume_init(self) -> Tensor<T, S, A> {
+        let (vec, mapping) = self.tensor.into_parts();
+
+        #[cfg(not(any(feature = "nightly", feature = "allocator-api2")))]
+        let vec = {
+            let mut vec = ManuallyDrop::new(vec);
+
+            unsafe { Vec::from_raw_parts(vec.as_mut_ptr().cast(), vec.len(), vec.capacity()) }
+        };
+        #[cfg(any(feature = "nightly", feature = "allocator-api2"))]
+        let vec = {
+            let (ptr, len, capacity, alloc) = vec.into_raw_parts_with_alloc();
+
+            unsafe { Vec::from_raw_parts_in(ptr.cast(), len, capacity, alloc) }
+        };
+
+        unsafe { Tensor::from_parts(vec, mapping) }
+    }
+}
+
+impl<T, A: Allocator> Tensor<T, (Dyn, Dyn), A> {
+    /// Transposes the matrix in place, without allocating a second array.
+    ///
+    /// Square matrices are transposed by swapping elements in blocks for cache
+    /// efficiency, and rectangular matrices by following the cycles of the element
+    /// permutation. The shape is updated to the transposed shape.
+    ///
+    /// For rectangular matrices, the elements already moved are tracked in a bitset
+    /// with one bit per element, which is allocated with the global allocator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mdarray::tensor;
+    ///
+    /// let mut t = tensor![[1, 2, 3], [4, 5, 6]];
+    ///
+    /// t.transpose_in_place();
+    ///
+    /// assert_eq!(t, tensor![[1, 4], [2, 5], [3, 6]]);
+    /// ```
+    pub fn transpose_in_place(&mut self) {
+        let (m, n) = *self.shape();
+
+        unsafe {
+            self.tensor.with_mut_parts(|vec, mapping| {
+                if m == n {
+                    transpose_square(vec, n);
+                } else {
+                    transpose_cycles(vec, m, n);
+                }
+
+                *mapping = DenseMapping::new((n, m));
+            });
+        }
+    }
+}
+
 impl<'a, T, U, S: Shape, A: Allocator> Apply<U> for &'a Tensor<T, S, A> {
     type Output<F: FnMut(&'a T) -> U> = Map<Self::IntoExpr, F>;
     type ZippedWith<I: IntoExpression, F: FnMut((&'a T, I::Item)) -> U> =
@@ -740,7 +1153,9 @@ impl<'a, T: 'a + Clone, S: Shape, L: Layout, I: IntoExpression<IntoExpr = View<'
 
 impl<T, D: Dim, A: Allocator> From<Tensor<T, (D,), A>> for vec_t!(T, A) {
     fn from(value: Tensor<T, (D,), A>) -> Self {
-        value.into_vec()
+        let (vec, _) = value.tensor.into_parts();
+
+        vec
     }
 }
 
@@ -752,6 +1167,24 @@ impl<T, A: Allocator> From<vec_t!(T, A)> for Tensor<T, (Dyn,), A> {
     }
 }
 
+#[cfg(all(feature = "allocator-api2", not(feature = "nightly")))]
+impl<T, D: Dim> From<Tensor<T, (D,)>> for alloc::vec::Vec<T> {
+    fn from(value: Tensor<T, (D,)>) -> Self {
+        value.into_vec()
+    }
+}
+
+#[cfg(all(feature = "allocator-api2", not(feature = "nightly")))]
+impl<T> From<alloc::vec::Vec<T>> for Tensor<T, (Dyn,)> {
+    fn from(value: alloc::vec::Vec<T>) -> Self {
+        let mut vec = ManuallyDrop::new(value);
+        let mapping = DenseMapping::new((vec.len(),));
+
+        // The global allocator is the same for the vector types.
+        unsafe { Self::from_raw_parts(vec.as_mut_ptr(), mapping, vec.capacity()) }
+    }
+}
+
 macro_rules! impl_from_array {
     (($($xyz:tt),+), ($($abc:tt),+), $array:tt) => {
         impl<T: Clone $(,$xyz: Dim + From<Const<$abc>>)+ $(,const $abc: usize)+> From<&$array>
@@ -785,12 +1218,12 @@ impl_from_array!((X, Y, Z, W, U), (A, B, C, D, E), [[[[[T; E]; D]; C]; B]; A]);
 impl_from_array!((X, Y, Z, W, U, V), (A, B, C, D, E, F), [[[[[[T; F]; E]; D]; C]; B]; A]);
 
 impl<T, S: Shape> FromExpression<T, S> for Tensor<T, S> {
-    #[cfg(not(feature = "nightly"))]
+    #[cfg(not(any(feature = "nightly", feature = "allocator-api2")))]
     fn from_expr<I: IntoExpression<Item = T, Shape = S>>(expr: I) -> Self {
         Self::from_expr(expr.into_expr())
     }
 
-    #[cfg(feature = "nightly")]
+    #[cfg(any(feature = "nightly", feature = "allocator-api2"))]
     fn from_expr<I: IntoExpression<Item = T, Shape = S>>(expr: I) -> Self {
         Self::from_expr_in(expr.into_expr(), Global)
     }
@@ -844,7 +1277,7 @@ impl<T, S: Shape, A: Allocator> IntoExpression for Tensor<T, S, A> {
     type Shape = S;
     type IntoExpr = IntoExpr<Tensor<ManuallyDrop<T>, S, A>>;
 
-    #[cfg(not(feature = "nightly"))]
+    #[cfg(not(any(feature = "nightly", feature = "allocator-api2")))]
     fn into_expr(self) -> Self::IntoExpr {
         let (vec, mapping) = self.tensor.into_parts();
 
@@ -857,7 +1290,7 @@ impl<T, S: Shape, A: Allocator> IntoExpression for Tensor<T, S, A> {
         IntoExpr::new(tensor)
     }
 
-    #[cfg(feature = "nightly")]
+    #[cfg(any(feature = "nightly", feature = "allocator-api2"))]
     fn into_expr(self) -> Self::IntoExpr {
         let (ptr, mapping, capacity, alloc) = self.into_raw_parts_with_alloc();
 
@@ -903,7 +1336,13 @@ impl<T, S: Shape> Owned<T, S> for Tensor<T, S> {
     {
         unsafe {
             self.tensor.with_mut_parts(|vec, mapping| {
-                slice[..].clone_into(vec);
+                vec.truncate(slice.len());
+
+                let (init, tail) = slice[..].split_at(vec.len());
+
+                vec.clone_from_slice(init);
+                vec.extend_from_slice(tail);
+
                 mapping.clone_from(slice.mapping());
             });
         }
@@ -915,7 +1354,7 @@ macro_rules! impl_try_from_array {
         impl<T $(,$xyz: Dim)+ $(,const $abc: usize)+> TryFrom<Tensor<T, ($($xyz,)+)>> for $array {
             type Error = Tensor<T, ($($xyz,)+)>;
 
-            fn try_from(value: Tensor<T, ($($xyz,)+)>) -> result::Result<Self, Self::Error> {
+            fn try_from(value: Tensor<T, ($($xyz,)+)>) -> Result<Self, Self::Error> {
                 if value.shape().with_dims(|dims| dims == &[$($abc),+]) {
                     let mut vec = value.into_vec();
 
@@ -939,14 +1378,45 @@ impl_try_from_array!((X, Y, Z, W), (A, B, C, D), [[[[T; D]; C]; B]; A]);
 impl_try_from_array!((X, Y, Z, W, U), (A, B, C, D, E), [[[[[T; E]; D]; C]; B]; A]);
 impl_try_from_array!((X, Y, Z, W, U, V), (A, B, C, D, E, F), [[[[[[T; F]; E]; D]; C]; B]; A]);
 
-#[derive(Debug, Error)]
-pub enum TensorError {
-    #[error("default length not zero.")]
-    DefaultLength(usize),
-    #[error("invalid rank.")]
-    InvalidRank(usize),
-    #[error("first dimension not dynamically sized.")]
-    FirstDimNotDyn(usize),
+// Transposes a rectangular matrix by following the cycles of the permutation, where
+// a bitset keeps track of the elements that are already moved.
+fn transpose_cycles<T>(slice: &mut [T], m: usize, n: usize) {
+    let len = slice.len();
+    let mut visited = alloc::vec![0u64; len.div_ceil(64)];
+
+    // The first and the last elements are fixed, so they are not visited.
+    for start in 1..len.saturating_sub(1) {
+        if visited[start / 64] & (1 << (start % 64)) != 0 {
+            continue;
+        }
+
+        let mut index = start;
+
+        loop {
+            // Move the element at the start index to its final position.
+            index = (index % n) * m + index / n;
+
+            if index == start {
+                break;
+            }
+
+            slice.swap(start, index);
+            visited[index / 64] |= 1 << (index % 64);
+        }
+    }
 }
 
-// pub type Result<T> = result::Result<T, TensorError>;
+// Transposes a square matrix by swapping elements in blocks for cache efficiency.
+fn transpose_square<T>(slice: &mut [T], n: usize) {
+    const BLOCK: usize = 32;
+
+    for ib in (0..n).step_by(BLOCK) {
+        for jb in (ib..n).step_by(BLOCK) {
+            for i in ib..(ib + BLOCK).min(n) {
+                for j in jb.max(i + 1)..(jb + BLOCK).min(n) {
+                    slice.swap(i * n + j, j * n + i);
+                }
+            }
+        }
+    }
+}
@@ -1,5 +1,6 @@
-use std::borrow::BorrowMut;
+use core::borrow::BorrowMut;
 
+#[cfg(feature = "alloc")]
 use crate::dim::Const;
 use crate::expr::{Apply, FromExpression};
 use crate::shape::Shape;
@@ -36,6 +37,7 @@ impl<T> IntoCloned<T> for T {
 
 /// Trait for a multidimensional array owning its contents.
 pub trait Owned<T, S: Shape>: Apply<T> + BorrowMut<Slice<T, S>> + FromExpression<T, S> {
+    #[cfg(feature = "alloc")]
     #[doc(hidden)]
     type WithConst<const N: usize>: Owned<T, S::Prepend<Const<N>>>;
 
@@ -1,10 +1,11 @@
-use std::borrow::{Borrow, BorrowMut};
-use std::fmt::{self, Debug, Formatter};
-use std::hash::{Hash, Hasher};
-use std::marker::PhantomData;
-use std::ops::{Deref, DerefMut, Index, IndexMut};
-use std::slice;
-
+use core::borrow::{Borrow, BorrowMut};
+use core::fmt::{self, Debug, Formatter};
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut, Index, IndexMut};
+use core::slice;
+
+use crate::accessor::{Accessor, Plain};
 use crate::dim::{Const, Dim, Dyn};
 use crate::expr::{Apply, Expression, IntoExpression, Iter, Map, Zip};
 use crate::index::{self, Axis, DimIndex, Permutation, Resize, SliceIndex, Split, ViewIndex};
@@ -15,14 +16,16 @@ use crate::shape::{DynRank, IntoShape, Rank, Shape};
 use crate::slice::Slice;
 
 /// Multidimensional array view.
-pub struct View<'a, T, S: Shape = DynRank, L: Layout = Dense> {
+pub struct View<'a, T, S: Shape = DynRank, L: Layout = Dense, A = Plain> {
     slice: RawSlice<T, S, L>,
+    accessor: A,
     phantom: PhantomData<&'a T>,
 }
 
 /// Mutable multidimensional array view.
-pub struct ViewMut<'a, T, S: Shape = DynRank, L: Layout = Dense> {
+pub struct ViewMut<'a, T, S: Shape = DynRank, L: Layout = Dense, A = Plain> {
     slice: RawSlice<T, S, L>,
+    accessor: A,
     phantom: PhantomData<&'a mut T>,
 }
 
@@ -33,7 +36,16 @@ pub type DView<'a, T, const N: usize, L = Dense> = View<'a, T, Rank<N>, L>;
 pub type DViewMut<'a, T, const N: usize, L = Dense> = ViewMut<'a, T, Rank<N>, L>;
 
 macro_rules! impl_view {
-    ($name:tt, $as_ptr:tt, $from_raw_parts:tt, $raw_mut:tt, {$($mut:tt)?}, $repeatable:tt) => {
+    (
+        $name:tt,
+        $as_ptr:tt,
+        $from_raw_parts:tt,
+        $raw_mut:tt,
+        {$($mut:tt)?},
+        $repeatable:tt,
+        $access:tt,
+        $reference:tt
+    ) => {
         impl<'a, T, S: Shape, L: Layout> $name<'a, T, S, L> {
             /// Converts the array view into a new array view indexing the first dimension.
             ///
@@ -242,7 +254,18 @@ macro_rules! impl_view {
             pub unsafe fn new_unchecked(ptr: *$raw_mut T, mapping: L::Mapping<S>) -> Self {
                 let slice = unsafe { RawSlice::new_unchecked(ptr as *mut T, mapping) };
 
-                Self { slice, phantom: PhantomData }
+                Self { slice, accessor: Plain, phantom: PhantomData }
+            }
+
+            /// Converts the array view into a new array view with the given accessor.
+            ///
+            /// # Safety
+            ///
+            /// The accessor must be valid for the elements. If the accessor gives write
+            /// access to elements in a shared array view, the elements must not be
+            /// accessed in other ways during the lifetime of the array view.
+            pub unsafe fn with_accessor<A: Accessor<T>>(self, accessor: A) -> $name<'a, T, S, L, A> {
+                $name { slice: self.slice, accessor, phantom: PhantomData }
             }
 
             pub(crate) unsafe fn axis_at<A: Axis>(
@@ -336,6 +359,13 @@ macro_rules! impl_view {
             }
         }
 
+        impl<T, S: Shape, L: Layout, A> $name<'_, T, S, L, A> {
+            /// Returns the accessor of the array view.
+            pub fn accessor(&self) -> &A {
+                &self.accessor
+            }
+        }
+
         impl<T, S: Shape, L: Layout> Deref for $name<'_, T, S, L> {
             type Target = Slice<T, S, L>;
 
@@ -344,33 +374,35 @@ macro_rules! impl_view {
             }
         }
 
-        impl<'a, T, S: Shape, L: Layout> Expression for $name<'a, T, S, L> {
+        impl<'a, T, S: Shape, L: Layout, A: Accessor<T>> Expression for $name<'a, T, S, L, A> {
             type Shape = S;
 
             const IS_REPEATABLE: bool = $repeatable;
 
             fn shape(&self) -> &S {
-                (**self).shape()
+                self.slice.mapping().shape()
             }
 
-            unsafe fn get_unchecked(&mut self, index: usize) -> &'a $($mut)? T {
+            unsafe fn get_unchecked(&mut self, index: usize) -> Self::Item {
                 let count = self.slice.mapping().inner_stride() * index as isize;
 
-                unsafe { &$($mut)? *self.slice.$as_ptr().offset(count) }
+                unsafe { self.accessor.$access(self.slice.$as_ptr().offset(count)) }
             }
 
             fn inner_rank(&self) -> usize {
+                let rank = self.slice.mapping().rank();
+
                 if L::IS_DENSE {
                     // For static rank 0, the inner stride is 0 so we allow inner rank >0.
-                    if S::RANK == Some(0) { usize::MAX } else { self.rank() }
+                    if S::RANK == Some(0) { usize::MAX } else { rank }
                 } else {
                     // For rank 0, the inner stride is always 0 so we can allow inner rank >0.
-                    if self.rank() > 0 { 1 } else { usize::MAX }
+                    if rank > 0 { 1 } else { usize::MAX }
                 }
             }
 
             unsafe fn reset_dim(&mut self, index: usize, count: usize) {
-                let count = -self.stride(index) * count as isize;
+                let count = -self.slice.mapping().stride(index) * count as isize;
                 let ptr = self.slice.as_mut_ptr();
 
                 unsafe {
@@ -382,7 +414,7 @@ macro_rules! impl_view {
                 let ptr = self.slice.as_mut_ptr();
 
                 unsafe {
-                    self.slice.set_ptr(ptr.offset(self.stride(index)));
+                    self.slice.set_ptr(ptr.offset(self.slice.mapping().stride(index)));
                 }
             }
         }
@@ -442,8 +474,8 @@ macro_rules! impl_view {
             }
         }
 
-        impl<'a, T, S: Shape, L: Layout> IntoIterator for $name<'a, T, S, L> {
-            type Item = &'a $($mut)? T;
+        impl<'a, T, S: Shape, L: Layout, A: Accessor<T>> IntoIterator for $name<'a, T, S, L, A> {
+            type Item = A::$reference<'a>;
             type IntoIter = Iter<Self>;
 
             fn into_iter(self) -> Iter<Self> {
@@ -453,8 +485,8 @@ macro_rules! impl_view {
     };
 }
 
-impl_view!(View, as_ptr, from_raw_parts, const, {}, true);
-impl_view!(ViewMut, as_mut_ptr, from_raw_parts_mut, mut, {mut}, false);
+impl_view!(View, as_ptr, from_raw_parts, const, {}, true, access, Reference);
+impl_view!(ViewMut, as_mut_ptr, from_raw_parts_mut, mut, {mut}, false, access_mut, ReferenceMut);
 
 macro_rules! impl_into_view {
     ($n:tt, ($($xyz:tt),+), ($($abc:tt),+), ($($idx:tt),+)) => {
@@ -547,17 +579,18 @@ impl<T, S: Shape, L: Layout> BorrowMut<Slice<T, S, L>> for ViewMut<'_, T, S, L>
     }
 }
 
-impl<T, S: Shape, L: Layout> Clone for View<'_, T, S, L> {
+impl<T, S: Shape, L: Layout, A: Clone> Clone for View<'_, T, S, L, A> {
     fn clone(&self) -> Self {
-        Self { slice: self.slice.clone(), phantom: PhantomData }
+        Self { slice: self.slice.clone(), accessor: self.accessor.clone(), phantom: PhantomData }
     }
 
     fn clone_from(&mut self, source: &Self) {
         self.slice.clone_from(&source.slice);
+        self.accessor.clone_from(&source.accessor);
     }
 }
 
-impl<T, S: Shape, L: Layout<Mapping<S>: Copy>> Copy for View<'_, T, S, L> {}
+impl<T, S: Shape, L: Layout<Mapping<S>: Copy>, A: Copy> Copy for View<'_, T, S, L, A> {}
 
 impl<T, S: Shape, L: Layout> DerefMut for ViewMut<'_, T, S, L> {
     fn deref_mut(&mut self) -> &mut Self::Target {
@@ -624,11 +657,11 @@ impl<'a, T, S: Shape, L: Layout> IntoIterator for &'a mut ViewMut<'_, T, S, L> {
     }
 }
 
-unsafe impl<T: Sync, S: Shape, L: Layout> Send for View<'_, T, S, L> {}
-unsafe impl<T: Sync, S: Shape, L: Layout> Sync for View<'_, T, S, L> {}
+unsafe impl<T: Sync, S: Shape, L: Layout, A: Send> Send for View<'_, T, S, L, A> {}
+unsafe impl<T: Sync, S: Shape, L: Layout, A: Sync> Sync for View<'_, T, S, L, A> {}
 
-unsafe impl<T: Send, S: Shape, L: Layout> Send for ViewMut<'_, T, S, L> {}
-unsafe impl<T: Sync, S: Shape, L: Layout> Sync for ViewMut<'_, T, S, L> {}
+unsafe impl<T: Send, S: Shape, L: Layout, A: Send> Send for ViewMut<'_, T, S, L, A> {}
+unsafe impl<T: Sync, S: Shape, L: Layout, A: Sync> Sync for ViewMut<'_, T, S, L, A> {}
 
 macro_rules! impl_try_from_array_ref {
     (($($xyz:tt),+), ($($abc:tt),+), $array:tt) => {
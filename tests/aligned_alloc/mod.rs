@@ -1,4 +1,8 @@
-use std::alloc::{AllocError, Allocator, Global, Layout};
+#[cfg(all(feature = "allocator-api2", not(feature = "nightly")))]
+use allocator_api2::alloc::{AllocError, Allocator, Global};
+use std::alloc::Layout;
+#[cfg(feature = "nightly")]
+use std::alloc::{AllocError, Allocator, Global};
 use std::cmp;
 use std::ptr::NonNull;
 
@@ -1,5 +1,9 @@
+#![allow(clippy::assign_op_pattern)]
 #![allow(clippy::comparison_chain)]
+#![allow(clippy::eq_op)]
 #![allow(clippy::needless_range_loop)]
+#![allow(clippy::nonminimal_bool)]
+#![allow(clippy::op_ref)]
 #![cfg_attr(feature = "nightly", feature(allocator_api))]
 #![cfg_attr(feature = "nightly", feature(extern_types))]
 #![cfg_attr(feature = "nightly", feature(hasher_prefixfree_extras))]
@@ -8,26 +12,57 @@
 #![warn(unreachable_pub)]
 #![warn(unused_results)]
 
-#[cfg(feature = "nightly")]
+#[cfg(any(feature = "nightly", feature = "allocator-api2"))]
 pub mod aligned_alloc;
 
 #[cfg(feature = "nightly")]
 use std::alloc::Global;
 use std::any;
+#[cfg(feature = "std")]
+use std::cell::Cell;
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
+use std::mem::MaybeUninit;
 use std::ops::RangeFull;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::thread;
 
+#[cfg(feature = "num-complex")]
+use num_complex::Complex;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 #[cfg(feature = "serde")]
-use serde_test::{Token, assert_tokens};
+use serde_test::{Token, assert_de_tokens, assert_de_tokens_error, assert_tokens};
 
-#[cfg(feature = "nightly")]
+#[cfg(any(feature = "nightly", feature = "allocator-api2"))]
 use aligned_alloc::AlignedAlloc;
-use mdarray::expr::{self, Apply, Expression, IntoExpression};
+#[cfg(all(feature = "allocator-api2", not(feature = "nightly")))]
+use allocator_api2::alloc::Global;
+use mdarray::accessor::{AtomicF32, Conj, Plain, Scaled};
+#[cfg(feature = "csv")]
+use mdarray::csv::{self, CsvError};
+#[cfg(feature = "dlpack")]
+use mdarray::dlpack::{DLDataType, DLDataTypeCode, DLPackError, DLTensorRef, ManagedTensor};
+use mdarray::expr::{self, Apply, Expression, FromExpression, IntoExpression, PadMode};
+#[cfg(feature = "ffi")]
+use mdarray::ffi::{self, DenseDescriptor, FfiError, Status, StridedDescriptor};
 use mdarray::index::{Axis, Cols, Rows};
-use mdarray::{Array, DTensor, DView, DViewMut, Tensor, View, ViewMut, array, tensor, view};
+#[cfg(feature = "std")]
+use mdarray::linalg::{
+    self, Diag, EinsumError, Level1, Level2, Level3, LinalgError, MatrixNorm, Norm, Reference,
+    Triangle, Triangular, TriangularMut,
+};
+#[cfg(feature = "matrix-market")]
+use mdarray::matrix_market::{self, Format, MatrixMarketError, Symmetry};
+#[cfg(feature = "safetensors")]
+use mdarray::safetensors::{Dtype, SafeTensors, SafeTensorsError, TensorInfo, Writer};
+use mdarray::{
+    Array, DSlice, DTensor, DView, DViewMut, SmallTensor, StackTensor, Tensor, TensorError, View,
+    ViewMut,
+};
 use mdarray::{Const, Dense, Dyn, DynRank, Layout, Rank, Shape, StepRange, Strided, step};
-use mdarray::{DenseMapping, IntoCloned, Mapping, StridedMapping};
+use mdarray::{DenseMapping, IntoCloned, Mapping, Owned, StridedMapping};
+use mdarray::{array, tensor, view};
 
 type U0 = Const<0>;
 type U1 = Const<1>;
@@ -78,12 +113,67 @@ fn sr() -> StepRange<RangeFull, isize> {
     step(.., 2)
 }
 
+#[test]
+fn test_accessor() {
+    let mut a = tensor![[1, 2, 3], [4, 5, 6]];
+
+    assert_eq!(a.scaled(2).eval(), tensor![[2, 4, 6], [8, 10, 12]]);
+    assert_eq!(a.view(.., 1).scaled(-1).eval(), tensor![-2, -5]);
+    assert_eq!(a.scaled(3).into_iter().sum::<i32>(), 63);
+    assert_eq!(*a.scaled(2).accessor().factor(), 2);
+    assert_eq!(a.expr().accessor(), &Plain);
+
+    let b = a.scaled(2);
+    let c = b;
+
+    assert_eq!(b.eval() + c, tensor![[4, 8, 12], [16, 20, 24]]);
+
+    let x = tensor![1.0, -2.0];
+
+    assert_eq!(x.conj().eval(), x);
+    assert_eq!(x.conj().accessor(), &Conj);
+    assert_eq!(x.scaled(0.5).accessor().factor(), Scaled::new(0.5).factor());
+
+    thread::scope(|s| {
+        let v = a.atomic();
+
+        for _ in 0..4 {
+            _ = s.spawn(move || {
+                v.into_iter().for_each(|x: &AtomicI32| _ = x.fetch_add(1, Ordering::Relaxed))
+            });
+        }
+    });
+
+    assert_eq!(a, tensor![[5, 6, 7], [8, 9, 10]]);
+
+    let mut y = Tensor::from_elem([3], 0.0f32);
+
+    thread::scope(|s| {
+        let v = y.atomic();
+
+        for i in 0..6 {
+            _ = s.spawn(move || {
+                _ = v.into_iter().nth(i % 3).unwrap().fetch_add(0.5, Ordering::Relaxed)
+            });
+        }
+    });
+
+    assert_eq!(y, tensor![1.0, 1.0, 1.0]);
+
+    let z = AtomicF32::new(1.5);
+
+    assert_eq!(z.fetch_add(1.0, Ordering::Relaxed), 1.5);
+    z.store(z.load(Ordering::Relaxed) * 2.0, Ordering::Relaxed);
+    assert_eq!(z.load(Ordering::Relaxed), 5.0);
+}
+
 #[test]
 fn test_base() {
     let mut a = DTensor::<usize, 3>::default();
-    #[cfg(not(feature = "nightly"))]
+    #[cfg(not(any(feature = "nightly", feature = "allocator-api2")))]
     let mut b = DTensor::<usize, 3>::with_capacity(60);
-    #[cfg(feature = "nightly")]
+    #[cfg(any(feature = "nightly", feature = "allocator-api2"))]
+    #[allow(clippy::clone_on_copy)]
     let mut b = DTensor::<usize, 3>::with_capacity_in(60, a.allocator().clone());
 
     a.resize(&[3, 4, 5], 0);
@@ -131,20 +221,20 @@ fn test_base() {
     assert_eq!(a, Tensor::from_fn([3, 4, 5], |i| 1000 + 100 * i[0] + 10 * i[1] + i[2]));
     assert_eq!(b, Tensor::from_fn([5, 4, 3], |i| 1000 + 100 * i[2] + 10 * i[1] + i[0]));
 
-    assert_eq!(a.view(2, .., ..), a.axis_expr(0).into_iter().skip(2).next().unwrap());
-    assert_eq!(b.tensor(2, .., ..), b.axis_expr_mut(U0).into_iter().skip(2).next().unwrap());
+    assert_eq!(a.view(2, .., ..), a.axis_expr(0).into_iter().nth(2).unwrap());
+    assert_eq!(b.tensor(2, .., ..), b.axis_expr_mut(U0).into_iter().nth(2).unwrap());
 
-    assert_eq!(b.view(.., 2, ..), b.axis_expr(U1).into_iter().skip(2).next().unwrap());
-    assert_eq!(a.tensor(.., 2, ..), a.axis_expr_mut(1).into_iter().skip(2).next().unwrap());
+    assert_eq!(b.view(.., 2, ..), b.axis_expr(U1).into_iter().nth(2).unwrap());
+    assert_eq!(a.tensor(.., 2, ..), a.axis_expr_mut(1).into_iter().nth(2).unwrap());
 
-    assert_eq!(a.view(.., .., 2), a.axis_expr(2).into_iter().skip(2).next().unwrap());
-    assert_eq!(b.tensor(.., .., 2), b.axis_expr_mut(U2).into_iter().skip(2).next().unwrap());
+    assert_eq!(a.view(.., .., 2), a.axis_expr(2).into_iter().nth(2).unwrap());
+    assert_eq!(b.tensor(.., .., 2), b.axis_expr_mut(U2).into_iter().nth(2).unwrap());
 
-    assert_eq!(a.view(2, .., ..), a.outer_expr().into_iter().skip(2).next().unwrap());
-    assert_eq!(b.tensor(2, .., ..), b.outer_expr_mut().into_iter().skip(2).next().unwrap());
+    assert_eq!(a.view(2, .., ..), a.outer_expr().into_iter().nth(2).unwrap());
+    assert_eq!(b.tensor(2, .., ..), b.outer_expr_mut().into_iter().nth(2).unwrap());
 
-    assert_eq!(a.contains(&1111), true);
-    assert_eq!(a.view(1, 1.., 1..).contains(&9999), false);
+    assert!(a.contains(&1111));
+    assert!(!a.view(1, 1.., 1..).contains(&9999));
 
     assert_eq!(a.view(1.., 2.., 3).into_diag(0), view![1123, 1233]);
     assert_eq!(a.view(2, 1.., ..).diag(0), view![1210, 1221, 1232]);
@@ -241,7 +331,7 @@ fn test_base() {
     assert_eq!(view![[1, 2, 3]].remap::<(U1, Dyn), Dense>(), view![[1, 2, 3]]);
     assert_eq!(tensor![[1, 2, 3]].remap_mut::<(Dyn, U3), Dense>(), view![[1, 2, 3]]);
 
-    r.clear();
+    r.clear().unwrap();
 
     assert!(r.is_empty());
     assert!(r.capacity() > 0);
@@ -263,16 +353,56 @@ fn test_base() {
 
     assert!(s.capacity() < 120 && t.capacity() < 120);
 
-    _ = t.try_reserve(usize::MAX).unwrap_err();
+    let err = t.try_reserve(usize::MAX).unwrap_err();
+
+    assert_eq!(err, t.try_reserve_exact(usize::MAX).unwrap_err());
     t.try_reserve_exact(60).unwrap();
 
-    s.append(&mut t.clone());
-    t.expand(&s.view(3.., .., ..));
+    s.append(&mut t.clone()).unwrap();
+    t.expand(&s.view(3.., .., ..)).unwrap();
+
+    let v: Vec<usize> = t.clone().into_vec();
+
+    assert_eq!(Tensor::from_iter(s.into_shape([120])).as_ref(), v);
+    #[cfg(any(feature = "nightly", feature = "allocator-api2"))]
+    assert_eq!(t.into_vec_in()[..], v[..]);
+
+    let mut u = Tensor::from_fn([2, 3, 4], |i| (100 * i[0] + 10 * i[1] + i[2]).to_string());
+
+    u.insert_axis_at(1, 3, Tensor::from_elem([2, 2, 4], "x".to_string())).unwrap();
+    u.insert_axis_at(Rows, 0, Tensor::from_elem([2, 5, 1], "y".to_string())).unwrap();
+
+    assert_eq!(u.shape(), &(2, 5, 5));
+    assert_eq!(u.view(1, 4, ..), view!["y", "x", "x", "x", "x"]);
+    assert_eq!(u.view(1, 2, ..), view!["y", "120", "121", "122", "123"]);
+
+    u.remove_axis_range(2, ..1).unwrap();
+    u.remove_axis_range(1, 3..).unwrap();
+    u.retain_axis(2, |x| x[[0, 0]] != "1").unwrap();
+
+    assert_eq!(
+        u,
+        view![
+            [["0", "2", "3"], ["10", "12", "13"], ["20", "22", "23"]],
+            [["100", "102", "103"], ["110", "112", "113"], ["120", "122", "123"]]
+        ]
+    );
+
+    let mut v = Tensor::<i32, (Dyn, U2)>::new();
+
+    v.insert_axis_at(0, 0, view![[1, 2], [3, 4]]).unwrap();
+    v.insert_axis_at(0, 1, view![[5, 6]]).unwrap();
+
+    assert_eq!(v, view![[1, 2], [5, 6], [3, 4]]);
+    assert!(matches!(v.remove_axis_range(1, ..), Err(TensorError::DimNotDyn(2))));
+    assert!(matches!(v.retain_axis(U1, |_| true), Err(TensorError::DimNotDyn(2))));
+
+    v.retain_axis(U0, |x| x[0] > 1).unwrap();
 
-    assert_eq!(Tensor::from_iter(s.into_shape([120])).as_ref(), t.into_vec());
+    assert_eq!(v, view![[5, 6], [3, 4]]);
 
     let mut d = DTensor::<_, 2>::from([[1, 2], [3, 4], [5, 6]]);
-    let mut e = d.drain(1..2).eval();
+    let mut e = d.drain(1..2).unwrap().eval();
 
     assert_eq!(d, Tensor::from(&array![[1, 2], [5, 6]]));
     assert_eq!(e, Tensor::<_, (U1, Dyn)>::from(&[[3, 4]]));
@@ -328,6 +458,31 @@ fn test_base() {
     assert_eq!(tensor![[1, 2, 3], [4, 5, 6]].reorder(), view![[1, 4], [2, 5], [3, 6]]);
     assert_eq!(tensor![[1, 2, 3]].reorder_mut(), view![[1, 2, 3]].into_reordered());
 
+    for (m, n) in [(0, 3), (1, 1), (1, 5), (7, 3), (40, 40), (33, 70), (100, 1)] {
+        let a = Tensor::from_fn((m, n), |i| (i[0] * n + i[1]).to_string());
+        let mut b = a.clone();
+        let mut c = Tensor::from_elem((n, m), String::new());
+        let mut d = Tensor::from_elem((2 * n, m), String::new());
+
+        b.transpose_in_place();
+        a.transpose_into(&mut c);
+        a.transpose_into(&mut d.view_mut(step(.., 2), ..));
+
+        assert_eq!(b, a.reorder());
+        assert_eq!(c, a.reorder());
+        assert_eq!(d.view(step(.., 2), ..), a.reorder());
+
+        b.transpose_in_place();
+
+        assert_eq!(b, a);
+    }
+
+    let mut e = DTensor::<i32, 2>::from([[0; 2]; 3]);
+
+    tensor![[1, 2], [3, 4], [5, 6]].view(.., ..).reorder().transpose_into(&mut e.view_mut(.., ..));
+
+    assert_eq!(e, view![[1, 2], [3, 4], [5, 6]]);
+
     let mut h1 = array![[1, 2, 3], [4, 5, 6]];
     let h2: &mut [[_; 3]; 2] = h1.as_mut();
     let h3: &mut Array<_, (_, _)> = h2.as_mut();
@@ -340,13 +495,246 @@ fn test_base() {
 
     assert!(*h6 == array![[1, 2, 3], [4, 5, 6]]);
 
-    #[cfg(feature = "nightly")]
+    let mut v = DTensor::<String, 2>::uninit([3, 2]);
+
+    v.view_mut(0, ..)
+        .assign(tensor![MaybeUninit::new("a".to_string()), MaybeUninit::new("b".to_string())]);
+    expr::fill("c".to_string()).eval_into_uninit(&mut v.view_mut(1.., ..1));
+
+    for (i, mut x) in v.lanes_mut(0).into_iter().skip(1).enumerate() {
+        x.view_mut(1..)
+            .expr_mut()
+            .zip(&view!["d", "e"])
+            .for_each(|(x, y)| _ = x.write(y.repeat(i + 1)));
+    }
+
+    assert_eq!(unsafe { v.assume_init() }, view![["a", "b"], ["c", "d"], ["c", "e"]]);
+
+    let mut w = Tensor::<usize, (Dyn, Const<2>)>::uninit((2, Const::<2>));
+
+    view![1, 2].expr().copied().eval_into_uninit(&mut w);
+
+    assert_eq!(unsafe { w.assume_init() }, view![[1, 2], [1, 2]]);
+
+    #[cfg(any(feature = "nightly", feature = "allocator-api2"))]
+    let x = DTensor::<u8, 1, AlignedAlloc<64>>::uninit_in([64], AlignedAlloc::new(Global));
+
+    #[cfg(any(feature = "nightly", feature = "allocator-api2"))]
+    assert_eq!(x.as_ptr() as usize % 64, 0);
+
+    #[cfg(any(feature = "nightly", feature = "allocator-api2"))]
     let u = DTensor::<u8, 1, AlignedAlloc<64>>::with_capacity_in(64, AlignedAlloc::new(Global));
 
-    #[cfg(feature = "nightly")]
+    #[cfg(any(feature = "nightly", feature = "allocator-api2"))]
     assert_eq!(u.as_ptr() as usize % 64, 0);
 }
 
+#[cfg(feature = "num-complex")]
+#[test]
+fn test_complex() {
+    let c = |re: f64, im: f64| Complex::new(re, im);
+
+    let mut a = tensor![
+        [c(1.0, 2.0), c(3.0, 4.0), c(5.0, 6.0)],
+        [c(7.0, 8.0), c(9.0, 10.0), c(11.0, 12.0)]
+    ];
+
+    assert_eq!(a.re(), tensor![[1.0, 3.0, 5.0], [7.0, 9.0, 11.0]]);
+    assert_eq!(a.im(), tensor![[2.0, 4.0, 6.0], [8.0, 10.0, 12.0]]);
+    assert_eq!(a.re().strides(), &[6, 2]);
+    assert_eq!(a.view(.., 1..).im(), tensor![[4.0, 6.0], [10.0, 12.0]]);
+    assert_eq!(a.col(2).re(), tensor![5.0, 11.0]);
+    assert_eq!(Tensor::<Complex<f64>, (Dyn,)>::new().im().shape(), &(0,));
+    assert!(a.view_mut(.., 3..).im_mut().is_empty());
+
+    a.view_mut(1, ..).re_mut().fill(0.0);
+    a.col_mut(0).im_mut().assign(tensor![-1.0, -2.0]);
+
+    assert_eq!(
+        a,
+        tensor![
+            [c(1.0, -1.0), c(3.0, 4.0), c(5.0, 6.0)],
+            [c(0.0, -2.0), c(0.0, 10.0), c(0.0, 12.0)]
+        ]
+    );
+
+    assert_eq!(a.norm_sqr().eval(), tensor![[2.0, 25.0, 61.0], [4.0, 100.0, 144.0]]);
+    assert_eq!(a.conj().eval(), a.expr().map(|x| x.conj()).eval());
+    assert_eq!(
+        a.conj_transpose().eval(),
+        tensor![
+            [c(1.0, 1.0), c(0.0, 2.0)],
+            [c(3.0, -4.0), c(0.0, -10.0)],
+            [c(5.0, -6.0), c(0.0, -12.0)]
+        ]
+    );
+
+    let b = a.clone();
+
+    a.conj_mut();
+
+    assert_eq!(a, b.conj().eval());
+    assert_eq!(
+        a.view(.., step(.., 2)).conj_transpose().eval(),
+        b.view(.., step(.., 2)).permute([1, 0])
+    );
+
+    #[cfg(feature = "std")]
+    {
+        let b = tensor![[c(1.0, 1.0), c(2.0, 0.0)], [c(0.0, -1.0), c(1.0, 2.0)]];
+        let bb =
+            Tensor::from_fn((2, 2), |i| (0..2).map(|k| b[[k, i[0]]].conj() * b[[k, i[1]]]).sum());
+
+        assert_eq!(linalg::matmul_hn(&b, &b), bb);
+        assert_eq!(linalg::matmul_nh(&b.conj_transpose().eval(), &b.conj_transpose().eval()), bb);
+        assert_eq!(linalg::matmul(&b.conj_transpose().eval(), &b), bb);
+        assert_eq!(bb.diag(0).im(), tensor![0.0, 0.0]);
+        assert_eq!(linalg::einsum("ji,jk", &[&b.conj().eval(), &b]).unwrap(), bb);
+    }
+}
+
+#[cfg(feature = "csv")]
+#[test]
+fn test_csv() {
+    let text = "# comment\na;b;c\n1; 2 ;\"3\"\r\n\n4;5;6\n";
+    let t =
+        csv::Reader::new().delimiter(';').skip_lines(2).read::<i32, _>(text.as_bytes()).unwrap();
+
+    assert_eq!(t, tensor![[1, 2, 3], [4, 5, 6]]);
+
+    let t = csv::read::<f64, _>("".as_bytes()).unwrap();
+
+    assert_eq!(t.shape(), &(0, 0));
+
+    let t = csv::read::<String, _>("\"a,\"\"b\"\"\",c\n".as_bytes()).unwrap();
+
+    assert_eq!(t, tensor![[String::from("a,\"b\""), String::from("c")]]);
+
+    let r = csv::read::<i32, _>("1,2\n3,x\n".as_bytes());
+
+    assert!(matches!(r, Err(CsvError::Parse { line: 2, column: 2, source: Some(_) })));
+
+    let r = csv::read::<i32, _>("1,2\n\n3\n".as_bytes());
+
+    assert!(matches!(r, Err(CsvError::RowLength { line: 3, len: 1, expected: 2 })));
+
+    let r = csv::read::<String, _>("\"a\"b\n".as_bytes());
+
+    assert!(matches!(r, Err(CsvError::Parse { line: 1, column: 1, source: None })));
+
+    let r = csv::read::<String, _>("a,b\nc,\"d\n".as_bytes());
+
+    assert!(matches!(r, Err(CsvError::Parse { line: 2, column: 2, source: None })));
+
+    let t = tensor![[1.5, 2.0, 3.0], [4.0, 5.0, 6.0]];
+    let mut buffer = Vec::new();
+
+    csv::write(&t.view(.., 1..), &mut buffer).unwrap();
+
+    assert_eq!(buffer, b"2,3\n5,6\n");
+
+    let t = tensor![["a", "b\tc"], ["\"d\"", "e"]];
+    let mut buffer = Vec::new();
+
+    csv::Writer::new().delimiter('\t').write(&t, &mut buffer).unwrap();
+
+    assert_eq!(buffer, b"a\t\"b\tc\"\n\"\"\"d\"\"\"\te\n");
+    assert_eq!(
+        csv::Reader::new().delimiter('\t').read::<String, _>(&buffer[..]).unwrap(),
+        t.expr().map(|x| x.to_string()).eval()
+    );
+
+    let t = tensor![["a\nb", "c"], ["d", "\"e\r\n\""]].expr().map(|x| x.to_string()).eval();
+    let mut buffer = Vec::new();
+
+    csv::write(&t, &mut buffer).unwrap();
+
+    assert_eq!(buffer, b"\"a\nb\",c\nd,\"\"\"e\r\n\"\"\"\n");
+    assert_eq!(csv::read::<String, _>(&buffer[..]).unwrap(), t);
+
+    let r = csv::read::<i32, _>("1,\"2\n\n\",3\n4,5,x\n".as_bytes());
+
+    assert!(matches!(r, Err(CsvError::Parse { line: 1, column: 2, source: Some(_) })));
+}
+
+#[cfg(feature = "dlpack")]
+#[test]
+fn test_dlpack() {
+    let mut t = Tensor::<i32, _>::from_fn([3, 4], |i| (10 * i[0] + i[1]) as i32);
+
+    let r = DLTensorRef::from(&*t);
+    let dl = r.as_dl_tensor();
+
+    assert_eq!(dl.ndim, 2);
+    assert_eq!(dl.dtype, DLDataType { code: DLDataTypeCode::INT, bits: 32, lanes: 1 });
+    assert_eq!(unsafe { std::slice::from_raw_parts(dl.shape, 2) }, [3, 4]);
+    assert_eq!(unsafe { std::slice::from_raw_parts(dl.strides, 2) }, [4, 1]);
+
+    let v = unsafe { dl.view::<i32, DynRank>().unwrap() };
+
+    assert_eq!(v, t);
+    assert!(matches!(unsafe { dl.view::<u32, DynRank>() }, Err(DLPackError::DataTypeMismatch(_))));
+    assert!(matches!(unsafe { dl.view::<i32, (Dyn,)>() }, Err(DLPackError::InvalidRank(2))));
+    assert!(matches!(unsafe { dl.view::<i32, (Dyn, U3)>() }, Err(DLPackError::InvalidShape)));
+
+    let s = t.view(1.., step(.., 2));
+    let r = DLTensorRef::from(&*s);
+    let dl = r.as_dl_tensor();
+
+    assert_eq!(unsafe { std::slice::from_raw_parts(dl.strides, 2) }, [4, 2]);
+    assert_eq!(unsafe { dl.view::<i32, (Dyn, U2)>().unwrap() }, s);
+
+    let mut compact = *dl;
+
+    compact.strides = std::ptr::null_mut();
+    compact.byte_offset = 4;
+
+    assert_eq!(unsafe { compact.view::<i32, (Dyn, Dyn)>().unwrap() }, view![[11, 12], [13, 20]]);
+
+    let mut s = t.view_mut(.., 1);
+    let r = DLTensorRef::from(&mut *s);
+
+    unsafe { r.as_dl_tensor().view_mut::<i32, (Dyn,)>().unwrap()[2] = 0 };
+
+    assert_eq!(t[[2, 1]], 0);
+
+    let mut m = ManagedTensor::from(t.clone());
+
+    m.view_mut::<i32, (Dyn, Dyn)>().unwrap()[[0, 0]] = 100;
+
+    assert_eq!(m.view::<i32, (Dyn, Dyn)>().unwrap()[[0, 0]], 100);
+    assert_eq!(m.view::<i32, (Dyn, Dyn)>().unwrap().view(1.., ..), t.view(1.., ..));
+
+    let mut n = ManagedTensor::from(t.clone());
+    let strides = unsafe { (*n.as_mut_ptr()).dl_tensor.strides };
+
+    unsafe { *strides.add(1) = 3 };
+
+    assert!(matches!(n.view_mut::<i32, (Dyn, Dyn)>(), Err(DLPackError::OverlappingElements)));
+
+    unsafe { (*strides, *strides.add(1)) = (1, 3) };
+
+    assert_eq!(
+        n.view_mut::<i32, (Dyn, Dyn)>().unwrap(),
+        view![[0, 3, 12, 0], [1, 10, 13, 22], [2, 11, 20, 23]]
+    );
+
+    unsafe { (*strides, *strides.add(1)) = (0, 1) };
+
+    assert_eq!(n.view::<i32, (Dyn, Dyn)>().unwrap().view(2, ..), t.view(0, ..));
+    assert!(matches!(n.view_mut::<i32, (Dyn, Dyn)>(), Err(DLPackError::OverlappingElements)));
+
+    let ptr = m.into_raw();
+    let m = unsafe { ManagedTensor::from_raw(ptr) };
+
+    assert_eq!(m.view::<i32, DynRank>().unwrap().shape(), &DynRank::from_dims(&[3, 4]));
+
+    let m = ManagedTensor::from(Tensor::<f64, ()>::from_elem((), 1.0));
+
+    assert_eq!(m.view::<f64, ()>().unwrap()[[]], 1.0);
+    assert_eq!(m.view::<f64, DynRank>().unwrap().rank(), 0);
+}
+
 #[test]
 fn test_expr() {
     let mut a = tensor![[1, 2, 3], [4, 5, 6]];
@@ -360,7 +748,7 @@ fn test_expr() {
     assert_eq!(format!("{:?}", a.cols()), "Lanes(0, [[1, 2, 3], [4, 5, 6]])");
     assert_eq!(format!("{:?}", a.rows_mut()), "LanesMut(1, [[1, 2, 3], [4, 5, 6]])");
 
-    assert_eq!(format!("{:?}", a.clone().drain(1..)), "IntoExpr([[4, 5, 6]])");
+    assert_eq!(format!("{:?}", a.clone().drain(1..).unwrap()), "IntoExpr([[4, 5, 6]])");
     assert_eq!(format!("{:?}", a.clone().into_expr()), "IntoExpr([[1, 2, 3], [4, 5, 6]])");
 
     assert_eq!(format!("{:?}", expr::fill(1)), "Fill(1)");
@@ -388,7 +776,7 @@ fn test_expr() {
 
     let mut c = tensor![[1, 2], [3, 4], [5, 6]];
 
-    c.expand(tensor![[7, 8], [9, 10]].into_expr());
+    c.expand(tensor![[7, 8], [9, 10]].into_expr()).unwrap();
     _ = view![[11, 12]].expr().cloned().eval_into(&mut c);
 
     assert_eq!(c, view![[1, 2], [3, 4], [5, 6], [7, 8], [9, 10], [11, 12]]);
@@ -413,8 +801,129 @@ fn test_expr() {
     assert_eq!(a.lanes(U0).eval(), view![view![1, 4], view![2, 5], view![3, 6]]);
     assert_eq!(a.lanes_mut(1).eval(), view![view![1, 2, 3], view![4, 5, 6]]);
 
+    let b = tensor![[[1, 2], [3, 4]], [[5, 6], [7, 8]]];
+
+    assert_eq!(b.lanes(2).map(|x| x[1]).eval(), view![[2, 4], [6, 8]]);
+    assert_eq!(b.lanes(U1).map(|x| x[0]).eval(), view![[1, 2], [5, 6]]);
+
     assert_eq!(a.rows().eval(), view![view![1, 2, 3], view![4, 5, 6]]);
     assert_eq!(a.rows_mut().eval(), view![view![1, 2, 3], view![4, 5, 6]]);
+
+    let p = a.pad(&[(1, 1), (2, 0)], PadMode::Constant(0));
+
+    assert_eq!(p, view![[0, 0, 0, 0, 0], [0, 0, 1, 2, 3], [0, 0, 4, 5, 6], [0, 0, 0, 0, 0]]);
+    assert_eq!(
+        a.pad(&[(0, 1), (1, 2)], PadMode::Edge),
+        view![[1, 1, 2, 3, 3, 3], [4, 4, 5, 6, 6, 6], [4, 4, 5, 6, 6, 6]]
+    );
+    assert_eq!(a.pad(&[(0, 1), (1, 2)], PadMode::Reflect)[[2, 0]], 2);
+    assert_eq!(
+        a.pad(&[(0, 1), (1, 2)], PadMode::Reflect).view(..2, ..),
+        view![[2, 1, 2, 3, 2, 1], [5, 4, 5, 6, 5, 4]]
+    );
+
+    let v = a.view(.., sr());
+    let q = expr::pad(&v, &[(0, 0), (1, 1)], PadMode::Wrap);
+
+    assert_eq!(format!("{:?}", q), "Pad([[1, 3], [4, 6]], (2, 4), Wrap)");
+    assert_eq!(q.map(|x| 2 * x).eval(), view![[6, 2, 6, 2], [12, 8, 12, 8]]);
+
+    assert_eq!(
+        view![1, 2].into_dyn().pad(&[(3, 3)], PadMode::Reflect),
+        view![2, 1, 2, 1, 2, 1, 2, 1]
+    );
+    assert_eq!(
+        view![[0; 0]; 2].pad(&[(0, 0), (1, 1)], PadMode::Constant(7)),
+        view![[7, 7], [7, 7]]
+    );
+    assert_eq!(Array::<_, ()>(5).pad(&[], PadMode::Edge).into_scalar(), 5);
+}
+
+#[cfg(feature = "ffi")]
+#[test]
+fn test_ffi() {
+    let mut t = Tensor::<i32, _>::from_fn([2, 3, 4], |i| (100 * i[0] + 10 * i[1] + i[2]) as i32);
+
+    let dense = DenseDescriptor::try_from(&*t).unwrap();
+
+    assert_eq!(dense.rank, 3);
+    assert_eq!(dense.extents, [2, 3, 4, 0, 0, 0]);
+    assert_eq!(dense.len(), 24);
+    assert_eq!(unsafe { dense.view::<i32, (Dyn, U3, Dyn)>() }, t);
+
+    let mut len = 0;
+    let mut offset = 0;
+    let mut contiguous = false;
+
+    assert_eq!(unsafe { ffi::mdarray_dense_len(&dense, &mut len) }, Status::Success);
+    assert_eq!(len, 24);
+
+    let status = unsafe { ffi::mdarray_dense_offset(&dense, [1, 2, 3].as_ptr(), &mut offset) };
+
+    assert!(status == Status::Success && offset == 23);
+
+    let strided = StridedDescriptor::try_from(dense).unwrap();
+
+    assert_eq!(strided.strides, [12, 4, 1, 0, 0, 0]);
+    assert_eq!(strided, StridedDescriptor::try_from(&*t).unwrap());
+
+    let status = unsafe { ffi::mdarray_strided_is_contiguous(&strided, &mut contiguous) };
+
+    assert!(status == Status::Success && contiguous);
+
+    let mut result = StridedDescriptor { data: std::ptr::null_mut(), ..strided };
+
+    assert_eq!(unsafe { ffi::mdarray_dense_to_strided(&dense, &mut result) }, Status::Success);
+    assert_eq!(result, strided);
+
+    let s = t.view(1, .., step(.., -2));
+    let strided = StridedDescriptor::try_from(&*s).unwrap();
+
+    assert_eq!(strided.rank, 2);
+    assert_eq!(strided.strides[..2], [4, -2]);
+    assert_eq!(unsafe { ffi::mdarray_strided_len(&strided, &mut len) }, Status::Success);
+    assert_eq!(len, 6);
+
+    let status = unsafe { ffi::mdarray_strided_offset(&strided, [2, 1].as_ptr(), &mut offset) };
+
+    assert!(status == Status::Success && offset == 6);
+
+    let status = unsafe { ffi::mdarray_strided_is_contiguous(&strided, &mut contiguous) };
+
+    assert!(status == Status::Success && !contiguous);
+    assert_eq!(unsafe { strided.view::<i32, DynRank>() }, s);
+
+    let mut s = t.view_mut(.., 0, ..);
+    let strided = StridedDescriptor::try_from(&mut *s).unwrap();
+
+    unsafe { strided.view_mut::<i32, (Dyn, Dyn)>()[[1, 2]] = 0 };
+
+    assert_eq!(t[[1, 0, 2]], 0);
+
+    let a = Array::<i32, ()>(5);
+    let dense = DenseDescriptor::try_from(&*a).unwrap();
+
+    assert_eq!(dense.len(), 1);
+    assert_eq!(unsafe { dense.view::<i32, ()>() }[[]], 5);
+
+    let t = Tensor::<i32, DynRank>::from_elem(&[1; 7][..], 0);
+
+    assert_eq!(StridedDescriptor::try_from(&*t), Err(FfiError::InvalidRank(7)));
+
+    let dense = DenseDescriptor { rank: 7, ..DenseDescriptor::try_from(&*a).unwrap() };
+
+    assert_eq!(unsafe { ffi::mdarray_dense_len(&dense, &mut len) }, Status::InvalidRank);
+    assert_eq!(unsafe { ffi::mdarray_dense_to_strided(&dense, &mut result) }, Status::InvalidRank);
+
+    let dense = DenseDescriptor { rank: 2, extents: [usize::MAX, 2, 0, 0, 0, 0], ..dense };
+
+    assert_eq!(unsafe { ffi::mdarray_dense_len(&dense, &mut len) }, Status::Overflow);
+    assert_eq!(unsafe { ffi::mdarray_dense_to_strided(&dense, &mut result) }, Status::Overflow);
+
+    let strided = StridedDescriptor { strides: [isize::MAX, 1, 0, 0, 0, 0], ..result };
+    let status = unsafe { ffi::mdarray_strided_offset(&strided, [2, 0].as_ptr(), &mut offset) };
+
+    assert!(status == Status::Overflow && len == 6 && offset == 6);
 }
 
 #[test]
@@ -437,111 +946,681 @@ fn test_index() {
     check_view::<Strided>();
 }
 
+#[cfg(feature = "std")]
 #[test]
-fn test_macros() {
-    let array1: Array<usize, _> = array![];
-    let array2: Array<usize, _> = array![[]];
-    let array3: Array<usize, _> = array![[[]]];
-    let array4: Array<usize, _> = array![[[[]]]];
-    let array5: Array<usize, _> = array![[[[[]]]]];
-    let array6: Array<usize, _> = array![[[[[[]]]]]];
+fn test_linalg() {
+    let mut a = tensor![[2.0, 4.0, 1.0], [4.0, 2.0, 2.0], [0.0, 1.0, 3.0]];
+    let p = linalg::lu(&mut a).unwrap();
 
-    let tensor1: Tensor<usize, _> = tensor![];
-    let tensor2: Tensor<usize, _> = tensor![[]];
-    let tensor3: Tensor<usize, _> = tensor![[[]]];
-    let tensor4: Tensor<usize, _> = tensor![[[[]]]];
-    let tensor5: Tensor<usize, _> = tensor![[[[[]]]]];
-    let tensor6: Tensor<usize, _> = tensor![[[[[[]]]]]];
+    assert_eq!(p, tensor![1, 1, 2]);
+    assert_eq!(a, tensor![[4.0, 2.0, 2.0], [0.5, 3.0, 0.0], [0.0, 1.0 / 3.0, 3.0]]);
 
-    let view1: View<usize, _> = view![];
-    let view2: View<usize, _> = view![[]];
-    let view3: View<usize, _> = view![[[]]];
-    let view4: View<usize, _> = view![[[[]]]];
-    let view5: View<usize, _> = view![[[[[]]]]];
-    let view6: View<usize, _> = view![[[[[[]]]]]];
+    let a = tensor![[2.0, 4.0, 1.0], [4.0, 2.0, 2.0], [0.0, 1.0, 3.0]];
 
-    assert_eq!(array1.shape(), &(U0,));
-    assert_eq!(array2.shape(), &(U1, U0,));
-    assert_eq!(array3.shape(), &(U1, U1, U0,));
-    assert_eq!(array4.shape(), &(U1, U1, U1, U0,));
-    assert_eq!(array5.shape(), &(U1, U1, U1, U1, U0,));
-    assert_eq!(array6.shape(), &(U1, U1, U1, U1, U1, U0,));
+    assert_eq!(linalg::det(&a), -36.0);
+    assert_eq!(linalg::det(&tensor![[1.0, 2.0], [2.0, 4.0]]), 0.0);
 
-    assert_eq!(tensor1.shape(), &(0,));
-    assert_eq!(tensor2.shape(), &(1, 0));
-    assert_eq!(tensor3.shape(), &(1, 1, 0));
-    assert_eq!(tensor4.shape(), &(1, 1, 1, 0));
-    assert_eq!(tensor5.shape(), &(1, 1, 1, 1, 0));
-    assert_eq!(tensor6.shape(), &(1, 1, 1, 1, 1, 0));
+    let b = tensor![[7.0, 1.0], [8.0, 2.0], [4.0, 3.0]];
+    let x = linalg::solve(&a, &b).unwrap();
 
-    assert_eq!(view1.shape(), &(0,));
-    assert_eq!(view2.shape(), &(1, 0));
-    assert_eq!(view3.shape(), &(1, 1, 0));
-    assert_eq!(view4.shape(), &(1, 1, 1, 0));
-    assert_eq!(view5.shape(), &(1, 1, 1, 1, 0));
-    assert_eq!(view6.shape(), &(1, 1, 1, 1, 1, 0));
+    let ax = Tensor::from_fn((3, 2), |i| (0..3).map(|k| a[[i[0], k]] * x[[k, i[1]]]).sum::<f64>());
 
-    assert_eq!(tensor![1, 2, 3], array![1, 2, 3]);
-    assert_eq!(tensor![[1, 2, 3], [4, 5, 6]], array![[1, 2, 3], [4, 5, 6]]);
-    assert_eq!(tensor![[[1, 2, 3], [4, 5, 6]]], array![[[1, 2, 3], [4, 5, 6]]]);
-    assert_eq!(tensor![[[[1, 2, 3], [4, 5, 6]]]], array![[[[1, 2, 3], [4, 5, 6]]]]);
-    assert_eq!(tensor![[[[[1, 2, 3], [4, 5, 6]]]]], array![[[[[1, 2, 3], [4, 5, 6]]]]]);
-    assert_eq!(tensor![[[[[[1, 2, 3], [4, 5, 6]]]]]], array![[[[[[1, 2, 3], [4, 5, 6]]]]]]);
+    assert!(ax.iter().zip(b.iter()).all(|(x, y)| (x - y).abs() < 1e-12));
 
-    assert_eq!(view![1, 2, 3], array![1, 2, 3]);
-    assert_eq!(view![[1, 2, 3], [4, 5, 6]], array![[1, 2, 3], [4, 5, 6]]);
-    assert_eq!(view![[[1, 2, 3], [4, 5, 6]]], array![[[1, 2, 3], [4, 5, 6]]]);
-    assert_eq!(view![[[[1, 2, 3], [4, 5, 6]]]], array![[[[1, 2, 3], [4, 5, 6]]]]);
-    assert_eq!(view![[[[[1, 2, 3], [4, 5, 6]]]]], array![[[[[1, 2, 3], [4, 5, 6]]]]]);
-    assert_eq!(view![[[[[[1, 2, 3], [4, 5, 6]]]]]], array![[[[[[1, 2, 3], [4, 5, 6]]]]]]);
+    let c = tensor![[4.0, 0.0, 7.0], [0.0, 0.0, 0.0], [1.0, 0.0, 2.0]];
+    let inv = linalg::inverse(&c.view(.., step(.., 2)).view(step(.., 2), ..)).unwrap();
 
-    assert_eq!(tensor![0; 1], array![0; 1]);
-    assert_eq!(tensor![[0; 1]; 2], array![[0; 1]; 2]);
-    assert_eq!(tensor![[[0; 1]; 2]; 3], array![[[0; 1]; 2]; 3]);
-    assert_eq!(tensor![[[[0; 1]; 2]; 3]; 4], array![[[[0; 1]; 2]; 3]; 4]);
-    assert_eq!(tensor![[[[[0; 1]; 2]; 3]; 4]; 5], array![[[[[0; 1]; 2]; 3]; 4]; 5]);
-    assert_eq!(tensor![[[[[[0; 1]; 2]; 3]; 4]; 5]; 6], array![[[[[[0; 1]; 2]; 3]; 4]; 5]; 6]);
+    assert_eq!(inv, tensor![[2.0, -7.0], [-1.0, 4.0]]);
 
-    assert_eq!(view![0; 1], array![0; 1]);
-    assert_eq!(view![[0; 1]; 2], array![[0; 1]; 2]);
-    assert_eq!(view![[[0; 1]; 2]; 3], array![[[0; 1]; 2]; 3]);
-    assert_eq!(view![[[[0; 1]; 2]; 3]; 4], array![[[[0; 1]; 2]; 3]; 4]);
-    assert_eq!(view![[[[[0; 1]; 2]; 3]; 4]; 5], array![[[[[0; 1]; 2]; 3]; 4]; 5]);
-    assert_eq!(view![[[[[[0; 1]; 2]; 3]; 4]; 5]; 6], array![[[[[[0; 1]; 2]; 3]; 4]; 5]; 6]);
-}
+    let err = linalg::solve(&tensor![[1.0, 2.0], [2.0, 4.0]], &tensor![[1.0], [1.0]]);
 
-#[test]
-fn test_mapping() {
-    let d = DenseMapping::new((U1, 2, U3));
-    let s = StridedMapping::new(DynRank::from_dims(&[1, 2, 3]), &[4, 5, 6]);
+    assert!(matches!(err, Err(LinalgError::Singular(1))));
 
-    assert_eq!(d.is_contiguous(), true);
-    assert_eq!(s.is_empty(), false);
-    assert_eq!(d.len(), 6);
-    assert_eq!(s.rank(), 3);
+    let mut a = tensor![[f64::NAN, 1.0], [1.0, 1.0]];
 
-    assert_eq!(d.dim(2), 3);
-    assert_eq!(s.dims(), [1, 2, 3]);
-    assert_eq!(d.stride(0), 6);
-    assert_eq!(s.strides(), [4, 5, 6]);
+    assert!(linalg::det(&a).is_nan());
+    assert!(matches!(linalg::lu(&mut a), Err(LinalgError::Singular(0))));
 
-    let x = format!("{:?}", d);
-    let y = format!("{:?}", s);
+    let a = Tensor::from_fn((70, 300), |i| ((i[0] * 7 + i[1] * 3) % 11) as f64 - 5.0);
+    let b = Tensor::from_fn((300, 5), |i| ((i[0] + i[1] * 5) % 7) as f64 - 3.0);
+    let c =
+        Tensor::from_fn((70, 5), |i| (0..300).map(|k| a[[i[0], k]] * b[[k, i[1]]]).sum::<f64>());
 
-    assert_eq!(x, "DenseMapping { shape: (Const(1), 2, Const(3)) }");
-    assert_eq!(y, "StridedMapping { shape: DynRank([1, 2, 3]), strides: [4, 5, 6] }");
-}
+    assert_eq!(linalg::matmul(&a, &b), c);
+    assert_eq!(linalg::matmul(&b.permute([1, 0]), &a.permute([1, 0])), c.permute([1, 0]));
 
-#[test]
-fn test_ops() {
-    let mut a = DTensor::<i32, 2>::from([[1, 2, 3], [4, 5, 6]]);
-    let b = DTensor::<i32, 2>::from([[9, 8, 7], [6, 5, 4]]);
+    let x = tensor![1.0, 2.0, 3.0];
+    let mut y = tensor![1.0, 1.0];
 
-    a -= expr::fill(1);
-    a -= &b;
-    a -= b.expr();
+    Reference.gemv(
+        2.0,
+        &tensor![[1.0, 0.0, 1.0], [0.0, 1.0, 0.0]].remap(),
+        &x.remap(),
+        3.0,
+        &mut y.remap_mut(),
+    );
 
-    *a -= expr::fill(1);
+    assert_eq!(y, tensor![11.0, 7.0]);
+
+    Reference.axpy(-1.0, &x.view(1..).remap(), &mut y.remap_mut());
+
+    assert_eq!(y, tensor![9.0, 4.0]);
+    assert_eq!(Reference.dot(&x.view(step(.., 2)).remap(), &y.remap()), 21.0);
+
+    struct Counter(Cell<usize>);
+
+    impl Level1<f64> for Counter {
+        fn axpy(&self, alpha: f64, x: &DSlice<f64, 1, Strided>, y: &mut DSlice<f64, 1, Strided>) {
+            Reference.axpy(alpha, x, y);
+        }
+
+        fn dot(&self, x: &DSlice<f64, 1, Strided>, y: &DSlice<f64, 1, Strided>) -> f64 {
+            Reference.dot(x, y)
+        }
+    }
+
+    impl Level2<f64> for Counter {
+        fn gemv(
+            &self,
+            alpha: f64,
+            a: &DSlice<f64, 2, Strided>,
+            x: &DSlice<f64, 1, Strided>,
+            beta: f64,
+            y: &mut DSlice<f64, 1, Strided>,
+        ) {
+            Reference.gemv(alpha, a, x, beta, y);
+        }
+    }
+
+    impl Level3<f64> for Counter {
+        fn gemm(
+            &self,
+            alpha: f64,
+            a: &DSlice<f64, 2, Strided>,
+            b: &DSlice<f64, 2, Strided>,
+            beta: f64,
+            c: &mut DSlice<f64, 2, Strided>,
+        ) {
+            self.0.set(self.0.get() + 1);
+            Reference.gemm(alpha, a, b, beta, c);
+        }
+    }
+
+    let counter = Counter(Cell::new(0));
+
+    assert_eq!(linalg::matmul_with(&counter, &a, &b), c);
+    assert_eq!(counter.0.get(), 1);
+
+    assert_eq!(linalg::matmul_hn_with(&counter, &b, &b), linalg::matmul(&b.permute([1, 0]), &b));
+    assert_eq!(linalg::matmul_nh_with(&counter, &a, &a), linalg::matmul(&a, &a.permute([1, 0])));
+    assert_eq!(counter.0.get(), 3);
+
+    let t = tensor![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]];
+    let a = t.clone().into_dyn();
+    let v = t.view(.., 1..).into_dyn();
+
+    assert_eq!(
+        linalg::einsum("ij->ji", &[&v]).unwrap(),
+        tensor![[2.0, 5.0, 8.0], [3.0, 6.0, 9.0]].into_dyn()
+    );
+    assert_eq!(linalg::einsum("ii->i", &[&a]).unwrap(), tensor![1.0, 5.0, 9.0].into_dyn());
+    assert_eq!(linalg::einsum("ij->", &[&a]).unwrap()[[]], 45.0);
+    assert_eq!(
+        linalg::einsum("ba", &[&v]).unwrap(),
+        tensor![[2.0, 5.0, 8.0], [3.0, 6.0, 9.0]].into_dyn()
+    );
+
+    let x = tensor![1.0, 2.0].into_dyn();
+    let y = tensor![3.0, 4.0, 5.0].into_dyn();
+
+    assert_eq!(
+        linalg::einsum("i,j", &[&x, &y]).unwrap(),
+        tensor![[3.0, 4.0, 5.0], [6.0, 8.0, 10.0]].into_dyn()
+    );
+
+    let b = Tensor::from_fn((2, 3, 2), |i| (i[0] * 6 + i[1] * 2 + i[2]) as f64);
+    let c = Tensor::from_fn((1, 2, 3), |i| (i[1] * 3 + i[2]) as f64);
+    let d = linalg::einsum("...ij,...jk->...ik", &[&b, &c]).unwrap();
+    let d = d.reshape([2, 3, 3]);
+
+    for k in 0..2 {
+        assert_eq!(d.view(k, .., ..), linalg::matmul(&b.view(k, .., ..), &c.view(0, .., ..)));
+    }
+
+    let e = linalg::einsum("...ij,jk", &[&b.expr().into_dyn(), &c.view(0, .., ..).into_dyn()]);
+
+    assert_eq!(e.unwrap(), d.to_tensor().into_dyn());
+    assert_eq!(linalg::einsum("...i,...i->...", &[&b, &b]).unwrap().dims(), &[2, 3]);
+
+    let z = Tensor::<f64, (Dyn,)>::from_elem(0, 0.0);
+    let o = Tensor::<f64, (Dyn,)>::from_elem(1, 1.0);
+
+    assert_eq!(linalg::einsum("...,...->...", &[&z, &o]).unwrap().dims(), &[0]);
+    assert_eq!(linalg::einsum("...,...->...", &[&o, &z]).unwrap().dims(), &[0]);
+
+    let e = Tensor::from_fn((4, 2), |i| (i[0] + i[1]) as f64).into_dyn();
+    let f = Tensor::from_fn((2, 5), |i| (i[0] * i[1]) as f64 - 1.0).into_dyn();
+    let g = Tensor::from_fn((5, 3), |i| (i[0] + 2 * i[1]) as f64).into_dyn();
+
+    let efg =
+        linalg::matmul(&linalg::matmul(&e.reshape([4, 2]), &f.reshape([2, 5])), &g.reshape([5, 3]));
+
+    assert_eq!(linalg::einsum("ij,jk,kl->il", &[&e, &f, &g]).unwrap(), efg.clone().into_dyn());
+    assert_eq!(linalg::einsum("ij,jk,kl->", &[&e, &f, &g]).unwrap()[[]], efg.iter().sum::<f64>());
+
+    let h: DTensor<f64, 2> = linalg::einsum_typed("ij,jk", &[&e, &f]).unwrap();
+
+    assert_eq!(h.shape(), &(4, 5));
+
+    assert_eq!(linalg::einsum("ij,jk->ix", &[&e, &f]), Err(EinsumError::InvalidSubscripts));
+    assert_eq!(linalg::einsum("ij->ii", &[&e]), Err(EinsumError::InvalidSubscripts));
+    assert_eq!(linalg::einsum("i.j", &[&x]), Err(EinsumError::InvalidSubscripts));
+    assert_eq!(linalg::einsum("...i->i", &[&e]), Err(EinsumError::InvalidSubscripts));
+    assert_eq!(
+        linalg::einsum("ij,jk", &[&e]),
+        Err(EinsumError::OperandCount { len: 1, expected: 2 })
+    );
+    assert_eq!(
+        linalg::einsum("ijk", &[&e]),
+        Err(EinsumError::RankMismatch { operand: 0, rank: 2, expected: 3 })
+    );
+    assert_eq!(
+        linalg::einsum("ij,jk", &[&e, &g]),
+        Err(EinsumError::DimensionMismatch { operand: 1, axis: 0, dim: 5, expected: 2 })
+    );
+    assert_eq!(
+        linalg::einsum_typed::<f64, (Dyn, Dyn), _, _>("ij,jk->i", &[&e, &f]),
+        Err(EinsumError::ShapeMismatch)
+    );
+
+    let mut a = tensor![[4.0, 2.0, 2.0], [2.0, 5.0, 3.0], [2.0, 3.0, 6.0]];
+
+    linalg::cholesky(&mut a).unwrap();
+
+    assert_eq!(a, tensor![[2.0, 0.0, 0.0], [1.0, 2.0, 0.0], [1.0, 1.0, 2.0]]);
+
+    let mut a = tensor![[1.0, 2.0], [2.0, 1.0]];
+
+    assert!(matches!(linalg::cholesky(&mut a), Err(LinalgError::NotPositiveDefinite(1))));
+
+    let a = tensor![[3.0, 1.0], [4.0, 2.0], [0.0, 5.0]];
+    let mut r = a.clone();
+    let tau = linalg::qr(&mut r);
+    let q = linalg::qr_q(&r, &tau[..]);
+
+    assert_eq!(q.shape(), &(3, 2));
+
+    for i in 0..3 {
+        for j in 0..2 {
+            let qr = (0..=j).map(|k| q[[i, k]] * r[[k, j]]).sum::<f64>();
+
+            assert!((qr - a[[i, j]]).abs() < 1e-12);
+        }
+    }
+
+    for i in 0..2 {
+        for j in 0..2 {
+            let qtq = (0..3).map(|k| q[[k, i]] * q[[k, j]]).sum::<f64>();
+
+            assert!((qtq - if i == j { 1.0 } else { 0.0 }).abs() < 1e-12);
+        }
+    }
+
+    let a = tensor![[1, 2, 3], [4, 5, 6]];
+
+    assert!(Triangular::new(&a, Triangle::Lower, Diag::NonUnit).iter().eq(&[1, 4, 5]));
+    assert!(Triangular::new(&a, Triangle::Lower, Diag::Unit).iter().eq(&[4]));
+    assert!(Triangular::new(&a, Triangle::Upper, Diag::Unit).iter().eq(&[2, 3, 6]));
+
+    let mut a = tensor![[0.0; 3]; 3];
+    let b = tensor![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]];
+
+    TriangularMut::new(&mut a, Triangle::Upper, Diag::NonUnit).assign(&b);
+    TriangularMut::new(&mut a, Triangle::Lower, Diag::Unit).fill(-1.0);
+
+    assert_eq!(a, tensor![[1.0, 2.0, 3.0], [-1.0, 5.0, 6.0], [-1.0, -1.0, 9.0]]);
+
+    let t = Triangular::new(&b, Triangle::Lower, Diag::Unit).to_tensor();
+
+    assert_eq!(t, tensor![[1.0, 0.0, 0.0], [4.0, 1.0, 0.0], [7.0, 8.0, 1.0]]);
+
+    let mut c = b.clone();
+
+    linalg::symmetrize(&mut c, Triangle::Lower);
+
+    assert_eq!(c, tensor![[1.0, 4.0, 7.0], [4.0, 5.0, 8.0], [7.0, 8.0, 9.0]]);
+
+    let a = tensor![[2.0, 1.0, 0.0], [0.0, 4.0, 2.0], [0.0, 0.0, 8.0]];
+    let mut x = tensor![[2.0, 0.0, 2.0], [0.0, 0.0, 8.0], [0.0, 0.0, 24.0]];
+
+    linalg::solve_triangular(
+        &a,
+        &mut x.view_mut(.., step(.., 2)),
+        Triangle::Upper,
+        Diag::NonUnit,
+        true,
+    )
+    .unwrap();
+
+    assert_eq!(x, tensor![[1.0, 0.0, 1.0], [-0.25, 0.0, 1.75], [0.0625, 0.0, 2.5625]]);
+
+    let mut x = tensor![3.0, 4.0, 8.0];
+
+    linalg::solve_triangular(&a, &mut x, Triangle::Upper, Diag::Unit, false).unwrap();
+
+    assert_eq!(x, tensor![15.0, -12.0, 8.0]);
+
+    let a = tensor![[1.0, 0.0, 0.0], [1.0, 0.0, 0.0], [1.0, 1.0, 1.0]];
+    let mut x = tensor![1.0, 1.0, 1.0];
+    let err = linalg::solve_triangular(&a, &mut x, Triangle::Lower, Diag::NonUnit, false);
+
+    assert!(matches!(err, Err(LinalgError::Singular(1))));
+
+    let a = tensor![[1.0, 0.0], [1.0, f64::NAN]];
+    let mut x = tensor![1.0, 1.0];
+    let err = linalg::solve_triangular(&a, &mut x, Triangle::Lower, Diag::NonUnit, false);
+
+    assert!(matches!(err, Err(LinalgError::Singular(1))));
+
+    let a = tensor![[1.0, -2.0, 2.0], [0.0, 3.0, -4.0]];
+
+    assert_eq!(linalg::norm(&a, Norm::L1), 12.0);
+    assert_eq!(linalg::norm(&a, Norm::L2), 34f64.sqrt());
+    assert_eq!(linalg::norm(&a, Norm::Inf), 4.0);
+    assert!((linalg::norm(&a, Norm::P(3.0)) - 108f64.cbrt()).abs() < 1e-12);
+    assert_eq!(linalg::norm(&a, Norm::P(1.0)), 12.0);
+    assert_eq!(linalg::norm(&tensor![[0.0; 2]; 2], Norm::P(4.0)), 0.0);
+    assert_eq!(linalg::norm(&Tensor::<f64, _>::from_elem([0], 0.0), Norm::L2), 0.0);
+
+    assert_eq!(linalg::norm(&tensor![3e-300, 4e-300], Norm::L2), 5e-300);
+    assert_eq!(linalg::norm(&tensor![3e300, 4e300], Norm::L2), 5e300);
+    let p3 = linalg::norm(&tensor![3e200, -4e200], Norm::P(3.0));
+
+    assert!((p3 / (91f64.cbrt() * 1e200) - 1.0).abs() < 1e-12);
+    assert_eq!(linalg::norm(&a, Norm::P(2.0)), linalg::norm(&a, Norm::L2));
+
+    assert_eq!(linalg::norm_axis(&a, Rows, Norm::L1), tensor![5.0, 7.0]);
+    assert_eq!(linalg::norm_axis(&a, Cols, Norm::Inf), tensor![1.0, 3.0, 4.0]);
+    assert_eq!(linalg::norm_axis(&a.view(.., 1..), Cols, Norm::L1), tensor![5.0, 6.0]);
+
+    let b = tensor![[[3.0, 4.0], [0.0, 1.0]], [[6.0, 8.0], [1.0, 0.0]]];
+
+    assert_eq!(linalg::norm_axis(&b, 2, Norm::L2), view![[5.0, 1.0], [10.0, 1.0]]);
+    assert_eq!(
+        linalg::norm_axis(&b.clone().into_dyn(), 0, Norm::L1),
+        tensor![[9.0, 12.0], [1.0, 1.0]].into_dyn()
+    );
+
+    assert_eq!(linalg::matrix_norm(&a, MatrixNorm::Frobenius), 34f64.sqrt());
+    assert_eq!(linalg::matrix_norm(&a, MatrixNorm::One), 6.0);
+    assert_eq!(linalg::matrix_norm(&a, MatrixNorm::Inf), 7.0);
+    assert_eq!(linalg::matrix_norm(&a.view(.., step(.., 2)), MatrixNorm::One), 6.0);
+
+    let c = tensor![[2.0f64, 0.0], [0.0, -3.0]];
+
+    assert!((linalg::spectral_norm(&c).unwrap() - 3.0).abs() < 1e-6);
+    assert!((linalg::spectral_norm(&a).unwrap() - (17.0 + 260f64.sqrt()).sqrt()).abs() < 1e-6);
+    assert_eq!(linalg::spectral_norm(&tensor![[0.0; 3]; 2]).unwrap(), 0.0);
+
+    let d = tensor![[1.0f64, -1.0], [1.0, -1.0]];
+
+    assert!((linalg::spectral_norm(&d).unwrap() - 2.0).abs() < 1e-6);
+    assert!(linalg::spectral_norm(&tensor![[1.0, f64::NAN]]).unwrap().is_nan());
+
+    let d = tensor![[1.0, 0.0], [0.0, 0.99]];
+
+    assert!(matches!(linalg::spectral_norm(&d), Err(LinalgError::NoConvergence)));
+
+    assert_eq!(linalg::norm(&tensor![f64::INFINITY, f64::INFINITY], Norm::L2), f64::INFINITY);
+    assert_eq!(linalg::norm(&tensor![f64::NAN, f64::INFINITY], Norm::L2), f64::INFINITY);
+    assert!(linalg::norm(&tensor![f64::NAN, 1.0], Norm::L2).is_nan());
+    assert!(linalg::norm(&tensor![f64::NAN, 1.0], Norm::Inf).is_nan());
+    assert!(linalg::norm(&tensor![1.0, f64::NAN], Norm::Inf).is_nan());
+    assert!(linalg::norm(&tensor![f64::NAN, 1.0], Norm::P(3.0)).is_nan());
+    assert_eq!(linalg::norm(&tensor![f64::INFINITY, 1.0], Norm::P(3.0)), f64::INFINITY);
+    assert!(linalg::matrix_norm(&tensor![[f64::NAN, 1.0]], MatrixNorm::Inf).is_nan());
+
+    assert_eq!(linalg::cond(&c).unwrap(), 1.5);
+    assert!(matches!(
+        linalg::cond(&tensor![[1.0, 2.0], [2.0, 4.0]]),
+        Err(LinalgError::Singular(1))
+    ));
+
+    let a = tensor![[1, 2], [3, 4]];
+    let b = tensor![[1, 0, -1]];
+
+    let ab = tensor![[1, 0, -1, 2, 0, -2], [3, 0, -3, 4, 0, -4]];
+    let ba = tensor![[1, 2, 0, 0, -1, -2], [3, 4, 0, 0, -3, -4]];
+
+    assert_eq!(linalg::kron(&a, &b), ab);
+    assert_eq!(linalg::kron(&b, &a), ba);
+    assert_eq!(linalg::kron_expr(&a.view(.., 1..), &b).eval(), view![[2, 0, -2], [4, 0, -4]]);
+    assert_eq!(linalg::kron(&tensor![1, 2], &tensor![1, 10]), tensor![1, 10, 2, 20]);
+    assert_eq!(linalg::kron(&a.view(..0, ..), &b).shape(), &(0, 6));
+
+    let c = tensor![[[1], [2]]];
+    let d = tensor![[[1, 1]], [[0, 1]]];
+
+    assert_eq!(linalg::kron(&c, &d), tensor![[[1, 1], [2, 2]], [[0, 1], [0, 2]]]);
+    assert_eq!(linalg::kron(&c.clone().into_dyn(), &d.into_dyn()).shape().dims(), [2, 2, 2]);
+
+    let o = linalg::outer(&tensor![1, 2], &a);
+
+    assert_eq!(o, tensor![[[1, 2], [3, 4]], [[2, 4], [6, 8]]].into_dyn());
+    assert_eq!(linalg::outer(&a, &tensor![1, -1]).shape().dims(), [2, 2, 2]);
+    assert_eq!(linalg::outer_expr(&view![2], &view![3]).eval(), tensor![[6]].into_dyn());
+    assert_eq!(linalg::outer_expr(&a, &c).map(|x| x / 2).eval()[[1, 1, 0, 1, 0]], 4);
+
+    let p = tensor![[2.0, 1.0], [0.0, 3.0]];
+    let p3 = tensor![[8.0, 19.0], [0.0, 27.0]];
+
+    assert_eq!(linalg::matrix_power(&p, 0), tensor![[1.0, 0.0], [0.0, 1.0]]);
+    assert_eq!(linalg::matrix_power(&p, 1), p);
+    assert_eq!(linalg::matrix_power(&p, 3), p3);
+    assert_eq!(
+        linalg::matrix_power(&p.view(.., ..).remap::<_, Strided>(), 6),
+        linalg::matmul(&p3, &p3)
+    );
+
+    let a = Tensor::from_fn((5, 5), |i| ((i[0] * 3 + i[1] * 5) % 7) as f64 - 3.0);
+    let mut b = tensor![[0.0; 5]; 10];
+
+    b.view_mut(step(.., 2), ..).assign(&a);
+
+    let (w, v) = linalg::eigh(&b.view(step(.., 2), ..)).unwrap();
+
+    assert!(w.iter().zip(w.iter().skip(1)).all(|(x, y)| x <= y));
+
+    for i in 0..5 {
+        for j in 0..5 {
+            let vwv = (0..5).map(|k| v[[i, k]] * w[k] * v[[j, k]]).sum::<f64>();
+            let vtv = (0..5).map(|k| v[[k, i]] * v[[k, j]]).sum::<f64>();
+
+            assert!((vwv - a[[i.max(j), i.min(j)]]).abs() < 1e-12);
+            assert!((vtv - if i == j { 1.0 } else { 0.0 }).abs() < 1e-12);
+        }
+    }
+
+    let (w, v) = linalg::eigh(&tensor![[1.0f32, 0.0], [0.0, -1.0]]).unwrap();
+
+    assert_eq!((w, v), (tensor![-1.0, 1.0], tensor![[0.0, 1.0], [1.0, 0.0]]));
+    assert_eq!(linalg::eigh(&tensor![[0.0; 0]; 0]).unwrap().0.len(), 0);
+
+    let c = Tensor::from_fn((4, 3), |i| ((i[0] * 2 + i[1] * 5) % 7) as f64 - 2.0);
+
+    for x in [c.clone(), Tensor::from_fn((3, 4), |i| c[[i[1], i[0]]])] {
+        let (m, n) = *x.shape();
+        let (u, s, vt) = linalg::svd(&x).unwrap();
+
+        assert_eq!((u.shape(), s.shape(), vt.shape()), (&(m, 3), &(3,), &(3, n)));
+        assert!(s.iter().zip(s.iter().skip(1)).all(|(x, y)| x >= y));
+
+        let usv = Tensor::from_fn((m, n), |i| {
+            (0..3).map(|k| u[[i[0], k]] * s[k] * vt[[k, i[1]]]).sum::<f64>()
+        });
+
+        assert!(linalg::norm(&(usv - &x), Norm::Inf) < 1e-12);
+        assert!(
+            linalg::norm(
+                &(linalg::matmul_hn(&u, &u) - linalg::matrix_power(&u.view(..3, ..3), 0)),
+                Norm::Inf
+            ) < 1e-12
+        );
+        assert!(
+            linalg::norm(
+                &(linalg::matmul_nh(&vt, &vt) - linalg::matrix_power(&vt.view(.., ..3), 0)),
+                Norm::Inf
+            ) < 1e-12
+        );
+        assert!((s[0] - linalg::spectral_norm(&x).unwrap()).abs() < 1e-6);
+    }
+
+    let d = tensor![[1.0f64, 2.0], [2.0, 4.0], [0.0, 0.0]];
+    let (u, s, _) = linalg::svd(&d).unwrap();
+
+    assert_eq!(s[1], 0.0);
+    assert!((s[0] - 5.0).abs() < 1e-14);
+    assert!(
+        linalg::norm(&(linalg::matmul_hn(&u, &u) - tensor![[1.0, 0.0], [0.0, 1.0]]), Norm::Inf)
+            < 1e-14
+    );
+
+    let (u, s, vt) = linalg::svd(&tensor![[0.0f32; 2]; 2]).unwrap();
+
+    assert_eq!(
+        (u, s, vt),
+        (tensor![[1.0, 0.0], [0.0, 1.0]], tensor![0.0, 0.0], tensor![[1.0, 0.0], [0.0, 1.0]])
+    );
+
+    assert_eq!(linalg::svd(&tensor![[3e200, 0.0], [0.0, 4e200]]).unwrap().1, tensor![4e200, 3e200]);
+    assert!(matches!(linalg::svd(&tensor![[1.0, f64::NAN]]), Err(LinalgError::NoConvergence)));
+    assert_eq!(linalg::svd(&tensor![[3e-200], [4e-200]]).unwrap().1, tensor![5e-200]);
+
+    let x = linalg::lstsq(&d, &tensor![[5.0], [10.0], [1.0]]).unwrap();
+
+    assert!(linalg::norm(&(x - tensor![[1.0], [2.0]]), Norm::Inf) < 1e-14);
+
+    let x = linalg::lstsq(&tensor![[1.0, 1.0]], &tensor![[2.0, 4.0]]).unwrap();
+
+    assert!(linalg::norm(&(x - tensor![[1.0, 2.0], [1.0, 2.0]]), Norm::Inf) < 1e-14);
+
+    let e = tensor![[2.0f32, 1.0], [1.0, 3.0]];
+    let x = linalg::lstsq(&e.view(.., ..).remap::<_, Strided>(), &tensor![[3.0], [5.0]]).unwrap();
+
+    assert!(
+        linalg::norm(&(x - linalg::solve(&e, &tensor![[3.0], [5.0]]).unwrap()), Norm::Inf) < 1e-6
+    );
+}
+
+#[test]
+fn test_macros() {
+    let array1: Array<usize, _> = array![];
+    let array2: Array<usize, _> = array![[]];
+    let array3: Array<usize, _> = array![[[]]];
+    let array4: Array<usize, _> = array![[[[]]]];
+    let array5: Array<usize, _> = array![[[[[]]]]];
+    let array6: Array<usize, _> = array![[[[[[]]]]]];
+
+    let tensor1: Tensor<usize, _> = tensor![];
+    let tensor2: Tensor<usize, _> = tensor![[]];
+    let tensor3: Tensor<usize, _> = tensor![[[]]];
+    let tensor4: Tensor<usize, _> = tensor![[[[]]]];
+    let tensor5: Tensor<usize, _> = tensor![[[[[]]]]];
+    let tensor6: Tensor<usize, _> = tensor![[[[[[]]]]]];
+
+    let view1: View<usize, _> = view![];
+    let view2: View<usize, _> = view![[]];
+    let view3: View<usize, _> = view![[[]]];
+    let view4: View<usize, _> = view![[[[]]]];
+    let view5: View<usize, _> = view![[[[[]]]]];
+    let view6: View<usize, _> = view![[[[[[]]]]]];
+
+    assert_eq!(array1.shape(), &(U0,));
+    assert_eq!(array2.shape(), &(U1, U0,));
+    assert_eq!(array3.shape(), &(U1, U1, U0,));
+    assert_eq!(array4.shape(), &(U1, U1, U1, U0,));
+    assert_eq!(array5.shape(), &(U1, U1, U1, U1, U0,));
+    assert_eq!(array6.shape(), &(U1, U1, U1, U1, U1, U0,));
+
+    assert_eq!(tensor1.shape(), &(0,));
+    assert_eq!(tensor2.shape(), &(1, 0));
+    assert_eq!(tensor3.shape(), &(1, 1, 0));
+    assert_eq!(tensor4.shape(), &(1, 1, 1, 0));
+    assert_eq!(tensor5.shape(), &(1, 1, 1, 1, 0));
+    assert_eq!(tensor6.shape(), &(1, 1, 1, 1, 1, 0));
+
+    assert_eq!(view1.shape(), &(0,));
+    assert_eq!(view2.shape(), &(1, 0));
+    assert_eq!(view3.shape(), &(1, 1, 0));
+    assert_eq!(view4.shape(), &(1, 1, 1, 0));
+    assert_eq!(view5.shape(), &(1, 1, 1, 1, 0));
+    assert_eq!(view6.shape(), &(1, 1, 1, 1, 1, 0));
+
+    assert_eq!(tensor![1, 2, 3], array![1, 2, 3]);
+    assert_eq!(tensor![[1, 2, 3], [4, 5, 6]], array![[1, 2, 3], [4, 5, 6]]);
+    assert_eq!(tensor![[[1, 2, 3], [4, 5, 6]]], array![[[1, 2, 3], [4, 5, 6]]]);
+    assert_eq!(tensor![[[[1, 2, 3], [4, 5, 6]]]], array![[[[1, 2, 3], [4, 5, 6]]]]);
+    assert_eq!(tensor![[[[[1, 2, 3], [4, 5, 6]]]]], array![[[[[1, 2, 3], [4, 5, 6]]]]]);
+    assert_eq!(tensor![[[[[[1, 2, 3], [4, 5, 6]]]]]], array![[[[[[1, 2, 3], [4, 5, 6]]]]]]);
+
+    assert_eq!(view![1, 2, 3], array![1, 2, 3]);
+    assert_eq!(view![[1, 2, 3], [4, 5, 6]], array![[1, 2, 3], [4, 5, 6]]);
+    assert_eq!(view![[[1, 2, 3], [4, 5, 6]]], array![[[1, 2, 3], [4, 5, 6]]]);
+    assert_eq!(view![[[[1, 2, 3], [4, 5, 6]]]], array![[[[1, 2, 3], [4, 5, 6]]]]);
+    assert_eq!(view![[[[[1, 2, 3], [4, 5, 6]]]]], array![[[[[1, 2, 3], [4, 5, 6]]]]]);
+    assert_eq!(view![[[[[[1, 2, 3], [4, 5, 6]]]]]], array![[[[[[1, 2, 3], [4, 5, 6]]]]]]);
+
+    assert_eq!(tensor![0; 1], array![0; 1]);
+    assert_eq!(tensor![[0; 1]; 2], array![[0; 1]; 2]);
+    assert_eq!(tensor![[[0; 1]; 2]; 3], array![[[0; 1]; 2]; 3]);
+    assert_eq!(tensor![[[[0; 1]; 2]; 3]; 4], array![[[[0; 1]; 2]; 3]; 4]);
+    assert_eq!(tensor![[[[[0; 1]; 2]; 3]; 4]; 5], array![[[[[0; 1]; 2]; 3]; 4]; 5]);
+    assert_eq!(tensor![[[[[[0; 1]; 2]; 3]; 4]; 5]; 6], array![[[[[[0; 1]; 2]; 3]; 4]; 5]; 6]);
+
+    assert_eq!(view![0; 1], array![0; 1]);
+    assert_eq!(view![[0; 1]; 2], array![[0; 1]; 2]);
+    assert_eq!(view![[[0; 1]; 2]; 3], array![[[0; 1]; 2]; 3]);
+    assert_eq!(view![[[[0; 1]; 2]; 3]; 4], array![[[[0; 1]; 2]; 3]; 4]);
+    assert_eq!(view![[[[[0; 1]; 2]; 3]; 4]; 5], array![[[[[0; 1]; 2]; 3]; 4]; 5]);
+    assert_eq!(view![[[[[[0; 1]; 2]; 3]; 4]; 5]; 6], array![[[[[[0; 1]; 2]; 3]; 4]; 5]; 6]);
+}
+
+#[test]
+fn test_mapping() {
+    let d = DenseMapping::new((U1, 2, U3));
+    let s = StridedMapping::new(DynRank::from_dims(&[1, 2, 3]), &[4, 5, 6]);
+
+    assert!(d.is_contiguous());
+    assert!(!s.is_empty());
+    assert_eq!(d.len(), 6);
+    assert_eq!(s.rank(), 3);
+
+    assert_eq!(d.dim(2), 3);
+    assert_eq!(s.dims(), [1, 2, 3]);
+    assert_eq!(d.stride(0), 6);
+    assert_eq!(s.strides(), [4, 5, 6]);
+
+    let x = format!("{:?}", d);
+    let y = format!("{:?}", s);
+
+    assert_eq!(x, "DenseMapping { shape: (Const(1), 2, Const(3)) }");
+    assert_eq!(y, "StridedMapping { shape: DynRank([1, 2, 3]), strides: [4, 5, 6] }");
+}
+
+#[cfg(feature = "matrix-market")]
+#[test]
+fn test_matrix_market() {
+    let text = "%%MatrixMarket matrix array real general\n% comment\n2 3\n1\n4\n2\n5\n3\n6\n";
+
+    assert_eq!(
+        matrix_market::read::<f64, _>(text.as_bytes()).unwrap(),
+        tensor![[1., 2., 3.], [4., 5., 6.]]
+    );
+
+    let text = "%%MatrixMarket matrix array integer symmetric\n2 2\n1\n2\n3\n";
+
+    assert_eq!(matrix_market::read::<i32, _>(text.as_bytes()).unwrap(), tensor![[1, 2], [2, 3]]);
+
+    let text = "%%MatrixMarket matrix array real skew-symmetric\n3 3\n1 2\n3\n";
+
+    assert_eq!(
+        matrix_market::read::<f32, _>(text.as_bytes()).unwrap(),
+        tensor![[0., -1., -2.], [1., 0., -3.], [2., 3., 0.]]
+    );
+
+    let text = "%%MatrixMarket matrix coordinate real general\n2 3 3\n1 3 1.5\n2 1 2\n1 3 1\n";
+
+    assert_eq!(
+        matrix_market::read::<f64, _>(text.as_bytes()).unwrap(),
+        tensor![[0., 0., 2.5], [2., 0., 0.]]
+    );
+
+    let text = "%%MatrixMarket matrix coordinate pattern symmetric\n3 3 2\n2 1\n3 3\n";
+
+    assert_eq!(
+        matrix_market::read::<i64, _>(text.as_bytes()).unwrap(),
+        tensor![[0, 1, 0], [1, 0, 0], [0, 0, 1]]
+    );
+
+    let a = tensor![[0, -1, -2], [1, 0, -3], [2, 3, 0]];
+
+    for format in [Format::Array, Format::Coordinate] {
+        for symmetry in [Symmetry::General, Symmetry::SkewSymmetric] {
+            let mut buffer = Vec::new();
+
+            matrix_market::write(&a, &mut buffer, format, symmetry).unwrap();
+
+            assert_eq!(matrix_market::read::<i32, _>(&buffer[..]).unwrap(), a);
+        }
+    }
+
+    let mut buffer = Vec::new();
+
+    matrix_market::write(&a.view(.., 1..), &mut buffer, Format::Array, Symmetry::General).unwrap();
+
+    let text = "%%MatrixMarket matrix array integer general\n3 2\n-1\n0\n3\n-2\n-3\n0\n";
+
+    assert_eq!(String::from_utf8(buffer).unwrap(), text);
+
+    let r = matrix_market::write(&a, &mut Vec::new(), Format::Array, Symmetry::Symmetric);
+
+    assert!(matches!(r, Err(MatrixMarketError::SymmetryMismatch)));
+
+    let r = matrix_market::write(
+        &a.view(.., 1..),
+        &mut Vec::new(),
+        Format::Array,
+        Symmetry::SkewSymmetric,
+    );
+
+    assert!(matches!(r, Err(MatrixMarketError::SymmetryMismatch)));
+
+    let text = "%%MatrixMarket matrix coordinate complex general\n1 1 1\n1 1 1 0\n";
+    let r = matrix_market::read::<f64, _>(text.as_bytes());
+
+    assert!(matches!(r, Err(MatrixMarketError::InvalidHeader)));
+
+    let text = "%%MatrixMarket matrix coordinate real general\n4294967296 1073741824 0\n";
+    let r = matrix_market::read::<f64, _>(text.as_bytes());
+
+    assert!(matches!(r, Err(MatrixMarketError::InvalidHeader)));
+
+    let text = "%%MatrixMarket matrix coordinate real general\n100000000 100000000 0\n";
+    let r = matrix_market::read::<f64, _>(text.as_bytes());
+
+    assert!(matches!(r, Err(MatrixMarketError::Allocation(_))));
+
+    let text = "%%MatrixMarket matrix array real general\n2 2\n1\n2\n3\n";
+    let r = matrix_market::read::<f64, _>(text.as_bytes());
+
+    assert!(matches!(r, Err(MatrixMarketError::MissingEntries)));
+
+    let text = "%%MatrixMarket matrix array real general\n1 1\n1\n2\n";
+    let r = matrix_market::read::<f64, _>(text.as_bytes());
+
+    assert!(matches!(r, Err(MatrixMarketError::InvalidEntry { line: 4 })));
+
+    let text = "%%MatrixMarket matrix coordinate integer general\n2 2 2\n1 1 1\n\n3 1 1\n";
+    let r = matrix_market::read::<i32, _>(text.as_bytes());
+
+    assert!(matches!(r, Err(MatrixMarketError::InvalidEntry { line: 5 })));
+
+    let text = "%%MatrixMarket matrix coordinate real general\n2 2 1\n1 1 1.5\n";
+    let r = matrix_market::read::<i32, _>(text.as_bytes());
+
+    assert!(matches!(r, Err(MatrixMarketError::Parse { line: 3 })));
+}
+
+#[test]
+fn test_ops() {
+    let mut a = DTensor::<i32, 2>::from([[1, 2, 3], [4, 5, 6]]);
+    let b = DTensor::<i32, 2>::from([[9, 8, 7], [6, 5, 4]]);
+
+    a -= expr::fill(1);
+    a -= &b;
+    a -= b.expr();
+
+    *a -= expr::fill(1);
     *a -= &b;
     *a -= b.expr();
 
@@ -551,44 +1630,148 @@ fn test_ops() {
     a = a - &b;
     a = a - b.expr();
 
-    a = expr::fill(1) - a;
-    a = &b - a;
-    a = b.expr() - a;
+    a = expr::fill(1) - a;
+    a = &b - a;
+    a = b.expr() - a;
+
+    assert_eq!(a, tensor![[57, 50, 43], [36, 29, 22]]);
+
+    let mut a = a.into_shape([2, 3].as_slice());
+
+    a = (&a - &b).eval();
+    a = (&a - b.expr()).eval();
+    a = (a.expr() - &b).eval();
+    a = (a.expr() - b.expr()).eval();
+
+    assert_eq!(a, tensor![[21, 18, 15], [12, 9, 6]]);
+
+    a = (&a - expr::fill(1)).eval();
+    a = (a.expr() - expr::fill(1)).eval();
+
+    a = (expr::fill(1) - &a).eval();
+    a = (expr::fill(1) - a.expr()).eval();
+
+    assert_eq!(a, tensor![[19, 16, 13], [10, 7, 4]]);
+
+    a = -a;
+    a = (-&a).eval();
+    a = (-a.expr()).eval();
+
+    assert_eq!(a, tensor![[-19, -16, -13], [-10, -7, -4]]);
+
+    assert!(a == a && *a == a && a == *a && *a == *a);
+    assert!(a == a.expr() && a.expr() == a && a.expr() == a.expr());
+    assert!(a == a.clone().expr_mut() && a.clone().expr_mut() == a);
+    assert!(a.clone().expr_mut() == a.clone().expr_mut());
+
+    let c = expr::fill_with(|| 1usize) + expr::from_elem([3, 2], 4);
+    let c = c + expr::from_fn([3, 2], |x| x[0] + x[1]);
+
+    assert_eq!(c.eval(), tensor![[5, 6], [6, 7], [7, 8]]);
+
+    let d = expr::from_fn(DynRank::from_dims(&[2, 2]), |x| 2 * x[0] + x[1]);
+
+    assert_eq!(d.eval(), tensor![[0, 1], [2, 3]].into_dyn());
+
+    let e = tensor![[1, 2], [3, 4]];
+
+    assert_eq!(e.view(..0, step(.., 2)).iter().count(), 0);
+    assert_eq!(expr::from_fn([0, 3], |x| x[0]).eval().shape(), &(0, 3));
+}
+
+#[cfg(feature = "safetensors")]
+#[test]
+fn test_safetensors() {
+    let a = tensor![[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]];
+    let b = tensor![[1i64, 2, 3], [4, 5, 6]];
+    let c = tensor![true, false];
+
+    let mut writer = Writer::new();
+
+    writer.add("a", &a).unwrap();
+    writer.add("b", &b.view(.., 1..)).unwrap();
+    writer.add("c", &c).unwrap();
+    writer.add("d", &Tensor::<u8>::from_elem(&[2, 0][..], 0)).unwrap();
+    writer.add("e", &tensor![1.5f64].into_shape(())).unwrap();
+    writer.add_metadata("format", "pt");
+
+    assert!(matches!(writer.add("a", &c), Err(SafeTensorsError::InvalidName(_))));
+    assert!(matches!(writer.add("__metadata__", &c), Err(SafeTensorsError::InvalidName(_))));
+
+    let buffer = writer.finish();
+
+    assert_eq!(u64::from_le_bytes(buffer[..8].try_into().unwrap()) % 8, 0);
+
+    // Copy to an aligned buffer for array views.
+    let mut aligned = vec![0u64; buffer.len().div_ceil(8)];
+    let aligned = unsafe {
+        let ptr = aligned.as_mut_ptr().cast::<u8>();
+
+        ptr.copy_from_nonoverlapping(buffer.as_ptr(), buffer.len());
+        std::slice::from_raw_parts(ptr, buffer.len())
+    };
+
+    let st = SafeTensors::parse(aligned).unwrap();
+
+    assert_eq!(st.len(), 5);
+    assert_eq!(st.names().collect::<Vec<_>>(), ["a", "b", "c", "d", "e"]);
+    assert_eq!(st.metadata().collect::<Vec<_>>(), [("format", "pt")]);
+
+    assert_eq!(
+        st.info("b"),
+        Some(&TensorInfo {
+            dtype: Dtype::I64,
+            shape: DynRank::from_dims(&[2, 2]),
+            data_offsets: (0, 32)
+        })
+    );
+
+    assert_eq!(st.tensor::<f32>("a").unwrap(), a);
+    assert_eq!(st.tensor::<i64>("b").unwrap(), tensor![[2, 3], [5, 6]]);
+    assert_eq!(st.tensor::<bool>("c").unwrap(), c);
+    assert_eq!(st.tensor::<u8>("d").unwrap().dims(), [2, 0]);
+    assert_eq!(st.tensor::<f64>("e").unwrap()[[]], 1.5);
+
+    assert_eq!(st.view::<f32>("a").unwrap(), a);
+    assert_eq!(st.view::<i64>("b").unwrap(), tensor![[2, 3], [5, 6]]);
+    assert_eq!(st.view::<u8>("d").unwrap().dims(), [2, 0]);
+    assert_eq!(st.bytes("c").unwrap(), [1, 0]);
 
-    assert_eq!(a, tensor![[57, 50, 43], [36, 29, 22]]);
+    assert!(matches!(st.tensor::<f64>("a"), Err(SafeTensorsError::DataTypeMismatch(Dtype::F32))));
+    assert!(matches!(st.view::<f32>("x"), Err(SafeTensorsError::NotFound(_))));
 
-    let mut a = a.into_shape([2, 3].as_slice());
+    let header = br#"{"x":{"dtype":"U8","shape":[1],"data_offsets":[0,1]},"y":{"dtype":"U16","shape":[2],"data_offsets":[1,5]},"__metadata__":{"k\u00e9\"":"v"}}"#;
+    let mut buffer = Vec::new();
 
-    a = (&a - &b).eval();
-    a = (&a - b.expr()).eval();
-    a = (a.expr() - &b).eval();
-    a = (a.expr() - b.expr()).eval();
+    buffer.extend_from_slice(&(header.len() as u64).to_le_bytes());
+    buffer.extend_from_slice(header);
+    buffer.extend_from_slice(&[7, 1, 2, 3, 4]);
 
-    assert_eq!(a, tensor![[21, 18, 15], [12, 9, 6]]);
+    let st = SafeTensors::parse(&buffer).unwrap();
 
-    a = (&a - expr::fill(1)).eval();
-    a = (a.expr() - expr::fill(1)).eval();
+    assert_eq!(st.metadata().collect::<Vec<_>>(), [("k\u{e9}\"", "v")]);
+    assert_eq!(st.tensor::<u8>("x").unwrap(), tensor![7]);
+    assert_eq!(st.tensor::<u16>("y").unwrap(), tensor![0x201, 0x403]);
 
-    a = (expr::fill(1) - &a).eval();
-    a = (expr::fill(1) - a.expr()).eval();
+    let st = SafeTensors::parse(&buffer[..buffer.len() - 1]);
 
-    assert_eq!(a, tensor![[19, 16, 13], [10, 7, 4]]);
+    assert!(matches!(st, Err(SafeTensorsError::InvalidOffsets(_))));
 
-    a = -a;
-    a = (-&a).eval();
-    a = (-a.expr()).eval();
+    let st = SafeTensors::parse(&buffer[..20]);
 
-    assert_eq!(a, tensor![[-19, -16, -13], [-10, -7, -4]]);
+    assert!(matches!(st, Err(SafeTensorsError::InvalidHeader)));
 
-    assert!(a == a && *a == a && a == *a && *a == *a);
-    assert!(a == a.expr() && a.expr() == a && a.expr() == a.expr());
-    assert!(a == a.clone().expr_mut() && a.clone().expr_mut() == a);
-    assert!(a.clone().expr_mut() == a.clone().expr_mut());
+    let header = br#"{"x":{"dtype":"BOOL","shape":[2],"data_offsets":[0,2]}}"#;
+    let mut buffer = Vec::new();
 
-    let c = expr::fill_with(|| 1usize) + expr::from_elem([3, 2], 4);
-    let c = c + expr::from_fn([3, 2], |x| x[0] + x[1]);
+    buffer.extend_from_slice(&(header.len() as u64).to_le_bytes());
+    buffer.extend_from_slice(header);
+    buffer.extend_from_slice(&[1, 2]);
 
-    assert_eq!(c.eval(), tensor![[5, 6], [6, 7], [7, 8]]);
+    let st = SafeTensors::parse(&buffer).unwrap();
+
+    assert!(matches!(st.tensor::<bool>("x"), Err(SafeTensorsError::InvalidData)));
+    assert!(matches!(st.view::<bool>("x"), Err(SafeTensorsError::InvalidData)));
 }
 
 #[cfg(feature = "serde")]
@@ -669,6 +1852,466 @@ fn test_serde() {
             Token::SeqEnd,
         ],
     );
+
+    assert_tokens(&Tensor::<i32>::from_elem(&[][..], 123), &[Token::I32(123)]);
+    assert_tokens(&Tensor::<i32>::new(), &[Token::Seq { len: Some(0) }, Token::SeqEnd]);
+
+    assert_tokens(
+        &tensor![[1, 2], [3, 4]].into_dyn(),
+        &[
+            Token::Seq { len: Some(2) },
+            Token::Seq { len: Some(2) },
+            Token::I32(1),
+            Token::I32(2),
+            Token::SeqEnd,
+            Token::Seq { len: Some(2) },
+            Token::I32(3),
+            Token::I32(4),
+            Token::SeqEnd,
+            Token::SeqEnd,
+        ],
+    );
+
+    let t = tensor![[Some(1), None], [Some(3), Some(4)]].into_dyn();
+
+    assert_tokens(
+        &t,
+        &[
+            Token::Seq { len: Some(2) },
+            Token::Seq { len: Some(2) },
+            Token::Some,
+            Token::I32(1),
+            Token::None,
+            Token::SeqEnd,
+            Token::Seq { len: Some(2) },
+            Token::Some,
+            Token::I32(3),
+            Token::Some,
+            Token::I32(4),
+            Token::SeqEnd,
+            Token::SeqEnd,
+        ],
+    );
+
+    assert_de_tokens(
+        &t,
+        &[
+            Token::Seq { len: Some(2) },
+            Token::Seq { len: Some(2) },
+            Token::I32(1),
+            Token::Unit,
+            Token::SeqEnd,
+            Token::Seq { len: Some(2) },
+            Token::I32(3),
+            Token::I32(4),
+            Token::SeqEnd,
+            Token::SeqEnd,
+        ],
+    );
+
+    assert_de_tokens(
+        &Tensor::<i32>::from_elem(&[2, 0][..], 0),
+        &[
+            Token::Seq { len: Some(2) },
+            Token::Seq { len: Some(0) },
+            Token::SeqEnd,
+            Token::Seq { len: Some(0) },
+            Token::SeqEnd,
+            Token::SeqEnd,
+        ],
+    );
+
+    assert_de_tokens_error::<Tensor<i32>>(
+        &[
+            Token::Seq { len: Some(2) },
+            Token::Seq { len: Some(1) },
+            Token::I32(1),
+            Token::SeqEnd,
+            Token::Seq { len: Some(2) },
+            Token::I32(2),
+            Token::I32(3),
+            Token::SeqEnd,
+        ],
+        "invalid dimension 2, expected 1 at index [1]",
+    );
+
+    assert_de_tokens_error::<Tensor<i32>>(
+        &[Token::Seq { len: Some(2) }, Token::I32(1), Token::Seq { len: Some(0) }],
+        "invalid depth 2, expected 1 at index [1]",
+    );
+
+    assert_de_tokens_error::<Tensor<i32>>(
+        &[Token::Seq { len: Some(2) }, Token::Seq { len: Some(0) }, Token::SeqEnd, Token::I32(1)],
+        "invalid depth 1, expected 2 at index [1]",
+    );
+
+    assert_de_tokens_error::<DTensor<i32, 3>>(
+        &[
+            Token::Seq { len: Some(1) },
+            Token::Seq { len: Some(2) },
+            Token::Seq { len: Some(2) },
+            Token::I32(1),
+            Token::I32(2),
+            Token::SeqEnd,
+            Token::Seq { len: Some(1) },
+            Token::I32(3),
+            Token::SeqEnd,
+        ],
+        "invalid dimension 1, expected 2 at index [0, 1]",
+    );
+
+    assert_de_tokens_error::<DTensor<i32, 2>>(
+        &[Token::Seq { len: Some(1) }, Token::I32(1)],
+        "invalid type: integer `1`, expected an array of rank 1",
+    );
+
+    assert_de_tokens_error::<Array<i32, (U2, U3)>>(
+        &[
+            Token::Seq { len: Some(2) },
+            Token::Seq { len: Some(2) },
+            Token::I32(1),
+            Token::I32(2),
+            Token::SeqEnd,
+        ],
+        "invalid dimension 2, expected 3 at index [0]",
+    );
+
+    assert_de_tokens_error::<Array<i32, (U2, U0)>>(
+        &[Token::Seq { len: Some(1) }, Token::Seq { len: Some(0) }, Token::SeqEnd, Token::SeqEnd],
+        "invalid dimension 1, expected 2",
+    );
+
+    assert_tokens(
+        &DynRank::from_dims(&[2, 3]),
+        &[Token::Seq { len: Some(2) }, Token::U64(2), Token::U64(3), Token::SeqEnd],
+    );
+
+    assert_tokens(
+        &(Const::<2>, 3usize),
+        &[Token::Tuple { len: 2 }, Token::U64(2), Token::U64(3), Token::TupleEnd],
+    );
+
+    assert_de_tokens_error::<U2>(&[Token::U64(3)], "invalid dimension 3, expected 2");
+
+    assert_tokens(
+        &DenseMapping::new((Const::<2>, 3usize)),
+        &[
+            Token::NewtypeStruct { name: "DenseMapping" },
+            Token::Tuple { len: 2 },
+            Token::U64(2),
+            Token::U64(3),
+            Token::TupleEnd,
+        ],
+    );
+
+    assert_tokens(
+        &StridedMapping::new(DynRank::from_dims(&[2, 3]), &[1, 2]),
+        &[
+            Token::Struct { name: "StridedMapping", len: 2 },
+            Token::Str("shape"),
+            Token::Seq { len: Some(2) },
+            Token::U64(2),
+            Token::U64(3),
+            Token::SeqEnd,
+            Token::Str("strides"),
+            Token::Seq { len: Some(2) },
+            Token::I64(1),
+            Token::I64(2),
+            Token::SeqEnd,
+            Token::StructEnd,
+        ],
+    );
+
+    assert_de_tokens_error::<StridedMapping<(usize, usize)>>(
+        &[
+            Token::Struct { name: "StridedMapping", len: 2 },
+            Token::Str("shape"),
+            Token::Tuple { len: 2 },
+            Token::U64(2),
+            Token::U64(3),
+            Token::TupleEnd,
+            Token::Str("strides"),
+            Token::Seq { len: Some(1) },
+            Token::I64(1),
+            Token::SeqEnd,
+            Token::StructEnd,
+        ],
+        "invalid rank 1, expected 2",
+    );
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct CompactArray(#[serde(with = "mdarray::serde::compact")] Array<i32, (U2, U3)>);
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct CompactTensor(#[serde(with = "mdarray::serde::compact")] Tensor<i32>);
+
+    assert_tokens(
+        &CompactArray(Array([[1, 2, 3], [4, 5, 6]])),
+        &[
+            Token::NewtypeStruct { name: "CompactArray" },
+            Token::Struct { name: "Compact", len: 2 },
+            Token::Str("shape"),
+            Token::Seq { len: Some(2) },
+            Token::U64(2),
+            Token::U64(3),
+            Token::SeqEnd,
+            Token::Str("data"),
+            Token::Seq { len: Some(6) },
+            Token::I32(1),
+            Token::I32(2),
+            Token::I32(3),
+            Token::I32(4),
+            Token::I32(5),
+            Token::I32(6),
+            Token::SeqEnd,
+            Token::StructEnd,
+        ],
+    );
+
+    assert_tokens(
+        &CompactTensor(Tensor::from_elem(&[][..], 123)),
+        &[
+            Token::NewtypeStruct { name: "CompactTensor" },
+            Token::Struct { name: "Compact", len: 2 },
+            Token::Str("shape"),
+            Token::Seq { len: Some(0) },
+            Token::SeqEnd,
+            Token::Str("data"),
+            Token::Seq { len: Some(1) },
+            Token::I32(123),
+            Token::SeqEnd,
+            Token::StructEnd,
+        ],
+    );
+
+    assert_de_tokens(
+        &CompactTensor(tensor![[1], [2]].into_dyn()),
+        &[
+            Token::NewtypeStruct { name: "CompactTensor" },
+            Token::Map { len: Some(2) },
+            Token::Str("data"),
+            Token::Seq { len: Some(2) },
+            Token::I32(1),
+            Token::I32(2),
+            Token::SeqEnd,
+            Token::Str("shape"),
+            Token::Seq { len: Some(2) },
+            Token::U64(2),
+            Token::U64(1),
+            Token::SeqEnd,
+            Token::MapEnd,
+        ],
+    );
+
+    assert_de_tokens(
+        &CompactTensor(Tensor::from_elem(&[2, 0, 3][..], 0)),
+        &[
+            Token::NewtypeStruct { name: "CompactTensor" },
+            Token::Seq { len: Some(2) },
+            Token::Seq { len: Some(3) },
+            Token::U64(2),
+            Token::U64(0),
+            Token::U64(3),
+            Token::SeqEnd,
+            Token::Seq { len: Some(0) },
+            Token::SeqEnd,
+            Token::SeqEnd,
+        ],
+    );
+
+    assert_de_tokens_error::<CompactArray>(
+        &[
+            Token::NewtypeStruct { name: "CompactArray" },
+            Token::Struct { name: "Compact", len: 2 },
+            Token::Str("shape"),
+            Token::Seq { len: Some(2) },
+            Token::U64(3),
+            Token::U64(2),
+            Token::SeqEnd,
+        ],
+        "invalid dimension 3, expected 2",
+    );
+
+    assert_de_tokens_error::<CompactArray>(
+        &[
+            Token::NewtypeStruct { name: "CompactArray" },
+            Token::Struct { name: "Compact", len: 2 },
+            Token::Str("shape"),
+            Token::Seq { len: Some(1) },
+            Token::U64(6),
+            Token::SeqEnd,
+        ],
+        "invalid rank 1, expected 2",
+    );
+
+    assert_de_tokens_error::<CompactTensor>(
+        &[
+            Token::NewtypeStruct { name: "CompactTensor" },
+            Token::Struct { name: "Compact", len: 2 },
+            Token::Str("shape"),
+            Token::Seq { len: Some(1) },
+            Token::U64(1),
+            Token::SeqEnd,
+            Token::Str("data"),
+            Token::Seq { len: Some(2) },
+            Token::I32(1),
+            Token::I32(2),
+        ],
+        "invalid length 2, expected a sequence of 1 elements",
+    );
+
+    assert_de_tokens_error::<CompactTensor>(
+        &[
+            Token::NewtypeStruct { name: "CompactTensor" },
+            Token::Map { len: Some(2) },
+            Token::Str("data"),
+            Token::Seq { len: Some(1) },
+            Token::I32(1),
+            Token::SeqEnd,
+            Token::Str("shape"),
+            Token::Seq { len: Some(1) },
+            Token::U64(2),
+            Token::SeqEnd,
+            Token::MapEnd,
+        ],
+        "invalid length 1, expected 2",
+    );
+
+    assert_de_tokens_error::<CompactTensor>(
+        &[
+            Token::NewtypeStruct { name: "CompactTensor" },
+            Token::Struct { name: "Compact", len: 2 },
+            Token::Str("shape"),
+            Token::Seq { len: Some(1) },
+            Token::U64(1 << 42),
+            Token::SeqEnd,
+            Token::Str("data"),
+            Token::Seq { len: Some(1 << 42) },
+            Token::I32(1),
+            Token::SeqEnd,
+            Token::StructEnd,
+        ],
+        "invalid length 1, expected 4398046511104",
+    );
+
+    assert_de_tokens_error::<CompactTensor>(
+        &[
+            Token::NewtypeStruct { name: "CompactTensor" },
+            Token::Struct { name: "Compact", len: 2 },
+            Token::Str("shape"),
+            Token::Seq { len: Some(2) },
+            Token::U64(u64::MAX),
+            Token::U64(2),
+            Token::SeqEnd,
+        ],
+        "array size overflow",
+    );
+}
+
+#[test]
+fn test_small_tensor() {
+    let a = SmallTensor::<i32, 9, Rank<2>>::from_fn([3, 3], |i| (3 * i[0] + i[1]) as i32);
+    let b = Box::new(a.clone());
+
+    assert!(b.is_inline() && b.capacity() == 9);
+    assert_eq!(*b, view![[0, 1, 2], [3, 4, 5], [6, 7, 8]]);
+    assert_eq!(b.view(1.., ..2), view![[3, 4], [6, 7]]);
+
+    let c = (*b).clone().map(|x| 2 * x);
+    let d = (&c + &a).eval();
+
+    assert_eq!(d, view![[0, 3, 6], [9, 12, 15], [18, 21, 24]]);
+    assert_eq!(format!("{:?}", c.into_shape([9])), "[0, 2, 4, 6, 8, 10, 12, 14, 16]");
+
+    let e = SmallTensor::<String, 4>::from_elem(&[2, 3][..], "x".to_string());
+
+    assert!(!e.is_inline() && e.capacity() >= 6);
+    assert_eq!(e.rank(), 2);
+    assert_eq!(e.clone().into_tensor(), Tensor::from_elem([2, 3], "x"));
+    assert_eq!(e.into_iter().collect::<String>(), "xxxxxx");
+
+    let mut f = SmallTensor::<_, 4, (Dyn,)>::from(&view![1, 2, 3]);
+    let g = f.clone();
+
+    f.clone_from_slice(&view![4, 5, 6, 7, 8]);
+
+    assert!(!f.is_inline() && f.apply(|x| x + 1) == view![5, 6, 7, 8, 9]);
+    assert_eq!(g.expr().zip(&g).map(|(x, y)| x * y).eval(), view![1, 4, 9]);
+
+    let h: SmallTensor<_, 4, _> = FromExpression::from_expr(expr::from_elem([2, 2], 0));
+
+    assert!(h.is_inline() && h == Tensor::<i32, (Dyn, Dyn)>::from_elem([2, 2], 0));
+    assert!(SmallTensor::<(), 0>::from_elem(&[5][..], ()).is_inline());
+
+    let s = SmallTensor::<i32, 4, (Dyn,)>::from(&view![1, 2, 3]);
+
+    thread::scope(|t| {
+        for _ in 0..2 {
+            _ = t.spawn(|| assert_eq!(s, view![1, 2, 3]));
+        }
+    });
+}
+
+#[test]
+fn test_stack_tensor() {
+    let mut a = StackTensor::<usize, 12, (Dyn, Dyn)>::from_fn([2, 3], |i| 3 * i[0] + i[1]);
+
+    assert!(a.capacity() == 12 && a.remaining_capacity() == 6);
+    assert_eq!(a, view![[0, 1, 2], [3, 4, 5]]);
+
+    a.expand(&view![[6, 7, 8]]).unwrap();
+    a.expand(expr::from_elem([1, 3], 9)).unwrap();
+
+    assert!(a.is_full());
+    assert!(matches!(a.expand(&view![[0, 0, 0]]), Err(TensorError::CapacityExceeded(15))));
+    assert_eq!(a.view(2.., ..), view![[6, 7, 8], [9, 9, 9]]);
+
+    a.truncate(1).unwrap();
+
+    let b = Box::new(a);
+
+    assert_eq!(*b, view![[0, 1, 2]]);
+    assert_eq!((&*b + &view![[1, 1, 1]]).eval(), view![[1, 2, 3]]);
+
+    let mut c = StackTensor::<String, 12, (Dyn, Dyn, Dyn)>::from_fn([2, 3, 2], |i| {
+        i.iter().map(|x| x.to_string()).collect()
+    });
+
+    c.resize(&[3, 2, 1], "x".to_string()).unwrap();
+
+    assert_eq!(c, view![[["000"], ["010"]], [["100"], ["110"]], [["x"], ["x"]]]);
+
+    c.resize_with(&[1, 3, 4], || "y".to_string()).unwrap();
+
+    assert_eq!(c, view![[["000", "y", "y", "y"], ["010", "y", "y", "y"], ["y", "y", "y", "y"]]]);
+    assert!(matches!(c.resize(&[2, 3, 4], String::new()), Err(TensorError::CapacityExceeded(24))));
+    assert_eq!(c.clone().into_tensor(), c);
+
+    c.clear().unwrap();
+
+    assert!(c.is_empty() && c.rank() == 3);
+
+    let mut d = StackTensor::<i32, 4, (Const<2>, Dyn)>::new();
+
+    assert!(matches!(d.expand(&view![[1], [2]]), Err(TensorError::FirstDimNotDyn(2))));
+    assert!(matches!(d.truncate(0), Err(TensorError::FirstDimNotDyn(2))));
+
+    d.resize(&[2, 2], 1).unwrap();
+
+    assert_eq!(d.map(|x| x + 1).into_iter().sum::<i32>(), 8);
+
+    let mut e = StackTensor::<i32, 8, (Dyn,)>::from(&view![1, 2, 3]);
+
+    e.expand(&view![4, 5]).unwrap();
+
+    assert_eq!(e, view![1, 2, 3, 4, 5]);
+    assert_eq!(e.clone().into_shape([5, 1]), view![[1], [2], [3], [4], [5]]);
+
+    thread::scope(|t| {
+        for _ in 0..2 {
+            _ = t.spawn(|| assert_eq!(e, view![1, 2, 3, 4, 5]));
+        }
+    });
 }
 
 #[test]